@@ -9,30 +9,76 @@ use enigma_3d::light::{Light, LightEmissionType};
 use enigma_3d::object::Object;
 use uuid::Uuid;
 
-use crate::editor::state::{EditorRoot, JobMessage, JobOutcome, MaterialDef, ProjectState, RunningJob};
+use crate::editor::diagnostics::{self, CargoLine};
+use crate::editor::dock::Tab;
+use crate::editor::logging::{self, Source};
+use crate::editor::{cursor_3d, lights};
+use crate::editor::state::{
+    Diagnostic, EditorRoot, JobKind, JobMessage, JobOutcome, JobRecord, MaterialDef, ParticleInstance, ProjectState,
+    RunningJob, Selection,
+};
 use crate::project;
 
 pub fn run_project(app_state: &mut AppState) {
-    if is_busy(app_state) { return; }
-    let Some(project) = save_before_run(app_state) else { return; };
-    start_cargo(app_state, &project, "Run", vec!["run".into()]);
+    submit(app_state, JobKind::Run);
 }
 
 pub fn build_project(app_state: &mut AppState, release: bool) {
-    if is_busy(app_state) { return; }
-    let Some(project) = save_before_run(app_state) else { return; };
-    let (label, args) = if release {
-        ("Release Build".to_string(), vec!["build".into(), "--release".into()])
-    } else {
-        ("Debug Build".to_string(), vec!["build".into()])
-    };
-    start_cargo(app_state, &project, &label, args);
+    submit(app_state, JobKind::Build { release });
 }
 
 pub fn update_dependencies(app_state: &mut AppState) {
-    if is_busy(app_state) { return; }
-    let Some(project) = save_before_run(app_state) else { return; };
-    start_cargo(app_state, &project, "Update Dependencies", vec!["update".into()]);
+    submit(app_state, JobKind::UpdateDependencies);
+}
+
+/// Start `kind` now, or queue it behind the running job.
+pub fn submit(app_state: &mut AppState, kind: JobKind) {
+    if is_busy(app_state) {
+        if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            r.editor.job_queue.push_back(kind);
+        }
+        return;
+    }
+    start_job(app_state, kind);
+}
+
+fn start_job(app_state: &mut AppState, kind: JobKind) {
+    let Some(project) = save_before_run(app_state) else {
+        // Later jobs depend on this one, like a failed build.
+        if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            if !r.editor.job_queue.is_empty() {
+                r.editor.job_queue.clear();
+                logging::warn(Source::Build, format!("{} was not started, queued jobs dropped", kind.label()));
+            }
+        }
+        return;
+    };
+    let json = diagnostics::MESSAGE_FORMAT_ARG;
+    let mut label = kind.label().to_string();
+    let mut env = Vec::new();
+    let args: Vec<String> = match kind {
+        JobKind::Run => {
+            let config = project.active_run_config
+                .and_then(|uuid| project.run_configs.iter().find(|c| c.uuid == uuid));
+            let args = match config {
+                Some(config) => {
+                    label = format!("Run ({})", config.name);
+                    env = project::run_config::environment(config, &project);
+                    project::run_config::cargo_args(config, &[json])
+                }
+                None => vec!["run".into(), json.into()],
+            };
+            if let Some(addr) = crate::editor::live_link::address(app_state) {
+                env.push((crate::editor::live_link::ADDR_ENV.to_string(), addr.to_string()));
+                crate::editor::live_link::remember_run_lights(app_state);
+            }
+            args
+        }
+        JobKind::Build { release: false } => vec!["build".into(), json.into()],
+        JobKind::Build { release: true } => vec!["build".into(), "--release".into(), json.into()],
+        JobKind::UpdateDependencies => vec!["update".into()],
+    };
+    start_cargo(app_state, &project, &label, args, env);
 }
 
 pub fn is_busy(app_state: &AppState) -> bool {
@@ -41,6 +87,19 @@ pub fn is_busy(app_state: &AppState) -> bool {
         .unwrap_or(false)
 }
 
+/// Kill the running cargo together with anything it spawned (the game, for
+/// Run) and drop the queue. The outcome arrives through `poll_job` as usual.
+pub fn cancel_job(app_state: &mut AppState) {
+    let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    r.editor.job_queue.clear();
+    let Some(job) = r.editor.job.as_mut() else { return; };
+    job.cancelled = true;
+    // Without a pid yet, poll_job kills as soon as the worker reports one.
+    if let Some(pid) = job.pid {
+        kill_process_tree(pid);
+    }
+}
+
 pub fn poll_job(app_state: &mut AppState) {
     let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
     let Some(job) = r.editor.job.as_mut() else { return; };
@@ -48,13 +107,26 @@ pub fn poll_job(app_state: &mut AppState) {
     let mut disconnected = false;
     loop {
         match job.rx.try_recv() {
+            Ok(JobMessage::Spawned(pid)) => {
+                job.pid = Some(pid);
+                if job.cancelled {
+                    kill_process_tree(pid);
+                }
+            }
             Ok(JobMessage::Line(s)) => {
+                job.log.push(s.clone());
                 job.lines.push(s);
                 if job.lines.len() > 500 {
                     let overflow = job.lines.len() - 500;
                     job.lines.drain(0..overflow);
                 }
             }
+            Ok(JobMessage::Diagnostic(d)) => {
+                // lib and bin targets report shared modules twice.
+                if !r.editor.problems.contains(&d) {
+                    r.editor.problems.push(d);
+                }
+            }
             Ok(JobMessage::Done(o)) => {
                 outcome = Some(o);
                 break;
@@ -74,27 +146,82 @@ pub fn poll_job(app_state: &mut AppState) {
             message: "job worker disconnected".to_string(),
         });
     }
-    if let Some(outcome) = outcome {
-        r.editor.job = None;
-        r.editor.last_job = Some(outcome);
+    let Some(mut outcome) = outcome else { return; };
+    if job.cancelled {
+        outcome.success = false;
+        outcome.message = "cancelled".into();
     }
+    let record = JobRecord {
+        id: Uuid::new_v4(),
+        outcome: outcome.clone(),
+        finished_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        log: std::mem::take(&mut job.log),
+    };
+
+    if !outcome.success {
+        // "build, then run" must not run a failed build.
+        r.editor.job_queue.clear();
+        if diagnostics::counts(&r.editor.problems).0 > 0 {
+            r.editor.layout.dock.open(Tab::Problems);
+        }
+    }
+    r.editor.job = None;
+    r.editor.last_job = Some(outcome);
+    project::job_history::push(&mut r.editor.job_history, record);
+    if let Some(p) = r.project.as_ref() {
+        project::job_history::start_save(p.root_path.clone(), r.editor.job_history.clone());
+    }
+
+    let next = r.editor.job_queue.pop_front();
+    if let Some(kind) = next {
+        start_job(app_state, kind);
+    }
+}
+
+#[cfg(unix)]
+fn kill_process_tree(pid: u32) {
+    // cargo runs as the leader of its own process group (see start_cargo),
+    // so a negative pid reaches rustc and the game as well.
+    let _ = Command::new("kill").args(["-TERM", &format!("-{pid}")]).status();
+}
+
+#[cfg(windows)]
+fn kill_process_tree(pid: u32) {
+    let _ = Command::new("taskkill").args(["/PID", &pid.to_string(), "/T", "/F"]).status();
 }
 
-fn start_cargo(app_state: &mut AppState, project: &ProjectState, label: &str, args: Vec<String>) {
+fn start_cargo(
+    app_state: &mut AppState,
+    project: &ProjectState,
+    label: &str,
+    args: Vec<String>,
+    env: Vec<(String, String)>,
+) {
     let (tx, rx) = mpsc::channel();
     let label_owned = label.to_string();
     let root_path = project.root_path.clone();
     let started_at = Instant::now();
     let thread_label = label_owned.clone();
+    let structured = args.iter().any(|a| a == diagnostics::MESSAGE_FORMAT_ARG);
     thread::spawn(move || {
         let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        let child = Command::new("cargo")
+        let mut command = Command::new("cargo");
+        command
             .args(&args_ref)
             .current_dir(&root_path)
             .env("CARGO_TERM_COLOR", "never")
+            .envs(env)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn();
+            .stderr(Stdio::piped());
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
+        }
+        let child = command.spawn();
 
         let mut child = match child {
             Ok(c) => c,
@@ -109,14 +236,37 @@ fn start_cargo(app_state: &mut AppState, project: &ProjectState, label: &str, ar
             }
         };
 
+        let _ = tx.send(JobMessage::Spawned(child.id()));
         let stdout = child.stdout.take();
         let stderr = child.stderr.take();
         let tx_out = tx.clone();
         let tx_err = tx.clone();
+        // In JSON mode stdout carries cargo's messages interleaved with the
+        // game's own output; stderr stays human-readable progress.
         let out_thread = stdout.map(|s| thread::spawn(move || {
+            // Counted once each, like the Problems list (see poll_job).
+            let mut seen: Vec<Diagnostic> = Vec::new();
             for line in BufReader::new(s).lines().flatten() {
-                if tx_out.send(JobMessage::Line(line)).is_err() { break; }
+                let message = if structured {
+                    match diagnostics::parse_line(&line) {
+                        CargoLine::Diagnostic(d) => {
+                            if !seen.contains(&d) {
+                                seen.push(d.clone());
+                            }
+                            if let Some(text) = d.rendered.clone() {
+                                if tx_out.send(JobMessage::Line(text)).is_err() { break; }
+                            }
+                            JobMessage::Diagnostic(d)
+                        }
+                        CargoLine::Text(text) => JobMessage::Line(text),
+                        CargoLine::Ignore => continue,
+                    }
+                } else {
+                    JobMessage::Line(line)
+                };
+                if tx_out.send(message).is_err() { break; }
             }
+            diagnostics::counts(&seen)
         }));
         let err_thread = stderr.map(|s| thread::spawn(move || {
             for line in BufReader::new(s).lines().flatten() {
@@ -125,7 +275,7 @@ fn start_cargo(app_state: &mut AppState, project: &ProjectState, label: &str, ar
         }));
 
         let status = child.wait();
-        if let Some(t) = out_thread { let _ = t.join(); }
+        let (errors, warnings) = out_thread.and_then(|t| t.join().ok()).unwrap_or((0, 0));
         if let Some(t) = err_thread { let _ = t.join(); }
 
         let outcome = match status {
@@ -133,7 +283,12 @@ fn start_cargo(app_state: &mut AppState, project: &ProjectState, label: &str, ar
                 label: thread_label,
                 success: s.success(),
                 duration: started_at.elapsed(),
-                message: if s.success() { "ok".into() } else { format!("exit {:?}", s.code()) },
+                message: match (s.success(), errors, warnings) {
+                    (true, _, 0) => "ok".into(),
+                    (true, _, w) => format!("ok, {w} warning(s)"),
+                    (false, 0, _) => format!("exit {:?}", s.code()),
+                    (false, e, w) => format!("{e} error(s), {w} warning(s)"),
+                },
             },
             Err(e) => JobOutcome {
                 label: thread_label,
@@ -145,31 +300,67 @@ fn start_cargo(app_state: &mut AppState, project: &ProjectState, label: &str, ar
         let _ = tx.send(JobMessage::Done(outcome));
     });
     if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        if structured {
+            r.editor.problems.clear();
+        }
         r.editor.job = Some(RunningJob {
             label: label_owned,
             started_at,
             rx,
             lines: Vec::new(),
+            log: Vec::new(),
+            pid: None,
+            cancelled: false,
         });
     }
 }
 
 fn save_before_run(app_state: &mut AppState) -> Option<ProjectState> {
+    if crate::editor::play::is_playing(app_state) {
+        logging::warn(Source::Build, "Stop play mode before building");
+        return None;
+    }
+    project::bindings::refresh_scene_objects(app_state);
     let project = app_state
         .get_state_data_value::<EditorRoot>("editor")
         .and_then(|r| r.project.clone())?;
     if let Err(e) = project::scene::save_active(&project, app_state) {
-        eprintln!("save scene failed: {e:?}");
+        logging::error(Source::Save, format!("Saving the scene failed: {e:?}"));
         return None;
     }
     if let Err(e) = project::try_save_project(app_state) {
-        eprintln!("save project failed: {e}");
+        logging::error(Source::Save, format!("Saving the project failed: {e}"));
+        return None;
+    }
+    if let Err(e) = project::bindings::write(&project) {
+        logging::error(Source::Build, format!("Writing asset bindings failed: {e}"));
+        return None;
+    }
+    if let Err(e) = project::script::write_registry(&project) {
+        logging::error(Source::Build, format!("Writing the script registry failed: {e}"));
+        return None;
+    }
+    if let Err(e) = crate::editor::live_link::ensure_runtime(std::path::Path::new(&project.root_path)) {
+        logging::error(Source::Build, format!("Writing the live link runtime failed: {e}"));
+        return None;
+    }
+    if let Err(e) = crate::editor::scene_graph::ensure_runtime(std::path::Path::new(&project.root_path)) {
+        logging::error(Source::Build, format!("Writing the hierarchy runtime failed: {e}"));
         return None;
     }
     if let Err(e) = stage_startup_scene(&project) {
-        eprintln!("stage startup scene failed: {e}");
+        logging::error(Source::Build, format!("Staging the startup scene failed: {e}"));
         return None;
     }
+    // main.rs belongs to the game, so older projects are told rather than edited.
+    let missing = project::bindings::missing_main_modules(std::path::Path::new(&project.root_path));
+    if !missing.is_empty() {
+        let lines = missing.iter().map(|m| format!("`mod {m};`")).collect::<Vec<_>>().join(", ");
+        logging::warn(Source::Build, format!(
+            "src/main.rs lacks {lines}; add them and their install calls (see a new project's main.rs) \
+             or the game won't use the editor's generated code"
+        ));
+    }
     if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
         r.editor.dirty = false;
     }
@@ -202,6 +393,7 @@ pub enum ObjectTemplate {
     Sphere,
 }
 
+/// New objects appear at `cursor_3d::spawn_point` and are selected.
 pub fn add_object(app_state: &mut AppState, template: ObjectTemplate) {
     let default_mat = ensure_default_material(app_state);
     let mut obj = match template {
@@ -212,7 +404,22 @@ pub fn add_object(app_state: &mut AppState, template: ObjectTemplate) {
     if let Some(mat_uuid) = default_mat {
         obj.add_material(mat_uuid);
     }
+    obj.transform.position = cursor_3d::spawn_point(app_state);
+    let uuid = obj.get_unique_id();
     app_state.add_object(obj);
+    cursor_3d::select_new(app_state, Selection::SceneObject(uuid));
+}
+
+/// A fresh object carrying `source`'s contents. `Object::clone` keeps the
+/// unique id, so copies start from `Object::new` to get their own.
+fn rebuild(source: &Object, name: String) -> Object {
+    let mut copy = Object::new(Some(name));
+    copy.transform = source.transform.clone();
+    *copy.get_shapes_mut() = source.get_shapes().clone();
+    *copy.get_materials_mut() = source.get_materials().clone();
+    *copy.get_animations_mut() = source.get_animations().clone();
+    *copy.get_current_animation_mut() = source.get_current_animation().clone();
+    copy
 }
 
 fn ensure_default_material(app_state: &mut AppState) -> Option<Uuid> {
@@ -228,21 +435,118 @@ fn ensure_default_material(app_state: &mut AppState) -> Option<Uuid> {
     Some(uuid)
 }
 
+/// Copy every selected scene item in place, along with its material
+/// assignments and script components, and select the copies.
+pub fn duplicate_selection(app_state: &mut AppState) {
+    let items = {
+        let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
+        root.editor.selection.items().to_vec()
+    };
+    let mut copies: Vec<Selection> = Vec::new();
+    let mut object_copies: Vec<(Uuid, Uuid)> = Vec::new();
+    for item in items {
+        match item {
+            Selection::SceneObject(uuid) => {
+                let Some(source) = app_state.objects.iter().find(|o| o.get_unique_id() == uuid) else { continue; };
+                let copy = rebuild(source, format!("{} copy", source.name));
+                let copy_uuid = copy.get_unique_id();
+                app_state.add_object(copy);
+                object_copies.push((uuid, copy_uuid));
+                copies.push(Selection::SceneObject(copy_uuid));
+            }
+            Selection::Light(uuid) => {
+                let Some(light) = lights::get(app_state, uuid).cloned() else { continue; };
+                let name = app_state.get_state_data_value::<EditorRoot>("editor")
+                    .and_then(|r| r.editor.lights.iter().find(|m| m.uuid == uuid))
+                    .map(|m| format!("{} copy", m.name));
+                let Some(copy) = lights::add(app_state, light, name) else { continue; };
+                copies.push(Selection::Light(copy));
+            }
+            Selection::ParticleInstance(uuid) => {
+                let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { continue; };
+                let Some(project) = root.project.as_mut() else { continue; };
+                let active = project.active_scene_index;
+                let Some(scene) = project.scenes.get_mut(active) else { continue; };
+                let Some(source) = scene.particle_instances.iter().find(|i| i.uuid == uuid) else { continue; };
+                let mut copy = source.clone();
+                copy.uuid = Uuid::new_v4();
+                copy.name = format!("{} copy", source.name);
+                copies.push(Selection::ParticleInstance(copy.uuid));
+                scene.particle_instances.push(copy);
+            }
+            _ => {}
+        }
+    }
+    if copies.is_empty() { return; }
+
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    if let Some(project) = root.project.as_mut() {
+        if let Some(scene) = project.scenes.get(project.active_scene_index).map(|s| s.uuid) {
+            for (source, copy) in &object_copies {
+                for (shape, material) in project.assignments_for_object(scene, *source) {
+                    project.set_assignment(scene, *copy, shape, material);
+                }
+                let components: Vec<_> = project.components_for_object(scene, *source).into_iter().cloned().collect();
+                for mut component in components {
+                    component.object = *copy;
+                    project.script_components.push(component);
+                }
+                // Copies sit next to their source, or under the parent's copy
+                // when the parent was duplicated too.
+                if let Some(parent) = project.parent_of(scene, *source) {
+                    let parent = object_copies.iter()
+                        .find(|(s, _)| *s == parent)
+                        .map(|(_, c)| *c)
+                        .unwrap_or(parent);
+                    let _ = crate::editor::scene_graph::set_parent(project, scene, *copy, Some(parent));
+                }
+            }
+        }
+    }
+    root.editor.selection.set(Selection::None);
+    for copy in copies {
+        root.editor.selection.add(copy);
+    }
+    root.editor.dirty = true;
+}
+
+/// Assign `material` to every shape of every selected object.
+pub fn assign_material_to_selection(app_state: &mut AppState, material: Uuid) {
+    let targets: Vec<(Uuid, usize)> = {
+        let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
+        root.editor.selection.scene_objects().into_iter()
+            .filter_map(|uuid| app_state.objects.iter().find(|o| o.get_unique_id() == uuid))
+            .map(|o| (o.get_unique_id(), o.get_shapes().len()))
+            .collect()
+    };
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let Some(project) = root.project.as_mut() else { return; };
+    let Some(scene) = project.scenes.get(project.active_scene_index).map(|s| s.uuid) else { return; };
+    for (object, shapes) in targets {
+        for shape in 0..shapes {
+            project.set_assignment(scene, object, shape, material);
+        }
+    }
+    root.editor.dirty = true;
+}
+
 pub enum LightTemplate {
     Directional,
     Point,
     Ambient,
 }
 
-pub fn spawn_from_model(app_state: &mut AppState, model_uuid: Uuid) {
+/// Load the model resource `model_uuid` into the scene; returns the new
+/// object's uuid.
+pub fn spawn_from_model(app_state: &mut AppState, model_uuid: Uuid) -> Option<Uuid> {
     let bytes = {
-        let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
-        let Some(project) = root.project.as_ref() else { return; };
+        let root = app_state.get_state_data_value::<EditorRoot>("editor")?;
+        let project = root.project.as_ref()?;
         match project::resource::bytes(project, model_uuid) {
             Ok(b) => b,
             Err(e) => {
-                eprintln!("spawn_from_model: {e:?}");
-                return;
+                logging::error(Source::Import, format!("Could not load the model: {e:?}"));
+                return None;
             }
         }
     };
@@ -253,23 +557,63 @@ pub fn spawn_from_model(app_state: &mut AppState, model_uuid: Uuid) {
             obj.add_material(mat_uuid);
         }
     }
+    obj.transform.position = cursor_3d::spawn_point(app_state);
+    let uuid = obj.get_unique_id();
     app_state.add_object(obj);
+    cursor_3d::select_new(app_state, Selection::SceneObject(uuid));
+    Some(uuid)
+}
+
+/// Put an emitter of the particle system `def_uuid` into the active scene at
+/// `position` and select it.
+pub fn add_particle_instance(app_state: &mut AppState, def_uuid: Uuid, position: [f32; 3]) -> Option<Uuid> {
+    let root = app_state.get_state_data_value_mut::<EditorRoot>("editor")?;
+    let project = root.project.as_mut()?;
+    let active = project.active_scene_index;
+    let def_name = project.particle_systems.iter()
+        .find(|d| d.uuid == def_uuid)
+        .map(|d| d.config.name.clone())
+        .unwrap_or_else(|| "particles".into());
+    let scene = project.scenes.get_mut(active)?;
+    let inst_uuid = Uuid::new_v4();
+    let n = scene.particle_instances.len() + 1;
+    scene.particle_instances.push(ParticleInstance {
+        uuid: inst_uuid,
+        def_uuid,
+        name: format!("{} {}", def_name, n),
+        position,
+    });
+    root.editor.selection.set(Selection::ParticleInstance(inst_uuid));
+    root.editor.dirty = true;
+    Some(inst_uuid)
 }
 
+/// Point and directional lights appear at `cursor_3d::spawn_point` and are
+/// selected. The ambient light has no position.
 pub fn add_light(app_state: &mut AppState, kind: LightTemplate) {
-    let (light, emission) = match kind {
-        LightTemplate::Directional => (
-            Light::new([0.0, 5.0, 0.0], [1.0, 1.0, 1.0], 1.0, Some([0.0, -1.0, 0.0]), true),
-            LightEmissionType::Source,
-        ),
-        LightTemplate::Point => (
-            Light::new([0.0, 1.0, 0.0], [1.0, 1.0, 1.0], 1.0, None, false),
-            LightEmissionType::Source,
-        ),
-        LightTemplate::Ambient => (
-            Light::new([0.0, 0.0, 0.0], [0.1, 0.1, 0.1], 1.0, None, false),
-            LightEmissionType::Ambient,
-        ),
+    let at: [f32; 3] = cursor_3d::spawn_point(app_state).into();
+    let light = match kind {
+        LightTemplate::Directional => Light::new(at, [1.0, 1.0, 1.0], 1.0, Some([0.0, -1.0, 0.0]), true),
+        LightTemplate::Point => Light::new(at, [1.0, 1.0, 1.0], 1.0, None, false),
+        LightTemplate::Ambient => {
+            let ambient = Light::new([0.0, 0.0, 0.0], [0.1, 0.1, 0.1], 1.0, None, false);
+            app_state.add_light(ambient, LightEmissionType::Ambient);
+            return;
+        }
     };
-    app_state.add_light(light, emission);
+    if let Some(uuid) = lights::add(app_state, light, None) {
+        cursor_3d::select_new(app_state, Selection::Light(uuid));
+    }
+}
+
+/// Switch to another scene of the open project; the current one is saved first.
+pub fn switch_scene(app_state: &mut AppState, index: usize) {
+    let Some(mut project) = app_state.get_state_data_value::<EditorRoot>("editor").and_then(|r| r.project.clone()) else { return; };
+    if let Err(e) = crate::project::scene::switch(&mut project, app_state, index) {
+        logging::error(Source::Scene, format!("Switching scenes failed: {e:?}"));
+        return;
+    }
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.project = Some(project);
+    }
 }
@@ -0,0 +1,190 @@
+//! Named editor camera viewpoints per scene.
+//!
+//! The Store View and Recall View commands (Ctrl+1..9 and 1..9 by default)
+//! keep the current view in a slot and glide back to it.
+//! The list lives in `EditorState::bookmarks` and is saved with the scene's
+//! editor keys (see `project::scene::SceneExtras`).
+
+use std::f32::consts::{PI, TAU};
+use std::time::Instant;
+
+use egui::Ui;
+use enigma_3d::AppState;
+use nalgebra::Vector3;
+
+use crate::editor::{cameras, commands, navigation};
+use crate::editor::state::{CameraBookmark, CameraPose, CameraTransition, EditorRoot};
+
+/// Seconds a recall takes to reach the bookmark.
+const TRANSITION_SECS: f32 = 0.35;
+
+/// Save the current view in `slot`, keeping the slot's name if it has one.
+pub fn store(app_state: &mut AppState, slot: u8) {
+    let Some(pose) = cameras::editor_pose(app_state) else { return; };
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let nav = &root.editor.navigation;
+    let bookmark = CameraBookmark {
+        slot,
+        name: format!("View {slot}"),
+        pose,
+        projection: nav.projection,
+        focus_distance: nav.focus_distance,
+    };
+    let bookmarks = &mut root.editor.bookmarks;
+    match bookmarks.iter_mut().find(|b| b.slot == slot) {
+        Some(existing) => *existing = CameraBookmark { name: existing.name.clone(), ..bookmark },
+        None => {
+            bookmarks.push(bookmark);
+            bookmarks.sort_by_key(|b| b.slot);
+        }
+    }
+    root.editor.dirty = true;
+}
+
+/// Start gliding to the view in `slot`.
+pub fn recall(app_state: &mut AppState, slot: u8) {
+    let Some(bookmark) = app_state.get_state_data_value::<EditorRoot>("editor")
+        .and_then(|r| r.editor.bookmarks.iter().find(|b| b.slot == slot).cloned()) else { return; };
+    navigation::set_projection(app_state, bookmark.projection);
+    let Some(from) = cameras::editor_pose(app_state) else { return; };
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    root.editor.navigation.focus_distance = bookmark.focus_distance;
+    root.editor.navigation.preset = None;
+    root.editor.camera_transition = Some(CameraTransition { from, to: bookmark.pose, started: Instant::now() });
+}
+
+pub fn remove(app_state: &mut AppState, slot: u8) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    root.editor.bookmarks.retain(|b| b.slot != slot);
+    root.editor.dirty = true;
+}
+
+pub fn rename(app_state: &mut AppState, slot: u8, name: String) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    if let Some(b) = root.editor.bookmarks.iter_mut().find(|b| b.slot == slot) {
+        b.name = name;
+        root.editor.dirty = true;
+    }
+}
+
+/// Per-frame: move the editor camera along a running transition. Returns
+/// whether one is still running.
+pub fn tick(app_state: &mut AppState) -> bool {
+    let Some(transition) = app_state.get_state_data_value::<EditorRoot>("editor")
+        .and_then(|r| r.editor.camera_transition.clone()) else { return false; };
+    let t = (transition.started.elapsed().as_secs_f32() / TRANSITION_SECS).min(1.0);
+    if let Some(cam) = app_state.camera.as_mut() {
+        cameras::apply_pose(cam, &interpolate(&transition.from, &transition.to, t));
+    }
+    let done = t >= 1.0;
+    if done {
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            root.editor.camera_transition = None;
+        }
+    }
+    !done
+}
+
+/// Eased blend of two poses; angles turn the short way round.
+pub fn interpolate(from: &CameraPose, to: &CameraPose, t: f32) -> CameraPose {
+    let s = t.clamp(0.0, 1.0);
+    let s = s * s * (3.0 - 2.0 * s);
+    let position = Vector3::from(from.position).lerp(&Vector3::from(to.position), s);
+    let mut rotation = [0.0; 3];
+    for (i, r) in rotation.iter_mut().enumerate() {
+        let delta = (to.rotation[i] - from.rotation[i] + PI).rem_euclid(TAU) - PI;
+        *r = from.rotation[i] + delta * s;
+    }
+    CameraPose { position: position.into(), rotation }
+}
+
+/// Bookmark list with recall, rename and delete, for the viewport overlay.
+pub fn menu(ui: &mut Ui, app_state: &mut AppState) {
+    let Some((bookmarks, mut renaming)) = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| (r.editor.bookmarks.clone(), r.editor.bookmark_rename.clone())) else { return; };
+    let store_key = commands::shortcut_text(app_state, commands::CommandId::StoreView1);
+    let mut recall_slot = None;
+    let mut remove_slot = None;
+    let mut commit: Option<(u8, String)> = None;
+    let mut store_slot = None;
+
+    ui.menu_button("★ Views", |ui| {
+        if bookmarks.is_empty() && !store_key.is_empty() {
+            ui.weak(format!("{store_key} saves the view in slot 1"));
+        }
+        for b in &bookmarks {
+            ui.horizontal(|ui| {
+                match renaming.as_mut() {
+                    Some((slot, draft)) if *slot == b.slot => {
+                        let response = ui.text_edit_singleline(draft);
+                        response.request_focus();
+                        if response.lost_focus() {
+                            commit = Some((*slot, draft.clone()));
+                        }
+                    }
+                    _ => {
+                        if ui.button(format!("{}  {}", b.slot, b.name)).clicked() {
+                            recall_slot = Some(b.slot);
+                            ui.close_menu();
+                        }
+                        if ui.small_button("✎").on_hover_text("Rename").clicked() {
+                            renaming = Some((b.slot, b.name.clone()));
+                        }
+                    }
+                }
+                if ui.small_button("×").on_hover_text("Delete").clicked() {
+                    remove_slot = Some(b.slot);
+                }
+            });
+        }
+        let free = (1..=9u8).find(|s| !bookmarks.iter().any(|b| b.slot == *s));
+        if let Some(slot) = free {
+            ui.separator();
+            if ui.button(format!("Save current view as {slot}")).clicked() {
+                store_slot = Some(slot);
+            }
+        }
+    });
+
+    if let Some((slot, name)) = commit {
+        renaming = None;
+        if !name.trim().is_empty() {
+            rename(app_state, slot, name.trim().to_string());
+        }
+    }
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.bookmark_rename = renaming;
+    }
+    if let Some(slot) = store_slot { store(app_state, slot); }
+    if let Some(slot) = remove_slot { remove(app_state, slot); }
+    if let Some(slot) = recall_slot { recall(app_state, slot); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pose(x: f32, yaw: f32) -> CameraPose {
+        CameraPose { position: [x, 0.0, 0.0], rotation: [0.0, yaw, 0.0] }
+    }
+
+    #[test]
+    fn interpolate_hits_endpoints_and_eases() {
+        let (a, b) = (pose(0.0, 0.0), pose(10.0, 1.0));
+        assert_eq!(interpolate(&a, &b, 0.0), a);
+        let end = interpolate(&a, &b, 1.0);
+        assert!((end.position[0] - 10.0).abs() < 1e-5 && (end.rotation[1] - 1.0).abs() < 1e-5);
+        let mid = interpolate(&a, &b, 0.5);
+        assert!((mid.position[0] - 5.0).abs() < 1e-4);
+        // Eased: slower than linear near the start.
+        assert!(interpolate(&a, &b, 0.1).position[0] < 1.0);
+    }
+
+    #[test]
+    fn interpolate_turns_the_short_way() {
+        let (a, b) = (pose(0.0, 3.0), pose(0.0, -3.0));
+        let mid = interpolate(&a, &b, 0.5);
+        // Through pi, not through zero.
+        assert!(mid.rotation[1].abs() > 3.0, "{}", mid.rotation[1]);
+    }
+}
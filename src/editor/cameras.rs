@@ -0,0 +1,162 @@
+//! The editor camera and the scene's game camera.
+//!
+//! The engine renders `app_state.camera`, so while editing that slot holds
+//! the editor's own view and the scene's camera waits in
+//! `EditorState::game_camera`. `with_game_camera` swaps it back for anything
+//! that serializes the scene. The editor view is saved per scene as a
+//! `CameraPose` in the scene file (see `project::scene::SceneExtras`).
+
+use egui::{Color32, Rect, Stroke, Ui};
+use enigma_3d::AppState;
+use enigma_3d::camera::Camera;
+use nalgebra::Vector3;
+
+use crate::editor::gizmo::math;
+use crate::editor::state::{CameraPose, EditorRoot, Selection};
+
+/// How far out the frustum preview is drawn, in world units.
+const PREVIEW_DISTANCE: f32 = 2.5;
+
+pub fn pose(camera: &Camera) -> CameraPose {
+    CameraPose {
+        position: camera.transform.position.into(),
+        rotation: camera.transform.rotation.into(),
+    }
+}
+
+pub fn apply_pose(camera: &mut Camera, pose: &CameraPose) {
+    camera.transform.position = Vector3::from(pose.position);
+    camera.transform.rotation = Vector3::from(pose.rotation);
+    camera.update_matrices();
+}
+
+/// The editor view, for saving with the scene.
+pub fn editor_pose(app_state: &AppState) -> Option<CameraPose> {
+    app_state.camera.as_ref().map(pose)
+}
+
+pub fn game_camera(app_state: &AppState) -> Option<&Camera> {
+    app_state.get_state_data_value::<EditorRoot>("editor")?.editor.game_camera.as_ref()
+}
+
+pub fn game_camera_mut(app_state: &mut AppState) -> Option<&mut Camera> {
+    app_state.get_state_data_value_mut::<EditorRoot>("editor")?.editor.game_camera.as_mut()
+}
+
+/// Take the editor view out before a scene is injected; hand it to `load`
+/// afterwards.
+pub fn take_view(app_state: &mut AppState) -> Option<Camera> {
+    app_state.camera.take()
+}
+
+/// After a scene load: whatever camera the scene brought becomes the game
+/// camera and `view` goes back into `app_state.camera`, moved to `pose` if
+/// the scene has one. A scene without a camera gets one where the view is.
+pub fn load(app_state: &mut AppState, view: Option<Camera>, pose: Option<CameraPose>) {
+    let game = app_state.camera.take().or_else(|| view.clone());
+    let mut view = view.or_else(|| game.clone());
+    if let (Some(view), Some(pose)) = (view.as_mut(), pose.as_ref()) {
+        apply_pose(view, pose);
+    }
+    if let Some(view) = view {
+        app_state.set_camera(view);
+    }
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.game_camera = game;
+    }
+}
+
+/// Run `f` with the game camera in `app_state.camera`.
+pub fn with_game_camera<T>(app_state: &mut AppState, f: impl FnOnce(&mut AppState) -> T) -> T {
+    let game = app_state.get_state_data_value_mut::<EditorRoot>("editor")
+        .and_then(|r| r.editor.game_camera.take());
+    let view = std::mem::replace(&mut app_state.camera, game);
+    let out = f(app_state);
+    let game = std::mem::replace(&mut app_state.camera, view);
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.game_camera = game;
+    }
+    out
+}
+
+/// Move the game camera to the editor view, creating it if the scene has none.
+pub fn align_game_camera_to_view(app_state: &mut AppState) {
+    let Some(view) = app_state.camera.clone() else { return; };
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    match root.editor.game_camera.as_mut() {
+        Some(game) => apply_pose(game, &pose(&view)),
+        None => root.editor.game_camera = Some(view),
+    }
+    root.editor.dirty = true;
+}
+
+/// The four corners of a camera's view at `distance`, clockwise from top left.
+pub fn frustum_corners(
+    position: Vector3<f32>,
+    forward: Vector3<f32>,
+    fov: f32,
+    aspect: f32,
+    distance: f32,
+) -> [Vector3<f32>; 4] {
+    let (right, up) = math::screen_basis(forward);
+    let half_h = (fov / 2.0).tan() * distance;
+    let half_w = half_h * aspect;
+    let center = position + forward * distance;
+    [
+        center - right * half_w + up * half_h,
+        center + right * half_w + up * half_h,
+        center + right * half_w - up * half_h,
+        center - right * half_w - up * half_h,
+    ]
+}
+
+/// Outline the game camera's view from the editor camera.
+pub fn draw_frustum(ui: &mut Ui, rect: Rect, app_state: &AppState) {
+    let (Some(view), Some(game)) = (app_state.camera.as_ref(), game_camera(app_state)) else { return; };
+    let selected = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| r.editor.selection.contains(&Selection::Camera))
+        .unwrap_or(false);
+    let color = if selected { Color32::from_rgb(255, 210, 60) } else { Color32::from_gray(200) };
+    let stroke = Stroke::new(if selected { 1.5 } else { 1.0 }, color);
+    let screen = ui.ctx().screen_rect();
+    let painter = ui.painter_at(rect);
+
+    let apex = Vector3::from(game.get_position());
+    let forward = Vector3::from(game.calculate_direction_vector());
+    let aspect = if game.height > 0.0 { game.width / game.height } else { 16.0 / 9.0 };
+    let corners = frustum_corners(apex, forward, game.fov, aspect, PREVIEW_DISTANCE);
+    let segment = |a: Vector3<f32>, b: Vector3<f32>| {
+        if let Some((a, b)) = math::world_segment_to_screen(view, screen, a, b) {
+            painter.line_segment([a, b], stroke);
+        }
+    };
+    for i in 0..4 {
+        segment(apex, corners[i]);
+        segment(corners[i], corners[(i + 1) % 4]);
+    }
+    // A notch on the top edge shows which way is up.
+    let top = (corners[0] + corners[1]) / 2.0;
+    let (_, up) = math::screen_basis(forward);
+    let notch = top + up * (corners[0] - corners[1]).norm() * 0.15;
+    segment(corners[0].lerp(&corners[1], 0.4), notch);
+    segment(notch, corners[0].lerp(&corners[1], 0.6));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frustum_corners_span_fov_and_aspect() {
+        let forward = Vector3::new(0.0, 0.0, -1.0);
+        let fov = std::f32::consts::FRAC_PI_2;
+        let corners = frustum_corners(Vector3::zeros(), forward, fov, 2.0, 1.0);
+        // tan(45°) = 1: half height 1, half width 2, one unit ahead.
+        for c in &corners {
+            assert!((c.z + 1.0).abs() < 1e-5);
+            assert!((c.x.abs() - 2.0).abs() < 1e-5);
+            assert!((c.y.abs() - 1.0).abs() < 1e-5);
+        }
+        assert!(corners[0].y > 0.0 && corners[3].y < 0.0);
+    }
+}
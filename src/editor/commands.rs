@@ -0,0 +1,670 @@
+//! Every editor command in one registry, with rebindable shortcuts.
+//!
+//! Menus, the Ctrl+Shift+P palette and the keyboard all go through `run`.
+//! Default shortcuts live in `COMMANDS`; the user's changes are kept in a
+//! `Keymap` of overrides and saved with the editor preferences.
+//!
+//! Viewport commands only fire with the pointer over the viewport. While the
+//! right mouse button is held the keys belong to fly navigation (WASDQE), so
+//! no shortcut fires. During a gizmo drag only the drag's own commands apply;
+//! the gizmo reads those itself (see `gizmo::entry`).
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use egui::{Context, Key};
+use enigma_3d::AppState;
+use serde::{Deserialize, Serialize};
+
+use crate::editor::actions::{self, LightTemplate, ObjectTemplate};
+use crate::editor::panels::toolbar;
+use crate::editor::state::{EditorRoot, GizmoMode, Modal, PaletteState, PendingDelete, Space};
+use crate::editor::dock::{DockLayout, Tab};
+use crate::editor::logging::{self, Source};
+use crate::editor::{bookmarks, cameras, gizmo, navigation, play};
+use crate::project;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub enum CommandId {
+    NewProject,
+    OpenProject,
+    SaveProject,
+    SaveScene,
+    NewScene,
+    Run,
+    DebugBuild,
+    ReleaseBuild,
+    UpdateDependencies,
+    CancelJob,
+    TogglePlay,
+    PauseResume,
+    AddEmpty,
+    AddCube,
+    AddSphere,
+    AddPointLight,
+    AddDirectionalLight,
+    Duplicate,
+    Delete,
+    FrameSelection,
+    AlignGameCamera,
+    ToggleProjection,
+    GizmoSelect,
+    GizmoTranslate,
+    GizmoRotate,
+    GizmoScale,
+    ToggleSpace,
+    ToggleSnap,
+    ToggleGrid,
+    ToggleHierarchy,
+    ToggleInspector,
+    ToggleResources,
+    ToggleProblems,
+    ToggleConsole,
+    ResetLayout,
+    ToggleJobHistory,
+    ToggleLiveLink,
+    CommandPalette,
+    KeyboardShortcuts,
+    Preferences,
+    StoreView1,
+    StoreView2,
+    StoreView3,
+    StoreView4,
+    StoreView5,
+    StoreView6,
+    StoreView7,
+    StoreView8,
+    StoreView9,
+    RecallView1,
+    RecallView2,
+    RecallView3,
+    RecallView4,
+    RecallView5,
+    RecallView6,
+    RecallView7,
+    RecallView8,
+    RecallView9,
+    ConstrainX,
+    ConstrainY,
+    ConstrainZ,
+    ConfirmDrag,
+}
+
+/// Where a command's shortcut is listened for.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Scope {
+    Global,
+    /// Only with the pointer over the viewport.
+    Viewport,
+    /// Only while dragging a gizmo, when nothing else fires.
+    Drag,
+}
+
+impl Scope {
+    fn overlaps(self, other: Scope) -> bool {
+        match (self, other) {
+            (Scope::Drag, other) | (other, Scope::Drag) => other == Scope::Drag,
+            (Scope::Global, _) | (_, Scope::Global) => true,
+            _ => self == other,
+        }
+    }
+}
+
+pub struct Command {
+    pub id: CommandId,
+    pub category: &'static str,
+    pub label: &'static str,
+    pub default: Option<Shortcut>,
+    pub scope: Scope,
+}
+
+/// A key with the modifiers that must be held, exactly. `command` is Ctrl,
+/// or Cmd on macOS. Saved as text such as `"Ctrl+Shift+P"`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Shortcut {
+    pub key: Key,
+    pub command: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl Shortcut {
+    pub const fn key(key: Key) -> Self {
+        Shortcut { key, command: false, shift: false, alt: false }
+    }
+
+    const fn ctrl(key: Key) -> Self {
+        Shortcut { key, command: true, shift: false, alt: false }
+    }
+
+    const fn ctrl_shift(key: Key) -> Self {
+        Shortcut { key, command: true, shift: true, alt: false }
+    }
+
+    const fn ctrl_alt(key: Key) -> Self {
+        Shortcut { key, command: true, shift: false, alt: true }
+    }
+
+    pub fn pressed(&self, input: &egui::InputState) -> bool {
+        let m = input.modifiers;
+        input.key_pressed(self.key) && m.command == self.command && m.shift == self.shift && m.alt == self.alt
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut shortcut = Shortcut::key(Key::A);
+        let mut key = None;
+        for part in text.split('+').map(str::trim) {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "cmd" | "command" => shortcut.command = true,
+                "shift" => shortcut.shift = true,
+                "alt" | "option" => shortcut.alt = true,
+                _ if key.is_none() => key = Some(BINDABLE_KEYS.iter().copied().find(|k| k.name().eq_ignore_ascii_case(part))?),
+                _ => return None,
+            }
+        }
+        shortcut.key = key?;
+        Some(shortcut)
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.command { f.write_str("Ctrl+")?; }
+        if self.alt { f.write_str("Alt+")?; }
+        if self.shift { f.write_str("Shift+")?; }
+        f.write_str(self.key.name())
+    }
+}
+
+impl TryFrom<String> for Shortcut {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        Shortcut::parse(&text).ok_or_else(|| format!("not a shortcut: {text:?}"))
+    }
+}
+
+impl From<Shortcut> for String {
+    fn from(shortcut: Shortcut) -> Self {
+        shortcut.to_string()
+    }
+}
+
+/// Mouse bindings, fixed and listed alongside the shortcuts. None of them
+/// can clash with a key.
+pub const MOUSE_BINDINGS: &[(&str, &str)] = &[
+    ("Fly (WASDQE to move)", "Hold Right Click"),
+    ("Orbit", "Alt+Left Drag"),
+    ("Place 3D Cursor", "Shift+Right Click"),
+];
+
+/// Keys a command can be bound to. Escape is kept for cancelling.
+pub const BINDABLE_KEYS: &[Key] = &[
+    Key::A, Key::B, Key::C, Key::D, Key::E, Key::F, Key::G, Key::H, Key::I,
+    Key::J, Key::K, Key::L, Key::M, Key::N, Key::O, Key::P, Key::Q, Key::R,
+    Key::S, Key::T, Key::U, Key::V, Key::W, Key::X, Key::Y, Key::Z,
+    Key::Num0, Key::Num1, Key::Num2, Key::Num3, Key::Num4,
+    Key::Num5, Key::Num6, Key::Num7, Key::Num8, Key::Num9,
+    Key::F1, Key::F2, Key::F3, Key::F4, Key::F5, Key::F6,
+    Key::F7, Key::F8, Key::F9, Key::F10, Key::F11, Key::F12,
+    Key::Delete, Key::Backspace, Key::Insert, Key::Home, Key::End,
+    Key::PageUp, Key::PageDown, Key::Space, Key::Tab, Key::Enter,
+    Key::ArrowUp, Key::ArrowDown, Key::ArrowLeft, Key::ArrowRight,
+    Key::Minus, Key::PlusEquals,
+];
+
+const fn command(id: CommandId, category: &'static str, label: &'static str, default: Option<Shortcut>, scope: Scope) -> Command {
+    Command { id, category, label, default, scope }
+}
+
+pub const COMMANDS: &[Command] = {
+    use CommandId::*;
+    use Scope::*;
+    &[
+        command(NewProject, "File", "New Project…", None, Global),
+        command(OpenProject, "File", "Open Project…", Some(Shortcut::ctrl(Key::O)), Global),
+        command(SaveProject, "File", "Save Project", None, Global),
+        command(SaveScene, "File", "Save Scene", Some(Shortcut::ctrl(Key::S)), Global),
+        command(NewScene, "Scene", "New Scene…", None, Global),
+        command(Run, "Build", "Run", Some(Shortcut::key(Key::F5)), Global),
+        command(DebugBuild, "Build", "Debug Build", Some(Shortcut::ctrl(Key::B)), Global),
+        command(ReleaseBuild, "Build", "Release Build", Some(Shortcut::ctrl_shift(Key::B)), Global),
+        command(UpdateDependencies, "Build", "Update Dependencies", None, Global),
+        command(CancelJob, "Build", "Cancel Job", None, Global),
+        command(TogglePlay, "Play", "Play / Stop", Some(Shortcut::ctrl(Key::P)), Global),
+        command(PauseResume, "Play", "Pause / Resume", None, Global),
+        command(AddEmpty, "Add", "Empty Object", None, Global),
+        command(AddCube, "Add", "Cube", None, Global),
+        command(AddSphere, "Add", "Sphere", None, Global),
+        command(AddPointLight, "Add", "Point Light", None, Global),
+        command(AddDirectionalLight, "Add", "Directional Light", None, Global),
+        command(Duplicate, "Edit", "Duplicate Selection", Some(Shortcut::ctrl(Key::D)), Global),
+        command(Delete, "Edit", "Delete Selection", Some(Shortcut::key(Key::Delete)), Global),
+        command(FrameSelection, "View", "Frame Selection", Some(Shortcut::key(Key::F)), Viewport),
+        command(AlignGameCamera, "View", "Align Game Camera to View", Some(Shortcut::ctrl_alt(Key::Num0)), Viewport),
+        command(ToggleProjection, "View", "Toggle Perspective / Orthographic", None, Viewport),
+        command(ToggleGrid, "View", "Toggle Grid", None, Global),
+        command(GizmoSelect, "Gizmo", "Select", Some(Shortcut::key(Key::Q)), Viewport),
+        command(GizmoTranslate, "Gizmo", "Translate", Some(Shortcut::key(Key::W)), Viewport),
+        command(GizmoRotate, "Gizmo", "Rotate", Some(Shortcut::key(Key::E)), Viewport),
+        command(GizmoScale, "Gizmo", "Scale", Some(Shortcut::key(Key::R)), Viewport),
+        command(ToggleSpace, "Gizmo", "Toggle World / Local", None, Viewport),
+        command(ToggleSnap, "Gizmo", "Toggle Snapping", None, Global),
+        command(ToggleHierarchy, "Window", "Hierarchy", None, Global),
+        command(ToggleInspector, "Window", "Inspector", None, Global),
+        command(ToggleResources, "Window", "Resources", None, Global),
+        command(ToggleProblems, "Window", "Problems", None, Global),
+        command(ToggleConsole, "Window", "Console", None, Global),
+        command(ResetLayout, "Window", "Reset Layout", None, Global),
+        command(ToggleJobHistory, "Window", "Job History", None, Global),
+        command(ToggleLiveLink, "Window", "Live Link", None, Global),
+        command(CommandPalette, "Window", "Command Palette", Some(Shortcut::ctrl_shift(Key::P)), Global),
+        command(KeyboardShortcuts, "Window", "Keyboard Shortcuts…", None, Global),
+        command(Preferences, "Window", "Preferences…", None, Global),
+        command(StoreView1, "Views", "Store View 1", Some(Shortcut::ctrl(Key::Num1)), Global),
+        command(StoreView2, "Views", "Store View 2", Some(Shortcut::ctrl(Key::Num2)), Global),
+        command(StoreView3, "Views", "Store View 3", Some(Shortcut::ctrl(Key::Num3)), Global),
+        command(StoreView4, "Views", "Store View 4", Some(Shortcut::ctrl(Key::Num4)), Global),
+        command(StoreView5, "Views", "Store View 5", Some(Shortcut::ctrl(Key::Num5)), Global),
+        command(StoreView6, "Views", "Store View 6", Some(Shortcut::ctrl(Key::Num6)), Global),
+        command(StoreView7, "Views", "Store View 7", Some(Shortcut::ctrl(Key::Num7)), Global),
+        command(StoreView8, "Views", "Store View 8", Some(Shortcut::ctrl(Key::Num8)), Global),
+        command(StoreView9, "Views", "Store View 9", Some(Shortcut::ctrl(Key::Num9)), Global),
+        command(RecallView1, "Views", "Recall View 1", Some(Shortcut::key(Key::Num1)), Global),
+        command(RecallView2, "Views", "Recall View 2", Some(Shortcut::key(Key::Num2)), Global),
+        command(RecallView3, "Views", "Recall View 3", Some(Shortcut::key(Key::Num3)), Global),
+        command(RecallView4, "Views", "Recall View 4", Some(Shortcut::key(Key::Num4)), Global),
+        command(RecallView5, "Views", "Recall View 5", Some(Shortcut::key(Key::Num5)), Global),
+        command(RecallView6, "Views", "Recall View 6", Some(Shortcut::key(Key::Num6)), Global),
+        command(RecallView7, "Views", "Recall View 7", Some(Shortcut::key(Key::Num7)), Global),
+        command(RecallView8, "Views", "Recall View 8", Some(Shortcut::key(Key::Num8)), Global),
+        command(RecallView9, "Views", "Recall View 9", Some(Shortcut::key(Key::Num9)), Global),
+        command(ConstrainX, "Gizmo Drag", "Constrain to X (Shift: YZ plane)", Some(Shortcut::key(Key::X)), Drag),
+        command(ConstrainY, "Gizmo Drag", "Constrain to Y (Shift: XZ plane)", Some(Shortcut::key(Key::Y)), Drag),
+        command(ConstrainZ, "Gizmo Drag", "Constrain to Z (Shift: XY plane)", Some(Shortcut::key(Key::Z)), Drag),
+        command(ConfirmDrag, "Gizmo Drag", "Confirm", Some(Shortcut::key(Key::Enter)), Drag),
+    ]
+};
+
+const STORE_VIEWS: [CommandId; 9] = {
+    use CommandId::*;
+    [StoreView1, StoreView2, StoreView3, StoreView4, StoreView5, StoreView6, StoreView7, StoreView8, StoreView9]
+};
+
+const RECALL_VIEWS: [CommandId; 9] = {
+    use CommandId::*;
+    [RecallView1, RecallView2, RecallView3, RecallView4, RecallView5, RecallView6, RecallView7, RecallView8, RecallView9]
+};
+
+/// Bookmark slot (1..9) of a Store View or Recall View command.
+fn view_slot(id: CommandId) -> u8 {
+    let index = STORE_VIEWS.iter().chain(&RECALL_VIEWS).position(|c| *c == id).unwrap_or(0);
+    (index % 9) as u8 + 1
+}
+
+pub fn find(id: CommandId) -> &'static Command {
+    COMMANDS.iter().find(|c| c.id == id).expect("every CommandId is registered")
+}
+
+/// The user's changes to the default shortcuts. `None` unbinds.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Keymap(BTreeMap<CommandId, Option<Shortcut>>);
+
+impl Keymap {
+    pub fn shortcut(&self, id: CommandId) -> Option<Shortcut> {
+        self.0.get(&id).copied().unwrap_or(find(id).default)
+    }
+
+    pub fn is_default(&self, id: CommandId) -> bool {
+        !self.0.contains_key(&id)
+    }
+
+    pub fn set(&mut self, id: CommandId, shortcut: Option<Shortcut>) {
+        if shortcut == find(id).default {
+            self.0.remove(&id);
+        } else {
+            self.0.insert(id, shortcut);
+        }
+    }
+
+    /// Other commands bound to the same shortcut in an overlapping scope.
+    pub fn conflicts(&self, id: CommandId) -> Vec<CommandId> {
+        let Some(shortcut) = self.shortcut(id) else { return Vec::new(); };
+        let scope = find(id).scope;
+        COMMANDS.iter()
+            .filter(|c| c.id != id && self.shortcut(c.id) == Some(shortcut))
+            .filter(|c| c.scope.overlaps(scope))
+            .map(|c| c.id)
+            .collect()
+    }
+}
+
+/// Per-frame from the viewport: run the commands whose shortcut was pressed.
+/// Nothing fires while typing, rebinding, dragging a gizmo or flying.
+pub fn handle_shortcuts(ctx: &Context, app_state: &mut AppState, pointer_in_viewport: bool) {
+    if ctx.wants_keyboard_input() || gizmo::is_dragging(app_state) { return; }
+    if ctx.input(|i| i.pointer.secondary_down()) { return; }
+    let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
+    if root.editor.capturing_shortcut.is_some() { return; }
+    let keymap = &root.editor.keymap;
+    let fired: Vec<CommandId> = ctx.input(|i| {
+        COMMANDS.iter()
+            .filter(|c| c.scope == Scope::Global || (c.scope == Scope::Viewport && pointer_in_viewport))
+            .filter(|c| keymap.shortcut(c.id).is_some_and(|s| s.pressed(i)))
+            .map(|c| c.id)
+            .collect()
+    });
+    for id in fired {
+        if available(app_state, id) {
+            run(app_state, id);
+        }
+    }
+}
+
+/// Whether the command makes sense right now. Unavailable commands are
+/// hidden from the palette and their shortcuts do nothing.
+pub fn available(app_state: &AppState, id: CommandId) -> bool {
+    use CommandId::*;
+    let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return false; };
+    let project = root.project.is_some();
+    let playing = play::is_playing(app_state);
+    match id {
+        NewProject | OpenProject => !playing,
+        SaveProject | SaveScene | NewScene | Run | DebugBuild | ReleaseBuild | UpdateDependencies => project && !playing,
+        CancelJob => actions::is_busy(app_state),
+        PauseResume => playing,
+        TogglePlay | AddEmpty | AddCube | AddSphere | AddPointLight | AddDirectionalLight
+        | Duplicate | Delete | AlignGameCamera | ToggleJobHistory => project,
+        _ if STORE_VIEWS.contains(&id) || RECALL_VIEWS.contains(&id) => project,
+        ConstrainX | ConstrainY | ConstrainZ | ConfirmDrag => false,
+        ToggleLiveLink => root.editor.live_link.is_some(),
+        _ => true,
+    }
+}
+
+pub fn run(app_state: &mut AppState, id: CommandId) {
+    use CommandId::*;
+    match id {
+        NewProject => {
+            if let Some(path) = toolbar::pick_folder() {
+                if let Err(e) = project::try_new_project(&path, app_state) {
+                    logging::error(Source::Project, format!("New project failed: {e}"));
+                }
+            }
+        }
+        OpenProject => {
+            if let Some(path) = toolbar::pick_file("json") {
+                project::start_open_project(&path, app_state);
+            }
+        }
+        SaveProject => project::start_save_project_only(app_state),
+        SaveScene => project::start_save_scene_and_project(app_state),
+        NewScene => set_modal(app_state, Modal::NewSceneName(String::new())),
+        Run => actions::run_project(app_state),
+        DebugBuild => actions::build_project(app_state, false),
+        ReleaseBuild => actions::build_project(app_state, true),
+        UpdateDependencies => actions::update_dependencies(app_state),
+        CancelJob => actions::cancel_job(app_state),
+        TogglePlay => {
+            if play::is_playing(app_state) { play::stop(app_state) } else { play::start(app_state) }
+        }
+        PauseResume => {
+            let paused = app_state.get_state_data_value::<EditorRoot>("editor")
+                .and_then(|r| r.editor.play.as_ref().map(|p| p.paused));
+            if let Some(paused) = paused {
+                play::set_paused(app_state, !paused);
+            }
+        }
+        AddEmpty => actions::add_object(app_state, ObjectTemplate::Empty),
+        AddCube => actions::add_object(app_state, ObjectTemplate::Cube),
+        AddSphere => actions::add_object(app_state, ObjectTemplate::Sphere),
+        AddPointLight => actions::add_light(app_state, LightTemplate::Point),
+        AddDirectionalLight => actions::add_light(app_state, LightTemplate::Directional),
+        Duplicate => actions::duplicate_selection(app_state),
+        Delete => {
+            let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+            let mut pending: Vec<PendingDelete> = root.editor.selection.items().iter()
+                .filter_map(PendingDelete::for_selection)
+                .collect();
+            let pending = match pending.len() {
+                0 => return,
+                1 => pending.remove(0),
+                _ => PendingDelete::Many(pending),
+            };
+            root.editor.modal = Some(Modal::ConfirmDelete { label: pending.label(), pending });
+        }
+        FrameSelection => navigation::frame_selection(app_state),
+        AlignGameCamera => cameras::align_game_camera_to_view(app_state),
+        ToggleProjection => navigation::toggle_projection(app_state),
+        GizmoSelect => set_gizmo_mode(app_state, GizmoMode::None),
+        GizmoTranslate => set_gizmo_mode(app_state, GizmoMode::Translate),
+        GizmoRotate => set_gizmo_mode(app_state, GizmoMode::Rotate),
+        GizmoScale => set_gizmo_mode(app_state, GizmoMode::Scale),
+        ToggleSpace => with_editor(app_state, |e| {
+            e.gizmo.space = match e.gizmo.space {
+                Space::World => Space::Local,
+                Space::Local => Space::World,
+            };
+        }),
+        ToggleSnap => with_editor(app_state, |e| e.gizmo.snap_enabled = !e.gizmo.snap_enabled),
+        ToggleGrid => with_editor(app_state, |e| e.gizmo.grid_enabled = !e.gizmo.grid_enabled),
+        ToggleHierarchy => with_editor(app_state, |e| e.layout.dock.toggle(Tab::Hierarchy)),
+        ToggleInspector => with_editor(app_state, |e| e.layout.dock.toggle(Tab::Inspector)),
+        ToggleResources => with_editor(app_state, |e| e.layout.dock.toggle(Tab::Resources)),
+        ToggleProblems => with_editor(app_state, |e| e.layout.dock.toggle(Tab::Problems)),
+        ToggleConsole => with_editor(app_state, |e| e.layout.dock.toggle(Tab::Console)),
+        ResetLayout => with_editor(app_state, |e| {
+            e.layout.dock = DockLayout::default();
+            e.dock_generation += 1;
+        }),
+        ToggleJobHistory => with_editor(app_state, |e| e.show_job_history = !e.show_job_history),
+        ToggleLiveLink => with_editor(app_state, |e| e.show_live_link = !e.show_live_link),
+        CommandPalette => with_editor(app_state, |e| {
+            e.palette = match e.palette {
+                Some(_) => None,
+                None => Some(PaletteState::default()),
+            };
+        }),
+        KeyboardShortcuts => with_editor(app_state, |e| e.show_keybindings = true),
+        Preferences => with_editor(app_state, |e| e.show_preferences = true),
+        StoreView1 | StoreView2 | StoreView3 | StoreView4 | StoreView5
+        | StoreView6 | StoreView7 | StoreView8 | StoreView9 => bookmarks::store(app_state, view_slot(id)),
+        RecallView1 | RecallView2 | RecallView3 | RecallView4 | RecallView5
+        | RecallView6 | RecallView7 | RecallView8 | RecallView9 => bookmarks::recall(app_state, view_slot(id)),
+        // Read by the gizmo while it drags.
+        ConstrainX | ConstrainY | ConstrainZ | ConfirmDrag => {}
+    }
+}
+
+fn with_editor(app_state: &mut AppState, f: impl FnOnce(&mut crate::editor::state::EditorState)) {
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        f(&mut root.editor);
+    }
+}
+
+fn set_modal(app_state: &mut AppState, modal: Modal) {
+    with_editor(app_state, |e| e.modal = Some(modal));
+}
+
+fn set_gizmo_mode(app_state: &mut AppState, mode: GizmoMode) {
+    with_editor(app_state, |e| e.gizmo.mode = mode);
+}
+
+pub fn rebind(app_state: &mut AppState, id: CommandId, shortcut: Option<Shortcut>) {
+    with_editor(app_state, |e| e.keymap.set(id, shortcut));
+}
+
+/// The shortcut as menus show it, or empty when unbound.
+pub fn shortcut_text(app_state: &AppState, id: CommandId) -> String {
+    app_state.get_state_data_value::<EditorRoot>("editor")
+        .and_then(|r| r.editor.keymap.shortcut(id))
+        .map(|s| s.to_string())
+        .unwrap_or_default()
+}
+
+/// Something the palette can do.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PaletteItem {
+    Command(CommandId),
+    /// Switch to the project's scene at this index.
+    Scene(usize),
+}
+
+/// Everything currently available, labelled as the palette lists it.
+pub fn palette_items(app_state: &AppState) -> Vec<(PaletteItem, String)> {
+    let mut items: Vec<(PaletteItem, String)> = COMMANDS.iter()
+        .filter(|c| c.id != CommandId::CommandPalette && available(app_state, c.id))
+        .map(|c| (PaletteItem::Command(c.id), format!("{}: {}", c.category, c.label)))
+        .collect();
+    let project = app_state.get_state_data_value::<EditorRoot>("editor").and_then(|r| r.project.as_ref());
+    if let Some(project) = project.filter(|_| !play::is_playing(app_state)) {
+        items.extend(project.scenes.iter().enumerate()
+            .filter(|(i, _)| *i != project.active_scene_index)
+            .map(|(i, s)| (PaletteItem::Scene(i), format!("Scene: Switch to {}", s.name))));
+    }
+    items
+}
+
+pub fn run_item(app_state: &mut AppState, item: PaletteItem) {
+    match item {
+        PaletteItem::Command(id) => run(app_state, id),
+        PaletteItem::Scene(index) => actions::switch_scene(app_state, index),
+    }
+}
+
+/// Scores `text` against `query` when every query character appears in
+/// order, ignoring case and spaces. Runs of adjacent characters and matches
+/// at the start of a word score higher.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut from = 0;
+    let mut last: Option<usize> = None;
+    for q in query.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase) {
+        let at = from + text[from..].iter().position(|c| *c == q)?;
+        score += 1;
+        if last.is_some_and(|l| l + 1 == at) { score += 5; }
+        if at == 0 || !text[at - 1].is_alphanumeric() { score += 3; }
+        last = Some(at);
+        from = at + 1;
+    }
+    // Prefer the shorter of two equally good matches.
+    Some(score * 100 - text.len() as i32)
+}
+
+/// The items matching `query`, best first. An empty query keeps the order.
+pub fn rank<T: Clone>(items: &[(T, String)], query: &str) -> Vec<(T, String)> {
+    if query.trim().is_empty() {
+        return items.to_vec();
+    }
+    let mut scored: Vec<(i32, &(T, String))> = items.iter()
+        .filter_map(|item| Some((fuzzy_score(query, &item.1)?, item)))
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, item)| item.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortcut_text_round_trips() {
+        for text in ["Ctrl+Shift+P", "F5", "Delete", "Ctrl+Alt+0", "Q"] {
+            let shortcut = Shortcut::parse(text).unwrap();
+            assert_eq!(shortcut.to_string(), text);
+        }
+        assert_eq!(Shortcut::parse("shift + ctrl + p"), Some(Shortcut::ctrl_shift(Key::P)));
+        assert_eq!(Shortcut::parse("Ctrl+"), None);
+        assert_eq!(Shortcut::parse("Ctrl+Escape"), None);
+        assert_eq!(Shortcut::parse("A+B"), None);
+    }
+
+    #[test]
+    fn every_command_is_registered_once() {
+        for command in COMMANDS {
+            assert_eq!(COMMANDS.iter().filter(|c| c.id == command.id).count(), 1, "{:?}", command.id);
+        }
+        assert_eq!(find(CommandId::KeyboardShortcuts).id, CommandId::KeyboardShortcuts);
+    }
+
+    #[test]
+    fn default_shortcuts_do_not_conflict() {
+        let keymap = Keymap::default();
+        for command in COMMANDS {
+            assert!(keymap.conflicts(command.id).is_empty(), "{:?}", command.id);
+        }
+    }
+
+    #[test]
+    fn view_commands_know_their_slot() {
+        assert_eq!(view_slot(CommandId::StoreView1), 1);
+        assert_eq!(view_slot(CommandId::StoreView9), 9);
+        assert_eq!(view_slot(CommandId::RecallView4), 4);
+    }
+
+    #[test]
+    fn keymap_keeps_only_changes() {
+        let mut keymap = Keymap::default();
+        keymap.set(CommandId::Run, Some(Shortcut::key(Key::F6)));
+        keymap.set(CommandId::Delete, None);
+        assert_eq!(keymap.shortcut(CommandId::Run), Some(Shortcut::key(Key::F6)));
+        assert_eq!(keymap.shortcut(CommandId::Delete), None);
+        assert_eq!(keymap.shortcut(CommandId::SaveScene), Some(Shortcut::ctrl(Key::S)));
+
+        let json = serde_json::to_string(&keymap).unwrap();
+        assert_eq!(json, r#"{"Run":"F6","Delete":null}"#);
+        assert_eq!(serde_json::from_str::<Keymap>(&json).unwrap(), keymap);
+
+        keymap.set(CommandId::Run, Some(Shortcut::key(Key::F5)));
+        assert!(keymap.is_default(CommandId::Run));
+    }
+
+    #[test]
+    fn conflicts_respect_scope() {
+        let mut keymap = Keymap::default();
+        // F frames the selection in the viewport; binding it globally clashes.
+        keymap.set(CommandId::ToggleGrid, Some(Shortcut::key(Key::F)));
+        assert_eq!(keymap.conflicts(CommandId::ToggleGrid), vec![CommandId::FrameSelection]);
+        keymap.set(CommandId::ToggleGrid, None);
+        keymap.set(CommandId::ToggleSpace, Some(Shortcut::key(Key::W)));
+        assert_eq!(keymap.conflicts(CommandId::ToggleSpace), vec![CommandId::GizmoTranslate]);
+        keymap.set(CommandId::ToggleSpace, None);
+        // 1 recalls the first view; another command on it clashes.
+        keymap.set(CommandId::ToggleGrid, Some(Shortcut::key(Key::Num1)));
+        assert_eq!(keymap.conflicts(CommandId::ToggleGrid), vec![CommandId::RecallView1]);
+        // Nothing else fires mid-drag, so drag keys only clash among themselves.
+        keymap.set(CommandId::ToggleGrid, Some(Shortcut::key(Key::X)));
+        assert!(keymap.conflicts(CommandId::ToggleGrid).is_empty());
+        keymap.set(CommandId::ConfirmDrag, Some(Shortcut::key(Key::X)));
+        assert_eq!(keymap.conflicts(CommandId::ConfirmDrag), vec![CommandId::ConstrainX]);
+    }
+
+    #[test]
+    fn fuzzy_matches_in_order() {
+        assert!(fuzzy_score("svsc", "File: Save Scene").is_some());
+        assert!(fuzzy_score("fss", "File: Save Scene").is_some());
+        assert!(fuzzy_score("xyz", "File: Save Scene").is_none());
+        assert!(fuzzy_score("scs", "File: Save Scene").is_none());
+        assert!(fuzzy_score("", "anything").is_some());
+        // Word starts and runs beat scattered letters.
+        let words = fuzzy_score("sas", "File: Save Scene").unwrap();
+        let scattered = fuzzy_score("sas", "Build: Release Build").unwrap_or(i32::MIN);
+        assert!(words > scattered);
+    }
+
+    #[test]
+    fn rank_puts_best_match_first() {
+        let items = vec![
+            (1, "Build: Release Build".to_string()),
+            (2, "File: Save Scene".to_string()),
+            (3, "Scene: Switch to Level 2".to_string()),
+        ];
+        let ranked: Vec<i32> = rank(&items, "save sc").into_iter().map(|(id, _)| id).collect();
+        assert_eq!(ranked.first(), Some(&2));
+        let all: Vec<i32> = rank(&items, "  ").into_iter().map(|(id, _)| id).collect();
+        assert_eq!(all, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,114 @@
+//! The 3D cursor and where new scene items appear.
+//!
+//! Shift+right-click in the viewport drops the cursor where the click ray
+//! lands (see `drag_drop::landing_point`). New objects, lights and emitters
+//! spawn at the cursor or in front of the editor camera, whichever the
+//! `SpawnAt` preference says.
+
+use egui::{Color32, Context, Rect, Stroke, Ui};
+use enigma_3d::AppState;
+use nalgebra::Vector3;
+
+use crate::editor::gizmo::math;
+use crate::editor::state::{EditorRoot, Selection, SpawnAt};
+use crate::editor::drag_drop;
+
+/// In front of the camera, new items sit at the focus distance, kept within
+/// this range so they're neither in the lens nor lost in the distance.
+const SPAWN_DISTANCE: (f32, f32) = (2.0, 20.0);
+const CURSOR_RADIUS: f32 = 10.0;
+
+/// Shift+right-click in the viewport places the cursor.
+pub fn handle_click(ctx: &Context, app_state: &mut AppState, pointer_in_rect: bool) {
+    let clicked = pointer_in_rect
+        && ctx.input(|i| i.modifiers.shift && i.pointer.button_clicked(egui::PointerButton::Secondary));
+    if !clicked { return; }
+    let Some(pos) = ctx.input(|i| i.pointer.interact_pos()) else { return; };
+    let Some(point) = drag_drop::landing_point(app_state, ctx.screen_rect(), pos) else { return; };
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.cursor_3d = point;
+    }
+}
+
+/// Where the next new item goes.
+pub fn spawn_point(app_state: &AppState) -> Vector3<f32> {
+    let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return Vector3::zeros(); };
+    match (root.editor.spawn_at, app_state.camera.as_ref()) {
+        (SpawnAt::InFrontOfCamera, Some(camera)) => in_front(
+            camera.transform.position,
+            Vector3::from(camera.calculate_direction_vector()),
+            root.editor.navigation.focus_distance,
+        ),
+        _ => root.editor.cursor_3d,
+    }
+}
+
+pub fn in_front(position: Vector3<f32>, forward: Vector3<f32>, focus_distance: f32) -> Vector3<f32> {
+    position + forward * focus_distance.clamp(SPAWN_DISTANCE.0, SPAWN_DISTANCE.1)
+}
+
+/// Select a freshly spawned item.
+pub fn select_new(app_state: &mut AppState, item: Selection) {
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.selection.set(item);
+        root.editor.dirty = true;
+    }
+}
+
+/// A red and white ring with a crosshair, like Blender's.
+pub fn draw(ui: &mut Ui, rect: Rect, app_state: &AppState) {
+    let Some(camera) = app_state.camera.as_ref() else { return; };
+    let Some(position) = app_state.get_state_data_value::<EditorRoot>("editor").map(|r| r.editor.cursor_3d) else { return; };
+    let Some(at) = math::world_to_screen(camera, ui.ctx().screen_rect(), position) else { return; };
+    let painter = ui.painter_at(rect);
+    let segments = 12;
+    for i in 0..segments {
+        let (a, b) = (i as f32 / segments as f32, (i + 1) as f32 / segments as f32);
+        let point = |t: f32| at + egui::vec2((t * std::f32::consts::TAU).cos(), (t * std::f32::consts::TAU).sin()) * CURSOR_RADIUS;
+        let color = if i % 2 == 0 { Color32::from_rgb(230, 60, 60) } else { Color32::WHITE };
+        painter.line_segment([point(a), point(b)], Stroke::new(2.0, color));
+    }
+    let cross = Stroke::new(1.0, Color32::from_gray(20));
+    for d in [egui::vec2(1.0, 0.0), egui::vec2(0.0, 1.0)] {
+        painter.line_segment([at - d * CURSOR_RADIUS * 1.6, at - d * CURSOR_RADIUS * 0.5], cross);
+        painter.line_segment([at + d * CURSOR_RADIUS * 0.5, at + d * CURSOR_RADIUS * 1.6], cross);
+    }
+}
+
+/// Spawn placement and cursor reset, for the viewport overlay.
+pub fn menu(ui: &mut Ui, app_state: &mut AppState) {
+    let Some(mut spawn_at) = app_state.get_state_data_value::<EditorRoot>("editor").map(|r| r.editor.spawn_at) else { return; };
+    let mut cursor_to: Option<Vector3<f32>> = None;
+    ui.menu_button("⊕ Spawn", |ui| {
+        ui.radio_value(&mut spawn_at, SpawnAt::InFrontOfCamera, "In front of camera");
+        ui.radio_value(&mut spawn_at, SpawnAt::Cursor, "At 3D cursor");
+        ui.weak("Shift+right-click places the cursor");
+        ui.separator();
+        if ui.button("Cursor to origin").clicked() {
+            cursor_to = Some(Vector3::zeros());
+            ui.close_menu();
+        }
+        if ui.button("Cursor to selection").clicked() {
+            cursor_to = crate::editor::gizmo::selection_pivot(app_state);
+            ui.close_menu();
+        }
+    });
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    if let Some(p) = cursor_to {
+        root.editor.cursor_3d = p;
+    }
+    root.editor.spawn_at = spawn_at;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_front_keeps_a_sensible_distance() {
+        let forward = Vector3::new(0.0, 0.0, -1.0);
+        assert_eq!(in_front(Vector3::zeros(), forward, 5.0), Vector3::new(0.0, 0.0, -5.0));
+        assert_eq!(in_front(Vector3::zeros(), forward, 0.1), Vector3::new(0.0, 0.0, -2.0));
+        assert_eq!(in_front(Vector3::zeros(), forward, 500.0), Vector3::new(0.0, 0.0, -20.0));
+    }
+}
@@ -0,0 +1,123 @@
+use serde_json::Value;
+
+use crate::editor::state::{Diagnostic, DiagnosticLevel};
+
+/// Cargo flag that switches stdout to one JSON message per line.
+pub const MESSAGE_FORMAT_ARG: &str = "--message-format=json";
+
+/// What one stdout line of a `--message-format=json` cargo run turned out to be.
+#[derive(Debug, PartialEq)]
+pub enum CargoLine {
+    /// A compiler error/warning worth listing in the Problems panel.
+    Diagnostic(Diagnostic),
+    /// Human-readable text: a rendered summary, or the game's own output.
+    Text(String),
+    /// Bookkeeping messages (artifacts, build scripts) nobody needs to read.
+    Ignore,
+}
+
+pub fn parse_line(line: &str) -> CargoLine {
+    // Everything cargo emits in JSON mode is an object; anything else is
+    // plain output from `cargo run`'s child process.
+    if !line.starts_with('{') {
+        return CargoLine::Text(line.to_string());
+    }
+    let Ok(value) = serde_json::from_str::<Value>(line) else {
+        return CargoLine::Text(line.to_string());
+    };
+    match value.get("reason").and_then(Value::as_str) {
+        Some("compiler-message") => {}
+        Some(_) => return CargoLine::Ignore,
+        None => return CargoLine::Text(line.to_string()),
+    }
+    let Some(message) = value.get("message") else { return CargoLine::Ignore; };
+    let text = message.get("message").and_then(Value::as_str).unwrap_or_default().to_string();
+    let rendered = message.get("rendered").and_then(Value::as_str).map(|s| s.trim_end().to_string());
+    let level = match message.get("level").and_then(Value::as_str) {
+        Some("error") | Some("error: internal compiler error") => DiagnosticLevel::Error,
+        Some("warning") => DiagnosticLevel::Warning,
+        Some("note") => DiagnosticLevel::Note,
+        Some("help") => DiagnosticLevel::Help,
+        _ => return CargoLine::Ignore,
+    };
+    if is_summary(&text) {
+        return rendered.map(CargoLine::Text).unwrap_or(CargoLine::Ignore);
+    }
+
+    let spans = message.get("spans").and_then(Value::as_array);
+    let primary = spans.and_then(|spans| {
+        spans.iter()
+            .find(|s| s.get("is_primary").and_then(Value::as_bool).unwrap_or(false))
+            .or_else(|| spans.first())
+    });
+    let (file, line_no, column) = match primary {
+        Some(span) => (
+            span.get("file_name").and_then(Value::as_str).map(str::to_string),
+            span.get("line_start").and_then(Value::as_u64).unwrap_or(0) as u32,
+            span.get("column_start").and_then(Value::as_u64).unwrap_or(0) as u32,
+        ),
+        None => (None, 0, 0),
+    };
+    let code = message.get("code")
+        .and_then(|c| c.get("code"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    CargoLine::Diagnostic(Diagnostic { level, message: text, file, line: line_no, column, code, rendered })
+}
+
+/// rustc's closing "aborting due to 2 previous errors" / "3 warnings emitted"
+/// lines repeat what the list already shows.
+fn is_summary(message: &str) -> bool {
+    message.starts_with("aborting due to")
+        || message.ends_with("warning emitted")
+        || message.ends_with("warnings emitted")
+}
+
+/// (errors, warnings) in `diagnostics`.
+pub fn counts(diagnostics: &[Diagnostic]) -> (usize, usize) {
+    let errors = diagnostics.iter().filter(|d| d.level == DiagnosticLevel::Error).count();
+    let warnings = diagnostics.iter().filter(|d| d.level == DiagnosticLevel::Warning).count();
+    (errors, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ERROR_LINE: &str = r#"{"reason":"compiler-message","package_id":"game 0.1.0","message":{"message":"mismatched types","code":{"code":"E0308","explanation":null},"level":"error","spans":[{"file_name":"src/other.rs","line_start":1,"column_start":1,"is_primary":false},{"file_name":"src/main.rs","line_start":12,"column_start":9,"is_primary":true}],"children":[],"rendered":"error[E0308]: mismatched types\n"}}"#;
+
+    #[test]
+    fn parses_compiler_message_with_primary_span() {
+        let CargoLine::Diagnostic(d) = parse_line(ERROR_LINE) else { panic!("expected diagnostic") };
+        assert_eq!(d.level, DiagnosticLevel::Error);
+        assert_eq!(d.message, "mismatched types");
+        assert_eq!(d.file.as_deref(), Some("src/main.rs"));
+        assert_eq!((d.line, d.column), (12, 9));
+        assert_eq!(d.code.as_deref(), Some("E0308"));
+        assert_eq!(d.rendered.as_deref(), Some("error[E0308]: mismatched types"));
+    }
+
+    #[test]
+    fn plain_output_and_bookkeeping() {
+        assert_eq!(parse_line("hello from the game"), CargoLine::Text("hello from the game".into()));
+        assert_eq!(parse_line(r#"{"reason":"compiler-artifact","target":{}}"#), CargoLine::Ignore);
+        assert_eq!(parse_line(r#"{"reason":"build-finished","success":true}"#), CargoLine::Ignore);
+    }
+
+    #[test]
+    fn summaries_become_text() {
+        let line = r#"{"reason":"compiler-message","message":{"message":"aborting due to 1 previous error","level":"error","spans":[],"rendered":"error: aborting due to 1 previous error\n"}}"#;
+        assert_eq!(parse_line(line), CargoLine::Text("error: aborting due to 1 previous error".into()));
+    }
+
+    #[test]
+    fn counts_errors_and_warnings() {
+        let CargoLine::Diagnostic(error) = parse_line(ERROR_LINE) else { panic!() };
+        let mut warning = error.clone();
+        warning.level = DiagnosticLevel::Warning;
+        let mut note = error.clone();
+        note.level = DiagnosticLevel::Note;
+        assert_eq!(counts(&[error, warning.clone(), warning, note]), (1, 2));
+    }
+}
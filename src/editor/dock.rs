@@ -0,0 +1,799 @@
+//! Docked editor panels.
+//!
+//! The viewport fills the middle of the window (the engine draws the scene
+//! behind egui); the panels dock around it in three areas, left, right and
+//! bottom. An area holds tab groups split side by side or stacked. Dragging
+//! a tab onto a group joins it, onto a group's edge splits the group, onto
+//! an empty area's strip at the viewport edge fills that area, and anywhere
+//! else detaches it into a floating window.
+//!
+//! The arrangement is kept in the editor preferences, along with layouts
+//! saved under a name. `PRESETS` are always available.
+
+use egui::{Context, Id, Pos2, Rect, Sense, Ui, Vec2};
+use enigma_3d::AppState;
+use serde::{Deserialize, Serialize};
+
+use crate::editor::panels;
+use crate::editor::state::EditorRoot;
+
+/// Smallest share of a split either side keeps.
+const MIN_FRACTION: f32 = 0.1;
+/// Width of the drop strips for empty areas, in points.
+const STRIP: f32 = 40.0;
+const SEPARATOR: f32 = 6.0;
+
+pub const PRESETS: [&str; 4] = ["Default", "Modeling", "Particles", "Build"];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Tab {
+    Hierarchy,
+    Inspector,
+    Resources,
+    Problems,
+    Console,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 5] = [Tab::Hierarchy, Tab::Inspector, Tab::Resources, Tab::Problems, Tab::Console];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Tab::Hierarchy => "Hierarchy",
+            Tab::Inspector => "Inspector",
+            Tab::Resources => "Resources",
+            Tab::Problems => "Problems",
+            Tab::Console => "Console",
+        }
+    }
+
+    /// Where the tab goes when opened from the Window menu.
+    pub fn home(self) -> Side {
+        match self {
+            Tab::Hierarchy => Side::Left,
+            Tab::Inspector => Side::Right,
+            Tab::Resources | Tab::Problems | Tab::Console => Side::Bottom,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Side {
+    Left,
+    Right,
+    Bottom,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Split {
+    /// Side by side.
+    Horizontal,
+    /// Stacked.
+    Vertical,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum Node {
+    Tabs { tabs: Vec<Tab>, active: usize },
+    /// `fraction` of the space goes to `first`.
+    Split { split: Split, fraction: f32, first: Box<Node>, second: Box<Node> },
+}
+
+/// Where in a group a dragged tab lands.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Zone {
+    Center,
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Target {
+    /// Into or beside the group holding `anchor`.
+    Group { anchor: Tab, zone: Zone },
+    Side(Side),
+    Float { pos: [f32; 2] },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Area {
+    pub root: Option<Node>,
+    /// Width, or height for the bottom area, in points.
+    pub size: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub struct Floating {
+    pub tab: Tab,
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct DockLayout {
+    pub left: Area,
+    pub right: Area,
+    pub bottom: Area,
+    pub floating: Vec<Floating>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct NamedLayout {
+    pub name: String,
+    pub dock: DockLayout,
+}
+
+impl Default for DockLayout {
+    fn default() -> Self {
+        preset("Default").expect("the default preset exists")
+    }
+}
+
+fn area(root: Option<Node>, size: f32) -> Area {
+    Area { root, size }
+}
+
+/// The built-in arrangements.
+pub fn preset(name: &str) -> Option<DockLayout> {
+    let tabs = |tabs: &[Tab]| Node::Tabs { tabs: tabs.to_vec(), active: 0 };
+    let (left, right, bottom) = match name {
+        "Default" => (
+            area(Some(tabs(&[Tab::Hierarchy])), 220.0),
+            area(Some(tabs(&[Tab::Inspector])), 320.0),
+            area(Some(tabs(&[Tab::Resources])), 220.0),
+        ),
+        // Scene tree and assets side by side with the viewport; no bottom bar.
+        "Modeling" => (
+            area(Some(Node::split(Split::Vertical, tabs(&[Tab::Hierarchy]), tabs(&[Tab::Resources]))), 260.0),
+            area(Some(tabs(&[Tab::Inspector])), 320.0),
+            area(None, 220.0),
+        ),
+        // A wide inspector for the particle and material editors.
+        "Particles" => (
+            area(Some(tabs(&[Tab::Hierarchy])), 200.0),
+            area(Some(tabs(&[Tab::Inspector])), 460.0),
+            area(Some(tabs(&[Tab::Resources])), 200.0),
+        ),
+        "Build" => (
+            area(Some(tabs(&[Tab::Hierarchy])), 220.0),
+            area(Some(tabs(&[Tab::Inspector])), 300.0),
+            area(Some(Node::split(Split::Horizontal, tabs(&[Tab::Problems, Tab::Console]), tabs(&[Tab::Resources]))), 300.0),
+        ),
+        _ => return None,
+    };
+    Some(DockLayout { left, right, bottom, floating: Vec::new() })
+}
+
+impl Node {
+    fn tabs(tab: Tab) -> Self {
+        Node::Tabs { tabs: vec![tab], active: 0 }
+    }
+
+    fn split(split: Split, first: Node, second: Node) -> Self {
+        Node::Split { split, fraction: 0.5, first: Box::new(first), second: Box::new(second) }
+    }
+
+    fn contains(&self, tab: Tab) -> bool {
+        match self {
+            Node::Tabs { tabs, .. } => tabs.contains(&tab),
+            Node::Split { first, second, .. } => first.contains(tab) || second.contains(tab),
+        }
+    }
+
+    /// This node with `tab` taken out; empty groups and splits fold away.
+    fn without(self, tab: Tab) -> Option<Node> {
+        match self {
+            Node::Tabs { mut tabs, active } => {
+                let removed = tabs.iter().position(|t| *t == tab);
+                tabs.retain(|t| *t != tab);
+                let active = match removed {
+                    Some(i) if i < active => active - 1,
+                    _ => active,
+                };
+                (!tabs.is_empty()).then(|| Node::Tabs { active: active.min(tabs.len() - 1), tabs })
+            }
+            Node::Split { split, fraction, first, second } => match (first.without(tab), second.without(tab)) {
+                (Some(first), Some(second)) => Some(Node::Split { split, fraction, first: Box::new(first), second: Box::new(second) }),
+                (Some(only), None) | (None, Some(only)) => Some(only),
+                (None, None) => None,
+            },
+        }
+    }
+
+    /// Put `tab` into or beside the group holding `anchor`.
+    fn insert(&mut self, anchor: Tab, tab: Tab, zone: Zone) -> bool {
+        match self {
+            Node::Split { first, second, .. } => first.insert(anchor, tab, zone) || second.insert(anchor, tab, zone),
+            Node::Tabs { tabs, active } => {
+                if !tabs.contains(&anchor) { return false; }
+                if zone == Zone::Center {
+                    tabs.push(tab);
+                    *active = tabs.len() - 1;
+                    return true;
+                }
+                let group = std::mem::replace(self, Node::tabs(tab));
+                let new = Node::tabs(tab);
+                *self = match zone {
+                    Zone::Left => Node::split(Split::Horizontal, new, group),
+                    Zone::Right => Node::split(Split::Horizontal, group, new),
+                    Zone::Top => Node::split(Split::Vertical, new, group),
+                    Zone::Bottom | Zone::Center => Node::split(Split::Vertical, group, new),
+                };
+                true
+            }
+        }
+    }
+
+    fn activate(&mut self, tab: Tab) {
+        match self {
+            Node::Tabs { tabs, active } => {
+                if let Some(i) = tabs.iter().position(|t| *t == tab) {
+                    *active = i;
+                }
+            }
+            Node::Split { first, second, .. } => {
+                first.activate(tab);
+                second.activate(tab);
+            }
+        }
+    }
+
+    /// The split reached by following `path` (false = first child).
+    fn split_at(&mut self, path: &[bool]) -> Option<&mut f32> {
+        let Node::Split { fraction, first, second, .. } = self else { return None; };
+        match path.split_first() {
+            None => Some(fraction),
+            Some((false, rest)) => first.split_at(rest),
+            Some((true, rest)) => second.split_at(rest),
+        }
+    }
+}
+
+impl DockLayout {
+    pub fn area(&self, side: Side) -> &Area {
+        match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+            Side::Bottom => &self.bottom,
+        }
+    }
+
+    pub fn area_mut(&mut self, side: Side) -> &mut Area {
+        match side {
+            Side::Left => &mut self.left,
+            Side::Right => &mut self.right,
+            Side::Bottom => &mut self.bottom,
+        }
+    }
+
+    fn areas_mut(&mut self) -> [&mut Area; 3] {
+        [&mut self.left, &mut self.right, &mut self.bottom]
+    }
+
+    pub fn contains(&self, tab: Tab) -> bool {
+        [&self.left, &self.right, &self.bottom].iter().any(|a| a.root.as_ref().is_some_and(|n| n.contains(tab)))
+            || self.floating.iter().any(|f| f.tab == tab)
+    }
+
+    pub fn remove(&mut self, tab: Tab) {
+        for area in self.areas_mut() {
+            area.root = area.root.take().and_then(|n| n.without(tab));
+        }
+        self.floating.retain(|f| f.tab != tab);
+    }
+
+    /// Move `tab` to `target`, from wherever it was.
+    pub fn dock(&mut self, tab: Tab, target: Target) {
+        if let Target::Group { anchor, .. } = target {
+            if anchor == tab || !self.areas_mut().iter().any(|a| a.root.as_ref().is_some_and(|n| n.contains(anchor))) {
+                return;
+            }
+        }
+        self.remove(tab);
+        match target {
+            Target::Group { anchor, zone } => {
+                for area in self.areas_mut() {
+                    if area.root.as_mut().is_some_and(|n| n.insert(anchor, tab, zone)) {
+                        return;
+                    }
+                }
+            }
+            Target::Side(side) => {
+                let area = self.area_mut(side);
+                let split = if side == Side::Bottom { Split::Horizontal } else { Split::Vertical };
+                area.root = Some(match area.root.take() {
+                    None => Node::tabs(tab),
+                    Some(root) => Node::split(split, root, Node::tabs(tab)),
+                });
+            }
+            Target::Float { pos } => self.floating.push(Floating { tab, pos, size: [360.0, 420.0] }),
+        }
+    }
+
+    /// Show `tab`, docking it at home if it's closed.
+    pub fn open(&mut self, tab: Tab) {
+        if !self.contains(tab) {
+            self.dock(tab, Target::Side(tab.home()));
+        }
+        for area in self.areas_mut() {
+            if let Some(root) = area.root.as_mut() {
+                root.activate(tab);
+            }
+        }
+    }
+
+    pub fn toggle(&mut self, tab: Tab) {
+        if self.contains(tab) { self.remove(tab) } else { self.open(tab) }
+    }
+}
+
+/// `zone` for a pointer at `pos` over a group: the middle half joins the
+/// group, the margins split off toward that edge.
+pub fn zone_at(rect: Rect, pos: Pos2) -> Zone {
+    let u = (pos.x - rect.left()) / rect.width().max(1.0);
+    let v = (pos.y - rect.top()) / rect.height().max(1.0);
+    if (0.25..=0.75).contains(&u) && (0.25..=0.75).contains(&v) {
+        return Zone::Center;
+    }
+    // Nearest edge.
+    [(u, Zone::Left), (1.0 - u, Zone::Right), (v, Zone::Top), (1.0 - v, Zone::Bottom)]
+        .into_iter()
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map_or(Zone::Center, |(_, zone)| zone)
+}
+
+/// The part of `rect` a tab dropped in `zone` would take.
+fn zone_rect(rect: Rect, zone: Zone) -> Rect {
+    let half = rect.size() * 0.5;
+    match zone {
+        Zone::Center => rect,
+        Zone::Left => Rect::from_min_size(rect.min, Vec2::new(half.x, rect.height())),
+        Zone::Right => Rect::from_min_size(rect.min + Vec2::new(half.x, 0.0), Vec2::new(half.x, rect.height())),
+        Zone::Top => Rect::from_min_size(rect.min, Vec2::new(rect.width(), half.y)),
+        Zone::Bottom => Rect::from_min_size(rect.min + Vec2::new(0.0, half.y), Vec2::new(rect.width(), half.y)),
+    }
+}
+
+/// The two halves of a split and the separator between them.
+pub fn split_rect(rect: Rect, split: Split, fraction: f32) -> (Rect, Rect, Rect) {
+    match split {
+        Split::Horizontal => {
+            let x = rect.left() + (rect.width() - SEPARATOR) * fraction;
+            (
+                Rect::from_min_max(rect.min, Pos2::new(x, rect.bottom())),
+                Rect::from_min_max(Pos2::new(x, rect.top()), Pos2::new(x + SEPARATOR, rect.bottom())),
+                Rect::from_min_max(Pos2::new(x + SEPARATOR, rect.top()), rect.max),
+            )
+        }
+        Split::Vertical => {
+            let y = rect.top() + (rect.height() - SEPARATOR) * fraction;
+            (
+                Rect::from_min_max(rect.min, Pos2::new(rect.right(), y)),
+                Rect::from_min_max(Pos2::new(rect.left(), y), Pos2::new(rect.right(), y + SEPARATOR)),
+                Rect::from_min_max(Pos2::new(rect.left(), y + SEPARATOR), rect.max),
+            )
+        }
+    }
+}
+
+/// Changes made while drawing, applied once everything is drawn.
+enum Edit {
+    Size(Side, f32),
+    Fraction(Side, Vec<bool>, f32),
+    Activate(Tab),
+    StartDrag(Tab),
+    Move(Tab, Target),
+    Close(Tab),
+    FloatRect(Tab, Rect),
+}
+
+/// A tab group as drawn this frame, for dropping onto.
+struct GroupRect {
+    tabs: Vec<Tab>,
+    rect: Rect,
+}
+
+/// Per-frame, before the viewport: the docked areas and floating panels.
+pub fn draw(ctx: &Context, app_state: &mut AppState) {
+    let Some((dock, generation, dragging)) = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| (r.editor.layout.dock.clone(), r.editor.dock_generation, r.editor.dock_drag)) else { return; };
+    let mut edits: Vec<Edit> = Vec::new();
+    let mut groups: Vec<GroupRect> = Vec::new();
+
+    for side in [Side::Left, Side::Right, Side::Bottom] {
+        let area = dock.area(side);
+        let Some(root) = area.root.as_ref() else { continue; };
+        // A new id per applied layout, so the panel takes the layout's size.
+        let id = Id::new(("dock", side, generation));
+        let mut contents = |ui: &mut Ui| {
+            draw_node(ui, root, side, &mut Vec::new(), app_state, &mut edits, &mut groups);
+        };
+        let rect = match side {
+            Side::Left => egui::SidePanel::left(id).default_width(area.size).min_width(120.0).resizable(true)
+                .show(ctx, |ui| contents(ui)).response.rect,
+            Side::Right => egui::SidePanel::right(id).default_width(area.size).min_width(160.0).resizable(true)
+                .show(ctx, |ui| contents(ui)).response.rect,
+            Side::Bottom => egui::TopBottomPanel::bottom(id).default_height(area.size).min_height(60.0).resizable(true)
+                .show(ctx, |ui| contents(ui)).response.rect,
+        };
+        let size = if side == Side::Bottom { rect.height() } else { rect.width() };
+        if size != area.size {
+            edits.push(Edit::Size(side, size));
+        }
+    }
+
+    for floating in &dock.floating {
+        let tab = floating.tab;
+        let mut open = true;
+        let response = egui::Window::new(tab.title())
+            .id(Id::new(("dock_float", tab, generation)))
+            .default_pos(Pos2::from(floating.pos))
+            .default_size(Vec2::from(floating.size))
+            .open(&mut open)
+            .show(ctx, |ui| {
+                if ui.small_button("⤓ Dock").on_hover_text(format!("Back to the {:?} area", tab.home())).clicked() {
+                    edits.push(Edit::Move(tab, Target::Side(tab.home())));
+                }
+                ui.separator();
+                draw_tab(ui, tab, app_state);
+            });
+        if let Some(response) = response {
+            edits.push(Edit::FloatRect(tab, response.response.rect));
+        }
+        if !open {
+            edits.push(Edit::Close(tab));
+        }
+    }
+
+    if let Some(tab) = dragging {
+        drag_tab(ctx, &dock, tab, &groups, &mut edits);
+    }
+
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let editor = &mut root.editor;
+    for edit in edits {
+        let dock = &mut editor.layout.dock;
+        match edit {
+            Edit::Size(side, size) => dock.area_mut(side).size = size,
+            Edit::Fraction(side, path, fraction) => {
+                if let Some(f) = dock.area_mut(side).root.as_mut().and_then(|n| n.split_at(&path)) {
+                    *f = fraction.clamp(MIN_FRACTION, 1.0 - MIN_FRACTION);
+                }
+            }
+            Edit::Activate(tab) => dock.open(tab),
+            Edit::StartDrag(tab) => editor.dock_drag = Some(tab),
+            Edit::Move(tab, target) => {
+                dock.dock(tab, target);
+                editor.dock_drag = None;
+            }
+            Edit::Close(tab) => dock.remove(tab),
+            Edit::FloatRect(tab, rect) => {
+                if let Some(f) = dock.floating.iter_mut().find(|f| f.tab == tab) {
+                    f.pos = rect.min.into();
+                    f.size = rect.size().into();
+                }
+            }
+        }
+    }
+    if ctx.input(|i| i.pointer.any_released()) {
+        editor.dock_drag = None;
+    }
+}
+
+fn draw_node(
+    ui: &mut Ui,
+    node: &Node,
+    side: Side,
+    path: &mut Vec<bool>,
+    app_state: &mut AppState,
+    edits: &mut Vec<Edit>,
+    groups: &mut Vec<GroupRect>,
+) {
+    match node {
+        Node::Tabs { tabs, active } => draw_group(ui, tabs, *active, app_state, edits, groups),
+        Node::Split { split, fraction, first, second } => {
+            let rect = ui.available_rect_before_wrap();
+            let (a, separator, b) = split_rect(rect, *split, *fraction);
+            let response = ui.interact(separator, ui.id().with(("dock_split", path.clone())), Sense::drag());
+            if response.hovered() || response.dragged() {
+                ui.ctx().set_cursor_icon(match split {
+                    Split::Horizontal => egui::CursorIcon::ResizeHorizontal,
+                    Split::Vertical => egui::CursorIcon::ResizeVertical,
+                });
+            }
+            if let Some(pos) = response.interact_pointer_pos().filter(|_| response.dragged()) {
+                let fraction = match split {
+                    Split::Horizontal => (pos.x - rect.left()) / rect.width(),
+                    Split::Vertical => (pos.y - rect.top()) / rect.height(),
+                };
+                edits.push(Edit::Fraction(side, path.clone(), fraction));
+            }
+            let stroke = ui.visuals().widgets.noninteractive.bg_stroke;
+            ui.painter().line_segment(match split {
+                Split::Horizontal => [separator.center_top(), separator.center_bottom()],
+                Split::Vertical => [separator.left_center(), separator.right_center()],
+            }, stroke);
+            for (child, rect, branch) in [(first, a, false), (second, b, true)] {
+                path.push(branch);
+                let mut child_ui = ui.child_ui_with_id_source(rect, *ui.layout(), ("dock_node", path.clone()));
+                child_ui.set_clip_rect(rect.intersect(ui.clip_rect()));
+                draw_node(&mut child_ui, child, side, path, app_state, edits, groups);
+                path.pop();
+            }
+            ui.allocate_rect(rect, Sense::hover());
+        }
+    }
+}
+
+fn draw_group(
+    ui: &mut Ui,
+    tabs: &[Tab],
+    active: usize,
+    app_state: &mut AppState,
+    edits: &mut Vec<Edit>,
+    groups: &mut Vec<GroupRect>,
+) {
+    groups.push(GroupRect { tabs: tabs.to_vec(), rect: ui.available_rect_before_wrap() });
+    let active = active.min(tabs.len().saturating_sub(1));
+    ui.horizontal(|ui| {
+        for (i, tab) in tabs.iter().enumerate() {
+            let label = ui.selectable_label(i == active, tab.title());
+            let response = ui.interact(label.rect, ui.id().with(("dock_tab", *tab)), Sense::click_and_drag());
+            if response.clicked() {
+                edits.push(Edit::Activate(*tab));
+            }
+            if response.drag_started() {
+                edits.push(Edit::StartDrag(*tab));
+            }
+            response.context_menu(|ui| {
+                if ui.button("Detach").clicked() {
+                    let pos = ui.ctx().input(|i| i.pointer.interact_pos()).unwrap_or_default();
+                    edits.push(Edit::Move(*tab, Target::Float { pos: pos.into() }));
+                    ui.close_menu();
+                }
+                if ui.button("Close").clicked() {
+                    edits.push(Edit::Close(*tab));
+                    ui.close_menu();
+                }
+            });
+        }
+    });
+    ui.separator();
+    if let Some(tab) = tabs.get(active) {
+        draw_tab(ui, *tab, app_state);
+    }
+}
+
+fn draw_tab(ui: &mut Ui, tab: Tab, app_state: &mut AppState) {
+    match tab {
+        Tab::Hierarchy => {
+            egui::ScrollArea::vertical()
+                .id_source("hierarchy")
+                .auto_shrink([false, false])
+                .show(ui, |ui| panels::hierarchy::draw(ui, app_state));
+        }
+        Tab::Inspector => {
+            egui::ScrollArea::vertical()
+                .id_source("inspector")
+                .auto_shrink([false, false])
+                .show(ui, |ui| panels::inspector::draw(ui, app_state));
+        }
+        Tab::Resources => panels::resource_browser::draw(ui, app_state),
+        Tab::Problems => panels::problems::draw(ui, app_state),
+        Tab::Console => panels::console::draw(ui, app_state),
+    }
+}
+
+/// A tab being dragged: highlight where it would land and move it there on
+/// release. Empty areas show a strip along the viewport edge.
+fn drag_tab(ctx: &Context, dock: &DockLayout, tab: Tab, groups: &[GroupRect], edits: &mut Vec<Edit>) {
+    let Some(pos) = ctx.input(|i| i.pointer.interact_pos()) else { return; };
+    let free = ctx.available_rect();
+    let strips = [Side::Left, Side::Right, Side::Bottom].into_iter()
+        .filter(|side| dock.area(*side).root.is_none())
+        .map(|side| (side, match side {
+            Side::Left => Rect::from_min_size(free.min, Vec2::new(STRIP, free.height())),
+            Side::Right => Rect::from_min_size(Pos2::new(free.right() - STRIP, free.top()), Vec2::new(STRIP, free.height())),
+            Side::Bottom => Rect::from_min_size(Pos2::new(free.left(), free.bottom() - STRIP), Vec2::new(free.width(), STRIP)),
+        }));
+
+    let mut target = Target::Float { pos: pos.into() };
+    let mut highlight: Option<Rect> = None;
+    let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, Id::new("dock_drop")));
+    let fill = ctx.style().visuals.selection.bg_fill.linear_multiply(0.3);
+    for (side, rect) in strips {
+        painter.rect_filled(rect, 2.0, fill.linear_multiply(0.5));
+        if rect.contains(pos) {
+            target = Target::Side(side);
+            highlight = Some(rect);
+        }
+    }
+    if let Some(group) = groups.iter().find(|g| g.rect.contains(pos)) {
+        // The other tabs of its own group anchor a drop; alone it stays put.
+        if let Some(anchor) = group.tabs.iter().copied().find(|t| *t != tab) {
+            let zone = zone_at(group.rect, pos);
+            target = Target::Group { anchor, zone };
+            highlight = Some(zone_rect(group.rect, zone));
+        } else {
+            target = Target::Group { anchor: tab, zone: Zone::Center };
+        }
+    }
+    if let Some(rect) = highlight {
+        painter.rect_filled(rect, 2.0, fill);
+    }
+    egui::show_tooltip_at_pointer(ctx, Id::new("dock_drag"), |ui| {
+        ui.label(tab.title());
+    });
+    if ctx.input(|i| i.pointer.any_released()) {
+        edits.push(Edit::Move(tab, target));
+    }
+    ctx.request_repaint();
+}
+
+/// The Window menu: which panels are open, and the layouts.
+pub fn menu(ui: &mut Ui, app_state: &mut AppState) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let editor = &mut root.editor;
+    ui.menu_button("Window", |ui| {
+        for tab in Tab::ALL {
+            let mut open = editor.layout.dock.contains(tab);
+            if ui.checkbox(&mut open, tab.title()).changed() {
+                editor.layout.dock.toggle(tab);
+            }
+        }
+        ui.separator();
+        let mut apply: Option<DockLayout> = None;
+        ui.menu_button("Layouts", |ui| {
+            for name in PRESETS {
+                if ui.button(name).clicked() {
+                    apply = preset(name);
+                    ui.close_menu();
+                }
+            }
+            if !editor.layout.saved.is_empty() {
+                ui.separator();
+            }
+            let mut delete: Option<usize> = None;
+            for (i, named) in editor.layout.saved.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.button(&named.name).clicked() {
+                        apply = Some(named.dock.clone());
+                        ui.close_menu();
+                    }
+                    if ui.small_button("🗑").on_hover_text("Delete this layout").clicked() {
+                        delete = Some(i);
+                    }
+                });
+            }
+            if let Some(i) = delete {
+                editor.layout.saved.remove(i);
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut editor.layout_name_draft)
+                    .hint_text("Layout name")
+                    .desired_width(120.0));
+                let name = editor.layout_name_draft.trim().to_string();
+                if ui.add_enabled(!name.is_empty(), egui::Button::new("Save")).clicked() {
+                    let dock = editor.layout.dock.clone();
+                    match editor.layout.saved.iter_mut().find(|n| n.name == name) {
+                        Some(existing) => existing.dock = dock,
+                        None => editor.layout.saved.push(NamedLayout { name, dock }),
+                    }
+                    editor.layout_name_draft.clear();
+                    ui.close_menu();
+                }
+            });
+        });
+        if let Some(dock) = apply {
+            editor.layout.dock = dock;
+            editor.dock_generation += 1;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(dock: &DockLayout, tab: Tab) -> usize {
+        fn in_node(node: &Node, tab: Tab) -> usize {
+            match node {
+                Node::Tabs { tabs, .. } => tabs.iter().filter(|t| **t == tab).count(),
+                Node::Split { first, second, .. } => in_node(first, tab) + in_node(second, tab),
+            }
+        }
+        [&dock.left, &dock.right, &dock.bottom].iter()
+            .filter_map(|a| a.root.as_ref())
+            .map(|n| in_node(n, tab))
+            .sum::<usize>()
+            + dock.floating.iter().filter(|f| f.tab == tab).count()
+    }
+
+    #[test]
+    fn presets_hold_each_tab_at_most_once() {
+        for name in PRESETS {
+            let dock = preset(name).unwrap();
+            for tab in Tab::ALL {
+                assert!(count(&dock, tab) <= 1, "{name} {tab:?}");
+            }
+        }
+        assert!(preset("Nope").is_none());
+    }
+
+    #[test]
+    fn dock_moves_and_splits() {
+        let mut dock = DockLayout::default();
+        dock.dock(Tab::Inspector, Target::Group { anchor: Tab::Hierarchy, zone: Zone::Bottom });
+        assert_eq!(dock.right.root, None);
+        assert_eq!(dock.left.root, Some(Node::split(Split::Vertical, Node::tabs(Tab::Hierarchy), Node::tabs(Tab::Inspector))));
+
+        dock.dock(Tab::Resources, Target::Group { anchor: Tab::Inspector, zone: Zone::Center });
+        assert_eq!(dock.bottom.root, None);
+        assert_eq!(dock.left.root, Some(Node::split(
+            Split::Vertical,
+            Node::tabs(Tab::Hierarchy),
+            Node::Tabs { tabs: vec![Tab::Inspector, Tab::Resources], active: 1 },
+        )));
+        for tab in Tab::ALL {
+            assert!(count(&dock, tab) <= 1);
+        }
+    }
+
+    #[test]
+    fn removing_folds_empty_groups() {
+        let mut dock = preset("Build").unwrap();
+        dock.remove(Tab::Problems);
+        dock.remove(Tab::Console);
+        assert_eq!(dock.bottom.root, Some(Node::tabs(Tab::Resources)));
+        dock.remove(Tab::Resources);
+        assert_eq!(dock.bottom.root, None);
+
+        let mut tabs = Node::Tabs { tabs: vec![Tab::Hierarchy, Tab::Inspector, Tab::Problems], active: 2 };
+        tabs = tabs.without(Tab::Hierarchy).unwrap();
+        assert_eq!(tabs, Node::Tabs { tabs: vec![Tab::Inspector, Tab::Problems], active: 1 });
+    }
+
+    #[test]
+    fn dropping_on_itself_does_nothing() {
+        let mut dock = DockLayout::default();
+        let before = dock.clone();
+        dock.dock(Tab::Hierarchy, Target::Group { anchor: Tab::Hierarchy, zone: Zone::Left });
+        assert_eq!(dock, before);
+    }
+
+    #[test]
+    fn float_open_and_toggle() {
+        let mut dock = DockLayout::default();
+        dock.dock(Tab::Inspector, Target::Float { pos: [10.0, 20.0] });
+        assert_eq!(dock.right.root, None);
+        assert_eq!(count(&dock, Tab::Inspector), 1);
+        dock.toggle(Tab::Problems);
+        assert_eq!(dock.bottom.root, Some(Node::split(Split::Horizontal, Node::tabs(Tab::Resources), Node::tabs(Tab::Problems))));
+        dock.toggle(Tab::Problems);
+        assert!(!dock.contains(Tab::Problems));
+        dock.open(Tab::Inspector);
+        assert_eq!(count(&dock, Tab::Inspector), 1);
+    }
+
+    #[test]
+    fn zones_split_toward_the_nearest_edge() {
+        let rect = Rect::from_min_size(Pos2::ZERO, Vec2::new(100.0, 200.0));
+        assert_eq!(zone_at(rect, Pos2::new(50.0, 100.0)), Zone::Center);
+        assert_eq!(zone_at(rect, Pos2::new(5.0, 100.0)), Zone::Left);
+        assert_eq!(zone_at(rect, Pos2::new(95.0, 100.0)), Zone::Right);
+        assert_eq!(zone_at(rect, Pos2::new(50.0, 10.0)), Zone::Top);
+        assert_eq!(zone_at(rect, Pos2::new(40.0, 195.0)), Zone::Bottom);
+    }
+
+    #[test]
+    fn layout_survives_serde() {
+        let mut dock = preset("Modeling").unwrap();
+        dock.dock(Tab::Problems, Target::Float { pos: [1.0, 2.0] });
+        let json = serde_json::to_string(&dock).unwrap();
+        assert_eq!(serde_json::from_str::<DockLayout>(&json).unwrap(), dock);
+    }
+}
@@ -0,0 +1,147 @@
+//! Dragging resource browser rows into the viewport. A model spawns where
+//! the cursor ray lands, a material goes on the shape under the cursor and a
+//! particle system becomes an emitter at the landing point.
+//!
+//! The ray lands on the nearest mesh or the terrain (approximated, see
+//! `picking::terrain_hit`), else on the ground plane, else at the focus
+//! distance in front of the camera.
+
+use egui::{Context, Pos2, Rect};
+use enigma_3d::AppState;
+use nalgebra::Vector3;
+use uuid::Uuid;
+
+use crate::editor::gizmo::{math, snapping};
+use crate::editor::{actions, navigation, visibility};
+use crate::editor::state::{DragPayload, EditorRoot};
+
+pub fn start(app_state: &mut AppState, payload: DragPayload) {
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.drag = Some(payload);
+    }
+}
+
+/// Per-frame from the viewport: label the drag and drop it on release. A
+/// release outside the viewport just ends the drag.
+pub fn update(ctx: &Context, app_state: &mut AppState, pointer_in_rect: bool) {
+    let Some(payload) = app_state.get_state_data_value::<EditorRoot>("editor")
+        .and_then(|r| r.editor.drag.clone())
+        .filter(|p| !matches!(p, DragPayload::SceneObject(_))) else { return; };
+    let Some(pos) = ctx.input(|i| i.pointer.interact_pos()) else { return; };
+    let screen = ctx.screen_rect();
+
+    if ctx.input(|i| i.pointer.any_released()) {
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            root.editor.drag = None;
+        }
+        if pointer_in_rect {
+            drop_at(app_state, &payload, screen, pos);
+        }
+        return;
+    }
+
+    let mut text = label(app_state, &payload);
+    if pointer_in_rect {
+        if let DragPayload::Material(_) = payload {
+            text = match shape_under(app_state, screen, pos) {
+                Some((object, shape)) => format!("{text} → {} (shape {shape})", object_name(app_state, object)),
+                None => format!("{text} → (no object)"),
+            };
+        }
+    }
+    egui::show_tooltip_at_pointer(ctx, egui::Id::new("viewport_drop"), |ui| {
+        ui.label(text);
+    });
+}
+
+fn drop_at(app_state: &mut AppState, payload: &DragPayload, screen: Rect, pos: Pos2) {
+    match *payload {
+        DragPayload::Resource(model) => {
+            let Some(point) = landing_point(app_state, screen, pos) else { return; };
+            let Some(uuid) = actions::spawn_from_model(app_state, model) else { return; };
+            if let Some(object) = app_state.objects.iter_mut().find(|o| o.get_unique_id() == uuid) {
+                // Rest the model's bounds on the point rather than sink it to its pivot.
+                let lift = navigation::object_bounds(object)
+                    .map_or(0.0, |b| object.transform.position.y - b.min.y);
+                object.transform.position = point + Vector3::new(0.0, lift, 0.0);
+            }
+        }
+        DragPayload::Material(material) => {
+            let Some((object, shape)) = shape_under(app_state, screen, pos) else { return; };
+            let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+            let Some(project) = root.project.as_mut() else { return; };
+            let Some(scene) = project.scenes.get(project.active_scene_index).map(|s| s.uuid) else { return; };
+            project.set_assignment(scene, object, shape, material);
+            root.editor.dirty = true;
+        }
+        DragPayload::Particle(def) => {
+            let Some(point) = landing_point(app_state, screen, pos) else { return; };
+            actions::add_particle_instance(app_state, def, point.into());
+        }
+        DragPayload::SceneObject(_) => {}
+    }
+}
+
+/// Where something dropped at `pos` lands.
+pub fn landing_point(app_state: &AppState, screen: Rect, pos: Pos2) -> Option<Vector3<f32>> {
+    let camera = app_state.camera.as_ref()?;
+    let (origin, dir) = math::unproject(camera, pos, screen);
+    if let Some(hit) = snapping::surface_hit(app_state, &[], origin, dir) {
+        return Some(hit.point);
+    }
+    let focus = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map_or(10.0, |r| r.editor.navigation.focus_distance);
+    Some(ground_point(origin, dir).unwrap_or(origin + dir * focus))
+}
+
+/// Where the ray meets the ground plane y = 0 in front of its origin.
+pub fn ground_point(origin: Vector3<f32>, dir: Vector3<f32>) -> Option<Vector3<f32>> {
+    math::ray_plane_intersect(origin, dir, Vector3::zeros(), Vector3::y())
+        .filter(|p| (p - origin).dot(&dir) > 0.0)
+}
+
+/// The object and shape index under `pos`. Locked objects are see-through.
+fn shape_under(app_state: &AppState, screen: Rect, pos: Pos2) -> Option<(Uuid, usize)> {
+    let camera = app_state.camera.as_ref()?;
+    let (origin, dir) = math::unproject(camera, pos, screen);
+    let locked: Vec<Uuid> = app_state.objects.iter()
+        .map(|o| o.get_unique_id())
+        .filter(|uuid| visibility::is_locked(app_state, *uuid))
+        .collect();
+    snapping::mesh_hit(app_state, &locked, origin, dir).map(|h| (h.object, h.shape))
+}
+
+fn object_name(app_state: &AppState, uuid: Uuid) -> String {
+    app_state.objects.iter()
+        .find(|o| o.get_unique_id() == uuid)
+        .map(|o| o.name.clone())
+        .unwrap_or_default()
+}
+
+fn label(app_state: &AppState, payload: &DragPayload) -> String {
+    let Some(project) = app_state.get_state_data_value::<EditorRoot>("editor").and_then(|r| r.project.as_ref()) else {
+        return String::new();
+    };
+    match payload {
+        DragPayload::Resource(uuid) => project.manifest.iter().find(|e| e.uuid == *uuid).map(|e| e.name.clone()),
+        DragPayload::Material(uuid) => project.materials.iter().find(|m| m.uuid == *uuid).map(|m| m.name.clone()),
+        DragPayload::Particle(uuid) => project.particle_systems.iter().find(|p| p.uuid == *uuid).map(|p| p.config.name.clone()),
+        DragPayload::SceneObject(_) => None,
+    }
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ground_point_only_in_front() {
+        let origin = Vector3::new(1.0, 4.0, 0.0);
+        let down = Vector3::new(0.0, -1.0, 0.0);
+        let p = ground_point(origin, down).unwrap();
+        assert!((p - Vector3::new(1.0, 0.0, 0.0)).norm() < 1e-5);
+        assert!(ground_point(origin, -down).is_none());
+        assert!(ground_point(origin, Vector3::x()).is_none());
+    }
+}
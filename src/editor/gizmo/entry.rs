@@ -0,0 +1,94 @@
+//! Keyboard control of a drag in progress, Blender style: typing a number
+//! sets the exact distance, angle or factor, X/Y/Z (Shift for the plane
+//! across) changes the constraint, Enter confirms and Escape cancels. All but
+//! Escape are the Gizmo Drag commands and follow the keymap.
+
+use egui::{Context, Event, Key};
+
+use crate::editor::commands::{CommandId, Keymap};
+use crate::editor::state::Axis;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EntryAction {
+    Confirm,
+    Cancel,
+}
+
+/// Feed this frame's typing into `entry`; returns Confirm or Cancel if
+/// pressed.
+pub fn read(ctx: &Context, keymap: &Keymap, entry: &mut String) -> Option<EntryAction> {
+    let confirm = keymap.shortcut(CommandId::ConfirmDrag);
+    let (text, backspace, enter, escape) = ctx.input(|i| {
+        let text: String = i.events.iter()
+            .filter_map(|e| match e {
+                Event::Text(t) => Some(t.as_str()),
+                _ => None,
+            })
+            .collect();
+        (text, i.key_pressed(Key::Backspace), confirm.is_some_and(|s| s.pressed(i)), i.key_pressed(Key::Escape))
+    });
+    accept(entry, &text);
+    if backspace {
+        entry.pop();
+    }
+    if escape { return Some(EntryAction::Cancel); }
+    enter.then_some(EntryAction::Confirm)
+}
+
+/// Append the digits and first decimal point of `text`; `-` flips the sign.
+pub fn accept(entry: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '0'..='9' => entry.push(c),
+            '.' | ',' if !entry.contains('.') => entry.push('.'),
+            '-' => match entry.strip_prefix('-') {
+                Some(rest) => *entry = rest.to_string(),
+                None => entry.insert(0, '-'),
+            },
+            _ => {}
+        }
+    }
+}
+
+/// The typed value, once there's a number to read.
+pub fn value(entry: &str) -> Option<f32> {
+    entry.parse().ok()
+}
+
+/// The axis whose constrain key was pressed this frame, and whether Shift
+/// was held on top of its binding.
+pub fn constraint_key(ctx: &Context, keymap: &Keymap) -> Option<(Axis, bool)> {
+    let bindings = [(CommandId::ConstrainX, Axis::X), (CommandId::ConstrainY, Axis::Y), (CommandId::ConstrainZ, Axis::Z)];
+    ctx.input(|i| {
+        let m = i.modifiers;
+        bindings.into_iter()
+            .filter_map(|(id, axis)| Some((keymap.shortcut(id)?, axis)))
+            .find(|(s, _)| i.key_pressed(s.key) && m.command == s.command && m.alt == s.alt && (m.shift || !s.shift))
+            .map(|(s, axis)| (axis, m.shift && !s.shift))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_builds_a_number() {
+        let mut entry = String::new();
+        accept(&mut entry, "2.5.1x");
+        assert_eq!(entry, "2.51");
+        accept(&mut entry, "-");
+        assert_eq!(value(&entry), Some(-2.51));
+        accept(&mut entry, "-");
+        assert_eq!(entry, "2.51");
+    }
+
+    #[test]
+    fn value_needs_a_digit() {
+        assert_eq!(value(""), None);
+        assert_eq!(value("-"), None);
+        assert_eq!(value("."), None);
+        assert_eq!(value("90"), Some(90.0));
+        assert_eq!(value("-.5"), Some(-0.5));
+    }
+}
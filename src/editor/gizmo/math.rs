@@ -1,11 +1,11 @@
 use egui::{Pos2, Rect};
 use enigma_3d::camera::Camera;
-use nalgebra::Vector3;
+use nalgebra::{UnitQuaternion, Vector3};
 
 /// Build a right/up screen basis from `forward`. Falls back to an alternate
 /// reference axis when `forward` is nearly parallel to world up (gimbal lock),
 /// so the basis is always finite and orthonormal.
-fn screen_basis(forward: Vector3<f32>) -> (Vector3<f32>, Vector3<f32>) {
+pub fn screen_basis(forward: Vector3<f32>) -> (Vector3<f32>, Vector3<f32>) {
     let world_up = Vector3::new(0.0, 1.0, 0.0);
     let right_raw = forward.cross(&world_up);
     let right = if right_raw.norm_squared() < 1e-6 {
@@ -158,6 +158,64 @@ pub fn snap(value: f32, step: f32) -> f32 {
     (value / step).round() * step
 }
 
+/// Average of `points`; the shared pivot of a group. None when empty.
+pub fn centroid(points: &[Vector3<f32>]) -> Option<Vector3<f32>> {
+    if points.is_empty() { return None; }
+    Some(points.iter().sum::<Vector3<f32>>() / points.len() as f32)
+}
+
+/// `point` rotated by `rotation` around `pivot`.
+pub fn rotate_about(point: Vector3<f32>, pivot: Vector3<f32>, rotation: UnitQuaternion<f32>) -> Vector3<f32> {
+    pivot + rotation * (point - pivot)
+}
+
+/// `point` scaled per axis by `factor` away from `pivot`.
+pub fn scale_about(point: Vector3<f32>, pivot: Vector3<f32>, factor: Vector3<f32>) -> Vector3<f32> {
+    pivot + (point - pivot).component_mul(&factor)
+}
+
+/// Whether `p` lies inside the polygon `points` (even-odd rule).
+pub fn point_in_polygon(p: Pos2, points: &[Pos2]) -> bool {
+    let mut inside = false;
+    for i in 0..points.len() {
+        let (a, b) = (points[i], points[(i + 1) % points.len()]);
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Rotation for a trackball drag of `delta` pixels, `radians_per_pixel` far,
+/// seen along `forward`: the side facing the camera follows the cursor.
+pub fn trackball(delta: egui::Vec2, forward: Vector3<f32>, radians_per_pixel: f32) -> Option<(Vector3<f32>, f32)> {
+    let (right, up) = screen_basis(forward);
+    let axis = (up * delta.x + right * delta.y).try_normalize(1e-6)?;
+    Some((axis, delta.length() * radians_per_pixel))
+}
+
+/// Distance along the ray to triangle `abc` (Möller–Trumbore), hitting
+/// either side. None when the ray misses or the triangle is behind it.
+pub fn ray_triangle(
+    origin: Vector3<f32>,
+    dir: Vector3<f32>,
+    [a, b, c]: [Vector3<f32>; 3],
+) -> Option<f32> {
+    let (ab, ac) = (b - a, c - a);
+    let p = dir.cross(&ac);
+    let det = ab.dot(&p);
+    if det.abs() < 1e-8 { return None; }
+    let inv = 1.0 / det;
+    let to_origin = origin - a;
+    let u = to_origin.dot(&p) * inv;
+    if !(0.0..=1.0).contains(&u) { return None; }
+    let q = to_origin.cross(&ab);
+    let v = dir.dot(&q) * inv;
+    if v < 0.0 || u + v > 1.0 { return None; }
+    let t = ac.dot(&q) * inv;
+    (t > 1e-5).then_some(t)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -323,4 +381,60 @@ mod tests {
         assert!((snap(3.7, 0.0) - 3.7).abs() < 1e-6);
         assert!((snap(3.7, -1.0) - 3.7).abs() < 1e-6);
     }
+
+    #[test]
+    fn centroid_of_points() {
+        assert!(centroid(&[]).is_none());
+        let c = centroid(&[Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 4.0, -2.0)]).unwrap();
+        assert!((c - Vector3::new(1.0, 2.0, -1.0)).norm() < 1e-6);
+    }
+
+    #[test]
+    fn rotate_and_scale_about_pivot() {
+        let pivot = Vector3::new(1.0, 0.0, 0.0);
+        let quarter = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), std::f32::consts::FRAC_PI_2);
+        // (2,0,0) is one unit along +X from the pivot; a quarter turn about +Y
+        // takes +X to -Z.
+        let p = rotate_about(Vector3::new(2.0, 0.0, 0.0), pivot, quarter);
+        assert!((p - Vector3::new(1.0, 0.0, -1.0)).norm() < 1e-5, "p = {:?}", p);
+        assert!((rotate_about(pivot, pivot, quarter) - pivot).norm() < 1e-6);
+
+        let s = scale_about(Vector3::new(2.0, 1.0, 0.0), pivot, Vector3::new(2.0, 3.0, 1.0));
+        assert!((s - Vector3::new(3.0, 3.0, 0.0)).norm() < 1e-6);
+    }
+
+    #[test]
+    fn ray_triangle_hits_inside_and_misses_outside() {
+        let tri = [Vector3::new(-1.0, 0.0, -1.0), Vector3::new(1.0, 0.0, -1.0), Vector3::new(0.0, 0.0, 1.0)];
+        let down = Vector3::new(0.0, -1.0, 0.0);
+        let t = ray_triangle(Vector3::new(0.0, 3.0, 0.0), down, tri).unwrap();
+        assert!((t - 3.0).abs() < 1e-5);
+        // From below, looking up: the back face still counts.
+        assert!(ray_triangle(Vector3::new(0.0, -2.0, 0.0), -down, tri).is_some());
+        assert!(ray_triangle(Vector3::new(2.0, 3.0, 0.0), down, tri).is_none());
+        // Behind the origin.
+        assert!(ray_triangle(Vector3::new(0.0, -2.0, 0.0), down, tri).is_none());
+    }
+
+    #[test]
+    fn point_in_polygon_square() {
+        let square = [Pos2::new(0.0, 0.0), Pos2::new(10.0, 0.0), Pos2::new(10.0, 10.0), Pos2::new(0.0, 10.0)];
+        assert!(point_in_polygon(Pos2::new(5.0, 5.0), &square));
+        assert!(!point_in_polygon(Pos2::new(15.0, 5.0), &square));
+        assert!(!point_in_polygon(Pos2::new(5.0, -1.0), &square));
+    }
+
+    #[test]
+    fn trackball_front_follows_cursor() {
+        let forward = Vector3::new(0.0, 0.0, -1.0);
+        let front = Vector3::new(0.0, 0.0, 1.0);
+        let turn = |d: egui::Vec2| {
+            let (axis, angle) = trackball(d, forward, 0.01).unwrap();
+            UnitQuaternion::from_axis_angle(&nalgebra::Unit::new_normalize(axis), angle) * front
+        };
+        // Dragging right swings the front to +X; dragging down (screen +y) to -Y.
+        assert!(turn(egui::vec2(50.0, 0.0)).x > 0.1);
+        assert!(turn(egui::vec2(0.0, 50.0)).y < -0.1);
+        assert!(trackball(egui::Vec2::ZERO, forward, 0.01).is_none());
+    }
 }
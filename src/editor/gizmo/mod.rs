@@ -1,4 +1,6 @@
+pub mod entry;
 pub mod math;
+pub mod snapping;
 pub mod toolbar;
 pub mod rotate;
 pub mod scale;
@@ -8,7 +10,9 @@ use egui::{Context, Pos2, Rect, Ui};
 use enigma_3d::AppState;
 use nalgebra::{UnitQuaternion, Vector3};
 
-use crate::editor::state::{Axis, Drag, EditorRoot, GizmoMode, Selection, Space};
+use crate::editor::{cameras, lights, scene_graph, visibility};
+use crate::editor::gizmo::entry::EntryAction;
+use crate::editor::state::{Axis, Drag, EditorRoot, GizmoMode, GroupStart, Handle, Selection, SnapSettings, Space};
 
 pub fn handle_input(ctx: &Context, rect: Rect, app_state: &mut AppState) {
     // Reset the per-frame consumed flag at the start of each frame.
@@ -16,29 +20,8 @@ pub fn handle_input(ctx: &Context, rect: Rect, app_state: &mut AppState) {
         root.editor.gizmo.consumed_click_this_frame = false;
     }
 
+    // Mode hotkeys are commands (see `editor::commands`).
     let rmb = ctx.input(|i| i.pointer.secondary_down());
-
-    // Hotkeys — only when the pointer is in the viewport and RMB isn't held
-    // (so the existing camera-fly Q/W/E keybinds still take precedence).
-    let pointer = ctx.input(|i| i.pointer.interact_pos());
-    let in_rect = pointer.map(|p| rect.contains(p)).unwrap_or(false);
-    if in_rect && !rmb {
-        let (q, w, e, r) = ctx.input(|i| (
-            i.key_pressed(egui::Key::Q),
-            i.key_pressed(egui::Key::W),
-            i.key_pressed(egui::Key::E),
-            i.key_pressed(egui::Key::R),
-        ));
-        if q || w || e || r {
-            if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-                if q { root.editor.gizmo.mode = GizmoMode::None; }
-                if w { root.editor.gizmo.mode = GizmoMode::Translate; }
-                if e { root.editor.gizmo.mode = GizmoMode::Rotate; }
-                if r { root.editor.gizmo.mode = GizmoMode::Scale; }
-            }
-        }
-    }
-
     if rmb { return; } // RMB-fly suppresses gizmo input.
 
     let Some(cursor) = ctx.input(|i| i.pointer.interact_pos()) else { return; };
@@ -48,19 +31,25 @@ pub fn handle_input(ctx: &Context, rect: Rect, app_state: &mut AppState) {
     let Some(camera) = app_state.camera.as_ref() else { return; };
     let camera = camera.clone();
 
-    let (mode, space, snap_enabled, drag_some) = {
+    let (mode, space, snap_enabled, settings, drag_some) = {
         let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
         (
             root.editor.gizmo.mode,
             root.editor.gizmo.space,
             root.editor.gizmo.snap_enabled,
+            root.editor.gizmo.snap,
             root.editor.gizmo.drag.is_some(),
         )
     };
 
-    // Effective snap: toolbar XOR Ctrl-held.
-    let ctrl = ctx.input(|i| i.modifiers.ctrl);
-    let snap = snap_enabled ^ ctrl;
+    // Effective snap: toolbar XOR Ctrl-held. Holding V snaps to vertices.
+    let (ctrl, v) = ctx.input(|i| (i.modifiers.ctrl, i.key_down(egui::Key::V)));
+    let snap = SnapInput {
+        increments: (snap_enabled ^ ctrl).then_some(settings),
+        vertex: settings.vertex || v,
+        surface: settings.surface,
+        align_to_normal: settings.align_to_normal,
+    };
 
     let rotation = selection_rotation(app_state);
 
@@ -71,11 +60,28 @@ pub fn handle_input(ctx: &Context, rect: Rect, app_state: &mut AppState) {
 
     // Drag in progress: update and possibly end.
     if drag_some {
+        let constraint = app_state.get_state_data_value::<EditorRoot>("editor")
+            .and_then(|r| entry::constraint_key(ctx, &r.editor.keymap));
+        if let Some((axis, shift)) = constraint {
+            constrain(app_state, axis, shift);
+        }
+        let action = app_state.get_state_data_value_mut::<EditorRoot>("editor")
+            .and_then(|r| entry::read(ctx, &r.editor.keymap, &mut r.editor.gizmo.entry));
+        if action == Some(EntryAction::Cancel) {
+            apply_group(app_state, GroupStart::clone);
+            end_drag(app_state);
+            return;
+        }
+        // Space may have just been flipped by a repeated X/Y/Z.
+        let space = app_state.get_state_data_value::<EditorRoot>("editor")
+            .map_or(space, |r| r.editor.gizmo.space);
         let released = ctx.input(|i| i.pointer.primary_released());
-        update_active_drag(app_state, cursor, pivot, space, rotation, snap, &camera, screen);
-        if released {
+        let frame = DragFrame { cursor, pivot, space, rotation, camera: &camera, screen };
+        update_active_drag(app_state, &frame, &snap);
+        if released || action == Some(EntryAction::Confirm) {
             end_drag(app_state);
         }
+        ctx.request_repaint();
         return;
     }
 
@@ -83,17 +89,11 @@ pub fn handle_input(ctx: &Context, rect: Rect, app_state: &mut AppState) {
     let camera_pos = Vector3::from(camera.get_position());
     let size = translate::handle_world_size(camera_pos, pivot, camera.fov);
 
-    let target_full = matches!(
-        app_state.get_state_data_value::<EditorRoot>("editor")
-            .map(|r| r.editor.selection.clone()),
-        Some(Selection::SceneObject(_))
-    );
+    let target_full = selection_has_object(app_state);
 
     let hovered = match mode {
-        GizmoMode::Translate => translate::hit_test(cursor, pivot, size, space, rotation, &camera, screen)
-            .map(crate::editor::state::Handle::Axis),
-        GizmoMode::Rotate if target_full => rotate::hit_test(cursor, pivot, size, space, rotation, &camera, screen)
-            .map(crate::editor::state::Handle::Axis),
+        GizmoMode::Translate => translate::hit_test(cursor, pivot, size, space, rotation, &camera, screen),
+        GizmoMode::Rotate if target_full => rotate::hit_test(cursor, pivot, size, space, rotation, &camera, screen),
         GizmoMode::Scale if target_full => scale::hit_test(cursor, pivot, size, rotation, &camera, screen),
         _ => None,
     };
@@ -106,18 +106,22 @@ pub fn handle_input(ctx: &Context, rect: Rect, app_state: &mut AppState) {
     if pressed {
         if let Some(handle) = hovered {
             let start_scale = selection_scale(app_state).unwrap_or(Vector3::new(1.0, 1.0, 1.0));
-            let drag = match (mode, handle) {
-                (GizmoMode::Translate, crate::editor::state::Handle::Axis(axis)) =>
-                    Some(translate::begin_drag(axis, cursor, pivot, space, rotation, &camera, screen)),
-                (GizmoMode::Rotate, crate::editor::state::Handle::Axis(axis)) =>
-                    rotate::begin_drag(axis, cursor, pivot, space, rotation, &camera, screen),
-                (GizmoMode::Scale, h) =>
-                    scale::begin_drag(h, cursor, pivot, start_scale, &camera, screen),
-                _ => None,
+            let drag = match mode {
+                GizmoMode::Translate => Some(translate::begin_drag(handle, cursor, pivot)),
+                GizmoMode::Rotate => Some(rotate::begin_drag(handle, cursor, rotation)),
+                GizmoMode::Scale => scale::begin_drag(handle, cursor, pivot, start_scale, &camera, screen),
+                GizmoMode::None => None,
             };
             if let Some(drag) = drag {
+                let group = capture_group(app_state);
+                let snap_source = matches!(drag, Drag::Translate { .. })
+                    .then(|| snapping::vertex_source(app_state, &group_objects(&group), &camera, screen, cursor))
+                    .flatten();
                 if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
                     root.editor.gizmo.drag = Some(drag);
+                    root.editor.gizmo.drag_group = group;
+                    root.editor.gizmo.snap_source = snap_source;
+                    root.editor.gizmo.entry.clear();
                     root.editor.gizmo.consumed_click_this_frame = true;
                 }
             }
@@ -138,27 +142,19 @@ pub fn draw(ui: &mut Ui, rect: Rect, app_state: &mut AppState) {
     if let Some(pivot) = selection_pivot(app_state) {
         if let Some(camera) = app_state.camera.as_ref() {
             let camera = camera.clone();
-            let (mode, space, hovered_handle, hovered_axis, dragging_axis) = {
+            let (mode, space, hovered_handle, dragging_handle) = {
                 let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else {
                     toolbar::draw(ui.ctx(), rect, app_state);
                     return;
                 };
-                let drag_axis = match &root.editor.gizmo.drag {
-                    Some(Drag::Translate { axis, .. }) => Some(*axis),
-                    Some(Drag::Rotate { axis, .. }) => Some(*axis),
-                    _ => None,
-                };
-                let hover_handle = root.editor.gizmo.hovered_handle;
-                let hover_axis = match hover_handle {
-                    Some(crate::editor::state::Handle::Axis(a)) => Some(a),
-                    _ => None,
-                };
+                let drag_handle = root.editor.gizmo.drag.as_ref().map(|d| match d {
+                    Drag::Translate { handle, .. } | Drag::Rotate { handle, .. } | Drag::Scale { handle, .. } => *handle,
+                });
                 (
                     root.editor.gizmo.mode,
                     root.editor.gizmo.space,
-                    hover_handle,
-                    hover_axis,
-                    drag_axis,
+                    root.editor.gizmo.hovered_handle,
+                    drag_handle,
                 )
             };
 
@@ -168,52 +164,77 @@ pub fn draw(ui: &mut Ui, rect: Rect, app_state: &mut AppState) {
 
             // PositionOnly targets always show the translate gizmo regardless of mode
             // (so long as a gizmo mode is active).
-            let target_full = matches!(
-                app_state.get_state_data_value::<EditorRoot>("editor")
-                    .map(|r| &r.editor.selection),
-                Some(Selection::SceneObject(_))
-            );
+            let target_full = selection_has_object(app_state);
             let show_translate = matches!(mode, GizmoMode::Translate)
                 || (!target_full && !matches!(mode, GizmoMode::None));
 
             if show_translate {
                 translate::draw(ui, rect, pivot, size, space, rotation, &camera,
-                    hovered_axis, dragging_axis);
+                    hovered_handle, dragging_handle);
             }
 
             let show_rotate = matches!(mode, GizmoMode::Rotate) && target_full;
             if show_rotate {
                 rotate::draw(ui, rect, pivot, size, space, rotation, &camera,
-                    hovered_axis, dragging_axis);
+                    hovered_handle, dragging_handle);
             }
 
             let show_scale = matches!(mode, GizmoMode::Scale) && target_full;
             if show_scale {
-                let dragging_handle = match &app_state.get_state_data_value::<EditorRoot>("editor")
-                    .and_then(|r| r.editor.gizmo.drag.as_ref())
-                {
-                    Some(Drag::Scale { handle, .. }) => Some(*handle),
-                    _ => None,
-                };
                 scale::draw(ui, rect, pivot, size, rotation, &camera, hovered_handle, dragging_handle);
             }
         }
     }
+    draw_entry(ui, app_state);
     toolbar::draw(ui.ctx(), rect, app_state);
 }
 
-pub(crate) fn selection_pivot(app_state: &AppState) -> Option<Vector3<f32>> {
-    let root = app_state.get_state_data_value::<EditorRoot>("editor")?;
-    match &root.editor.selection {
+/// Mid-drag, a label by the cursor with the constraint and any typed value.
+fn draw_entry(ui: &mut Ui, app_state: &AppState) {
+    let Some(gizmo) = app_state.get_state_data_value::<EditorRoot>("editor").map(|r| &r.editor.gizmo) else { return; };
+    let Some(drag) = gizmo.drag.as_ref() else { return; };
+    let Some(cursor) = ui.ctx().input(|i| i.pointer.interact_pos()) else { return; };
+    let (handle, unit) = match drag {
+        Drag::Translate { handle, .. } => (*handle, ""),
+        Drag::Rotate { handle, .. } => (*handle, "°"),
+        Drag::Scale { handle, .. } => (*handle, "×"),
+    };
+    let space = match gizmo.space {
+        Space::World => "",
+        Space::Local => " local",
+    };
+    let constraint = match handle {
+        Handle::Axis(a) => format!("{a:?}{space}"),
+        Handle::Plane(a) => {
+            let (u, v) = translate::plane_axes(a);
+            format!("{u:?}{v:?}{space}")
+        }
+        Handle::View => "View".to_string(),
+        Handle::Trackball => "Trackball".to_string(),
+        Handle::Center => "Uniform".to_string(),
+    };
+    let text = if gizmo.entry.is_empty() {
+        constraint
+    } else {
+        format!("{constraint}: {}{unit}", gizmo.entry)
+    };
+    let painter = ui.painter();
+    let at = cursor + egui::vec2(16.0, 16.0);
+    let galley = painter.layout_no_wrap(text, egui::FontId::monospace(12.0), egui::Color32::WHITE);
+    painter.rect_filled(egui::Rect::from_min_size(at, galley.size()).expand(3.0), 3.0, egui::Color32::from_black_alpha(180));
+    painter.galley(at, galley);
+}
+
+/// Where a selected scene item sits; None for items without a position.
+fn item_position(app_state: &AppState, root: &EditorRoot, item: &Selection) -> Option<Vector3<f32>> {
+    match item {
         Selection::SceneObject(uuid) => app_state
             .objects
             .iter()
             .find(|o| o.get_unique_id() == *uuid)
             .map(|o| o.transform.position),
-        Selection::Light(idx) => app_state
-            .light
-            .get(*idx)
-            .map(|l| Vector3::from(l.position)),
+        Selection::Light(uuid) => lights::get(app_state, *uuid).map(|l| Vector3::from(l.position)),
+        Selection::Camera => root.editor.game_camera.as_ref().map(|c| c.transform.position),
         Selection::ParticleInstance(uuid) => root
             .project
             .as_ref()
@@ -224,11 +245,27 @@ pub(crate) fn selection_pivot(app_state: &AppState) -> Option<Vector3<f32>> {
     }
 }
 
+/// Locked objects stay selected but the gizmo leaves them alone.
+fn is_locked(app_state: &AppState, item: &Selection) -> bool {
+    matches!(item, Selection::SceneObject(uuid) if visibility::is_locked(app_state, *uuid))
+}
+
+/// Centroid of the selected scene items; the gizmo's shared pivot.
+pub(crate) fn selection_pivot(app_state: &AppState) -> Option<Vector3<f32>> {
+    let root = app_state.get_state_data_value::<EditorRoot>("editor")?;
+    let positions: Vec<Vector3<f32>> = root.editor.selection.items().iter()
+        .filter(|item| !is_locked(app_state, item))
+        .filter_map(|item| item_position(app_state, root, item))
+        .collect();
+    math::centroid(&positions)
+}
+
+/// Local axes of a single selected object. Groups use world axes.
 pub(crate) fn selection_rotation(app_state: &AppState) -> Vector3<f32> {
     let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else {
         return Vector3::zeros();
     };
-    if let Selection::SceneObject(uuid) = &root.editor.selection {
+    if let [Selection::SceneObject(uuid)] = root.editor.selection.items() {
         if let Some(o) = app_state.objects.iter().find(|o| o.get_unique_id() == *uuid) {
             return o.transform.rotation;
         }
@@ -236,94 +273,268 @@ pub(crate) fn selection_rotation(app_state: &AppState) -> Vector3<f32> {
     Vector3::zeros()
 }
 
-fn update_active_drag(
-    app_state: &mut AppState,
-    cursor: Pos2,
-    pivot: Vector3<f32>,
-    space: Space,
-    rotation: Vector3<f32>,
-    snap: bool,
-    camera: &enigma_3d::camera::Camera,
-    screen: Rect,
-) {
+/// Rotate and scale only make sense when an object is part of the selection.
+fn selection_has_object(app_state: &AppState) -> bool {
+    app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| r.editor.selection.items().iter()
+            .any(|i| matches!(i, Selection::SceneObject(_)) && !is_locked(app_state, i)))
+        .unwrap_or(false)
+}
+
+/// Start transforms of every selected scene item, followed by the children
+/// of selected objects so they move with their parents, hidden ones too.
+fn capture_group(app_state: &AppState) -> Vec<GroupStart> {
+    let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return Vec::new(); };
+    let mut items: Vec<Selection> = root.editor.selection.items().iter()
+        .filter(|item| !is_locked(app_state, item))
+        .cloned()
+        .collect();
+    for uuid in root.editor.selection.scene_objects() {
+        if visibility::is_locked(app_state, uuid) { continue; }
+        for child in scene_graph::descendants_of(app_state, uuid) {
+            let child = Selection::SceneObject(child);
+            if !items.contains(&child) {
+                items.push(child);
+            }
+        }
+    }
+    items.iter().filter_map(|item| {
+        let (position, rotation, scale) = match item {
+            Selection::SceneObject(uuid) => {
+                let o = visibility::find(app_state, *uuid)?;
+                let r = o.transform.rotation;
+                (o.transform.position, UnitQuaternion::from_euler_angles(r.x, r.y, r.z), o.transform.scale)
+            }
+            _ => (item_position(app_state, root, item)?, UnitQuaternion::identity(), Vector3::new(1.0, 1.0, 1.0)),
+        };
+        Some(GroupStart { item: item.clone(), position, rotation, scale })
+    }).collect()
+}
+
+fn update_active_drag(app_state: &mut AppState, frame: &DragFrame, snap: &SnapInput) {
     let drag_snapshot = app_state.get_state_data_value::<EditorRoot>("editor")
-        .and_then(|r| r.editor.gizmo.drag.as_ref().map(|d| match d {
-            Drag::Translate { axis, start_pos, start_on_axis } =>
-                DragSnapshot::Translate(*axis, *start_pos, *start_on_axis),
-            Drag::Rotate { axis, start_quat, start_dir } =>
-                DragSnapshot::Rotate(*axis, *start_quat, *start_dir),
+        .and_then(|r| r.editor.gizmo.drag.as_ref().map(|d| (match d {
+            Drag::Translate { handle, start_pos, start_cursor } =>
+                DragSnapshot::Translate(*handle, *start_pos, *start_cursor),
+            Drag::Rotate { handle, start_quat, start_cursor } =>
+                DragSnapshot::Rotate(*handle, *start_quat, *start_cursor),
             Drag::Scale { handle, start_scale, start_pivot_screen, start_distance, .. } =>
                 DragSnapshot::Scale(*handle, *start_scale, *start_pivot_screen, *start_distance),
-        }));
-    let Some(snap_data) = drag_snapshot else { return; };
+        }, entry::value(&r.editor.gizmo.entry))));
+    let Some((snap_data, typed)) = drag_snapshot else { return; };
     match snap_data {
-        DragSnapshot::Translate(axis, start_pos, start_on_axis) => {
-            let new_pos = translate::update_drag(
-                axis, start_pos, start_on_axis, cursor, space, rotation, snap, camera, screen,
-            );
-            apply_position(app_state, new_pos);
+        DragSnapshot::Translate(handle, start_pos, start_cursor) => {
+            if typed.is_none() && snap_to_geometry(app_state, frame.cursor, start_pos, snap, frame.camera, frame.screen) {
+                return;
+            }
+            let step = snap.increments.map(|s| s.translate);
+            let new_pos = translate::update_drag(handle, start_pos, start_cursor, frame, step, typed);
+            apply_position(app_state, start_pos, new_pos);
         }
-        DragSnapshot::Rotate(axis, start_quat, start_dir) => {
-            let new_rot = rotate::update_drag(
-                axis, start_quat, start_dir, pivot, cursor, space, snap, camera, screen,
-            );
-            apply_rotation(app_state, new_rot);
+        DragSnapshot::Rotate(handle, start_quat, start_cursor) => {
+            let step = snap.increments.map(|s| s.rotate_degrees.to_radians());
+            let new_rot = rotate::update_drag(handle, start_quat, start_cursor, frame, step, typed);
+            apply_rotation(app_state, start_quat, new_rot);
         }
         DragSnapshot::Scale(handle, start_scale, start_pivot_screen, start_distance) => {
+            let step = snap.increments.map(|s| s.scale);
             let new_scale = scale::update_drag(
-                handle, start_scale, start_pivot_screen, start_distance, cursor, snap,
+                handle, start_scale, start_pivot_screen, start_distance, frame.cursor, step, typed,
             );
-            apply_scale(app_state, new_scale);
+            apply_scale(app_state, start_scale, new_scale);
         }
     }
 }
 
+/// Where the cursor and gizmo are this frame, shared by the drag updates.
+pub struct DragFrame<'a> {
+    pub cursor: Pos2,
+    /// The gizmo's current position; rotation rings turn about it.
+    pub pivot: Vector3<f32>,
+    pub space: Space,
+    /// Rotation of the active object, for local-space axes.
+    pub rotation: Vector3<f32>,
+    pub camera: &'a enigma_3d::camera::Camera,
+    pub screen: Rect,
+}
+
+/// Snapping in effect for this frame's drag update.
+struct SnapInput {
+    /// Step sizes, while Snap is on (Ctrl flips it).
+    increments: Option<SnapSettings>,
+    vertex: bool,
+    surface: bool,
+    align_to_normal: bool,
+}
+
+/// Surface or vertex snapping for a translate drag. Returns false when
+/// neither is on or nothing is under the cursor, and the drag falls back
+/// to its axis.
+fn snap_to_geometry(
+    app_state: &mut AppState,
+    cursor: Pos2,
+    start_pos: Vector3<f32>,
+    snap: &SnapInput,
+    camera: &enigma_3d::camera::Camera,
+    screen: Rect,
+) -> bool {
+    if !snap.vertex && !snap.surface { return false; }
+    let Some((group, source)) = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| (r.editor.gizmo.drag_group.clone(), r.editor.gizmo.snap_source)) else { return false; };
+    let dragged = group_objects(&group);
+
+    if snap.vertex {
+        if let Some(target) = snapping::vertex_target(app_state, &dragged, camera, screen, cursor) {
+            apply_position(app_state, start_pos, start_pos + target - source.unwrap_or(start_pos));
+            return true;
+        }
+    }
+    if snap.surface {
+        let (origin, dir) = math::unproject(camera, cursor, screen);
+        if let Some(hit) = snapping::surface_hit(app_state, &dragged, origin, dir) {
+            // Aligning turns the first selected object's up axis onto the normal.
+            let up = group.iter()
+                .find(|g| matches!(g.item, Selection::SceneObject(_)))
+                .map(|g| g.rotation * Vector3::y())
+                .unwrap_or_else(Vector3::y);
+            let turn = if snap.align_to_normal { snapping::align(up, hit.normal) } else { UnitQuaternion::identity() };
+            apply_group(app_state, |start| GroupStart {
+                position: hit.point + turn * (start.position - start_pos),
+                rotation: turn * start.rotation,
+                ..start.clone()
+            });
+            return true;
+        }
+    }
+    false
+}
+
+/// Scene objects among the items of a drag group.
+fn group_objects(group: &[GroupStart]) -> Vec<uuid::Uuid> {
+    group.iter()
+        .filter_map(|g| match g.item {
+            Selection::SceneObject(uuid) => Some(uuid),
+            _ => None,
+        })
+        .collect()
+}
+
 enum DragSnapshot {
-    Translate(Axis, Vector3<f32>, Vector3<f32>),
-    Rotate(Axis, UnitQuaternion<f32>, Vector3<f32>),
-    Scale(crate::editor::state::Handle, Vector3<f32>, Pos2, f32),
+    Translate(Handle, Vector3<f32>, Pos2),
+    Rotate(Handle, UnitQuaternion<f32>, Pos2),
+    Scale(Handle, Vector3<f32>, Pos2, f32),
+}
+
+/// X/Y/Z mid-drag: lock a move or turn to that axis, or with Shift a move to
+/// the plane across it. Pressing the current axis again flips World/Local.
+fn constrain(app_state: &mut AppState, axis: Axis, shift: bool) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let gizmo = &mut root.editor.gizmo;
+    let (handle, next) = match gizmo.drag.as_mut() {
+        Some(Drag::Translate { handle, .. }) =>
+            (handle, if shift { Handle::Plane(axis) } else { Handle::Axis(axis) }),
+        Some(Drag::Rotate { handle, .. }) | Some(Drag::Scale { handle, .. }) => (handle, Handle::Axis(axis)),
+        None => return,
+    };
+    if *handle == next {
+        gizmo.space = match gizmo.space {
+            Space::World => Space::Local,
+            Space::Local => Space::World,
+        };
+    } else {
+        *handle = next;
+    }
+}
+
+/// Whether a gizmo drag is in progress; its keys take precedence.
+pub fn is_dragging(app_state: &AppState) -> bool {
+    app_state.get_state_data_value::<EditorRoot>("editor")
+        .is_some_and(|r| r.editor.gizmo.drag.is_some())
+}
+
+/// Move every item captured at drag start by the same translation.
+fn apply_position(app_state: &mut AppState, start_pos: Vector3<f32>, new_pos: Vector3<f32>) {
+    let delta = new_pos - start_pos;
+    apply_group(app_state, |start| GroupStart { position: start.position + delta, ..start.clone() });
+}
+
+/// Turn the group around its pivot. `start_quat` is the gizmo's orientation at
+/// drag start and `new_rot` the Euler rotation the drag turned it into.
+fn apply_rotation(app_state: &mut AppState, start_quat: UnitQuaternion<f32>, new_rot: Vector3<f32>) {
+    let delta = UnitQuaternion::from_euler_angles(new_rot.x, new_rot.y, new_rot.z) * start_quat.inverse();
+    let Some(pivot) = group_pivot(app_state) else { return; };
+    apply_group(app_state, |start| GroupStart {
+        position: math::rotate_about(start.position, pivot, delta),
+        rotation: delta * start.rotation,
+        ..start.clone()
+    });
+}
+
+/// Scale the group away from its pivot by the factor the drag produced.
+fn apply_scale(app_state: &mut AppState, start_scale: Vector3<f32>, new_scale: Vector3<f32>) {
+    let safe = |v: f32| if v.abs() < 1e-6 { 1e-6 } else { v };
+    let factor = Vector3::new(
+        new_scale.x / safe(start_scale.x),
+        new_scale.y / safe(start_scale.y),
+        new_scale.z / safe(start_scale.z),
+    );
+    let Some(pivot) = group_pivot(app_state) else { return; };
+    apply_group(app_state, |start| GroupStart {
+        position: math::scale_about(start.position, pivot, factor),
+        scale: start.scale.component_mul(&factor),
+        ..start.clone()
+    });
 }
 
-fn apply_position(app_state: &mut AppState, new_pos: Vector3<f32>) {
-    let selection = app_state.get_state_data_value::<EditorRoot>("editor")
-        .map(|r| r.editor.selection.clone());
-    let Some(selection) = selection else { return; };
-    match selection {
-        Selection::SceneObject(uuid) => {
-            if let Some(o) = app_state.objects.iter_mut().find(|o| o.get_unique_id() == uuid) {
-                o.transform.position = new_pos;
+/// Pivot of the group as it was when the drag began. Children carried along
+/// with their parents don't count.
+fn group_pivot(app_state: &AppState) -> Option<Vector3<f32>> {
+    let root = app_state.get_state_data_value::<EditorRoot>("editor")?;
+    let positions: Vec<Vector3<f32>> = root.editor.gizmo.drag_group.iter()
+        .filter(|g| root.editor.selection.contains(&g.item))
+        .map(|g| g.position)
+        .collect();
+    math::centroid(&positions)
+}
+
+fn apply_group(app_state: &mut AppState, transform: impl Fn(&GroupStart) -> GroupStart) {
+    let group = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| r.editor.gizmo.drag_group.clone())
+        .unwrap_or_default();
+    for start in &group {
+        let next = transform(start);
+        match next.item {
+            Selection::SceneObject(uuid) => {
+                if let Some(o) = visibility::find_mut(app_state, uuid) {
+                    let (rx, ry, rz) = next.rotation.euler_angles();
+                    o.transform.position = next.position;
+                    o.transform.rotation = Vector3::new(rx, ry, rz);
+                    o.transform.scale = next.scale;
+                }
             }
-        }
-        Selection::Light(idx) => {
-            if let Some(l) = app_state.light.get_mut(idx) {
-                l.position = [new_pos.x, new_pos.y, new_pos.z];
+            Selection::Camera => {
+                if let Some(c) = cameras::game_camera_mut(app_state) {
+                    c.transform.position = next.position;
+                    c.update_matrices();
+                }
             }
-        }
-        Selection::ParticleInstance(uuid) => {
-            if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-                if let Some(project) = root.project.as_mut() {
-                    if let Some(scene) = project.scenes.get_mut(project.active_scene_index) {
-                        if let Some(inst) = scene.particle_instances.iter_mut().find(|i| i.uuid == uuid) {
-                            inst.position = [new_pos.x, new_pos.y, new_pos.z];
+            Selection::Light(uuid) => {
+                if let Some(l) = lights::get_mut(app_state, uuid) {
+                    l.position = next.position.into();
+                }
+            }
+            Selection::ParticleInstance(uuid) => {
+                if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+                    if let Some(project) = root.project.as_mut() {
+                        if let Some(scene) = project.scenes.get_mut(project.active_scene_index) {
+                            if let Some(inst) = scene.particle_instances.iter_mut().find(|i| i.uuid == uuid) {
+                                inst.position = next.position.into();
+                            }
                         }
                     }
                 }
             }
-        }
-        _ => {}
-    }
-    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-        root.editor.dirty = true;
-    }
-}
-
-fn apply_rotation(app_state: &mut AppState, new_rot: Vector3<f32>) {
-    let selection = app_state.get_state_data_value::<EditorRoot>("editor")
-        .map(|r| r.editor.selection.clone());
-    let Some(selection) = selection else { return; };
-    if let Selection::SceneObject(uuid) = selection {
-        if let Some(o) = app_state.objects.iter_mut().find(|o| o.get_unique_id() == uuid) {
-            o.transform.rotation = new_rot;
+            _ => {}
         }
     }
     if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
@@ -331,9 +542,10 @@ fn apply_rotation(app_state: &mut AppState, new_rot: Vector3<f32>) {
     }
 }
 
+/// Scale of a single selected object; groups scale from 1.
 fn selection_scale(app_state: &AppState) -> Option<Vector3<f32>> {
     let root = app_state.get_state_data_value::<EditorRoot>("editor")?;
-    if let Selection::SceneObject(uuid) = &root.editor.selection {
+    if let [Selection::SceneObject(uuid)] = root.editor.selection.items() {
         return app_state.objects.iter()
             .find(|o| o.get_unique_id() == *uuid)
             .map(|o| o.transform.scale);
@@ -341,23 +553,12 @@ fn selection_scale(app_state: &AppState) -> Option<Vector3<f32>> {
     None
 }
 
-fn apply_scale(app_state: &mut AppState, new_scale: Vector3<f32>) {
-    let selection = app_state.get_state_data_value::<EditorRoot>("editor")
-        .map(|r| r.editor.selection.clone());
-    let Some(selection) = selection else { return; };
-    if let Selection::SceneObject(uuid) = selection {
-        if let Some(o) = app_state.objects.iter_mut().find(|o| o.get_unique_id() == uuid) {
-            o.transform.scale = new_scale;
-        }
-    }
-    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-        root.editor.dirty = true;
-    }
-}
-
 fn end_drag(app_state: &mut AppState) {
     if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
         root.editor.gizmo.drag = None;
+        root.editor.gizmo.drag_group.clear();
+        root.editor.gizmo.snap_source = None;
+        root.editor.gizmo.entry.clear();
         root.editor.gizmo.consumed_click_this_frame = true;
     }
 }
@@ -1,12 +1,16 @@
-use egui::{Pos2, Rect, Stroke, Ui};
+use egui::{Color32, Pos2, Rect, Stroke, Ui};
 use nalgebra::{Unit, UnitQuaternion, Vector3};
 
-use crate::editor::gizmo::math;
+use crate::editor::gizmo::{math, DragFrame};
 use crate::editor::gizmo::translate::{axis_color, axis_dir};
-use crate::editor::state::{Axis, Drag, Space};
+use crate::editor::state::{Axis, Drag, Handle, Space};
 
 const RING_SAMPLES: usize = 64;
 const HIT_TOLERANCE: f32 = 8.0;
+/// The view ring sits this much outside the axis rings.
+const VIEW_RING_SCALE: f32 = 1.25;
+/// Trackball turn per pixel of drag, in radians.
+const TRACKBALL_SPEED: f32 = 0.01;
 
 /// Build the two basis vectors that span the ring plane for `axis_dir`.
 fn ring_basis(axis_dir: Vector3<f32>) -> (Vector3<f32>, Vector3<f32>) {
@@ -32,6 +36,42 @@ fn sample_ring(pivot: Vector3<f32>, axis_dir: Vector3<f32>, radius: f32) -> Vec<
         .collect()
 }
 
+/// Normal of the ring for `handle`: an axis, or the view direction turned
+/// toward the camera for the view ring.
+fn ring_normal(
+    handle: Handle,
+    space: Space,
+    rotation: Vector3<f32>,
+    camera: &enigma_3d::camera::Camera,
+) -> Option<Vector3<f32>> {
+    match handle {
+        Handle::Axis(axis) => Some(axis_dir(axis, space, rotation)),
+        Handle::View => Some(-Vector3::from(camera.calculate_direction_vector())),
+        _ => None,
+    }
+}
+
+fn ring_on_screen(
+    pivot: Vector3<f32>,
+    normal: Vector3<f32>,
+    radius: f32,
+    camera: &enigma_3d::camera::Camera,
+    screen: Rect,
+) -> Vec<Pos2> {
+    sample_ring(pivot, normal, radius).into_iter()
+        .filter_map(|p| math::world_to_screen(camera, screen, p))
+        .collect()
+}
+
+fn distance_to_ring(cursor: Pos2, pts: &[Pos2]) -> f32 {
+    if pts.len() < 2 { return f32::INFINITY; }
+    (0..pts.len())
+        .map(|i| math::distance_point_to_segment_2d(cursor, pts[i], pts[(i + 1) % pts.len()]))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Axis rings first, then the view ring just outside them, then the
+/// trackball inside.
 pub fn hit_test(
     cursor: Pos2,
     pivot: Vector3<f32>,
@@ -40,25 +80,11 @@ pub fn hit_test(
     rotation: Vector3<f32>,
     camera: &enigma_3d::camera::Camera,
     screen: Rect,
-) -> Option<Axis> {
+) -> Option<Handle> {
     let mut best: Option<(Axis, f32)> = None;
     for axis in [Axis::X, Axis::Y, Axis::Z] {
         let dir = axis_dir(axis, space, rotation);
-        let pts = sample_ring(pivot, dir, radius);
-        let mut screen_pts: Vec<Pos2> = Vec::with_capacity(RING_SAMPLES);
-        for p in &pts {
-            if let Some(s) = math::world_to_screen(camera, screen, *p) {
-                screen_pts.push(s);
-            }
-        }
-        if screen_pts.len() < 2 { continue; }
-        let mut min_d = f32::INFINITY;
-        for i in 0..screen_pts.len() {
-            let a = screen_pts[i];
-            let b = screen_pts[(i + 1) % screen_pts.len()];
-            let d = math::distance_point_to_segment_2d(cursor, a, b);
-            if d < min_d { min_d = d; }
-        }
+        let min_d = distance_to_ring(cursor, &ring_on_screen(pivot, dir, radius, camera, screen));
         if min_d <= HIT_TOLERANCE {
             best = match best {
                 Some((_, prev)) if prev <= min_d => best,
@@ -66,7 +92,15 @@ pub fn hit_test(
             };
         }
     }
-    best.map(|(a, _)| a)
+    if let Some((axis, _)) = best {
+        return Some(Handle::Axis(axis));
+    }
+    let view = ring_normal(Handle::View, space, rotation, camera)?;
+    if distance_to_ring(cursor, &ring_on_screen(pivot, view, radius * VIEW_RING_SCALE, camera, screen)) <= HIT_TOLERANCE {
+        return Some(Handle::View);
+    }
+    let inside = ring_on_screen(pivot, view, radius, camera, screen);
+    (inside.len() > 2 && math::point_in_polygon(cursor, &inside)).then_some(Handle::Trackball)
 }
 
 pub fn draw(
@@ -77,82 +111,87 @@ pub fn draw(
     space: Space,
     rotation: Vector3<f32>,
     camera: &enigma_3d::camera::Camera,
-    hovered: Option<Axis>,
-    dragging: Option<Axis>,
+    hovered: Option<Handle>,
+    dragging: Option<Handle>,
 ) {
     let painter = ui.painter_at(rect);
     let screen = ui.ctx().screen_rect();
-    for axis in [Axis::X, Axis::Y, Axis::Z] {
-        let dir = axis_dir(axis, space, rotation);
-        let pts = sample_ring(pivot, dir, radius);
-        let mut screen_pts: Vec<Pos2> = Vec::with_capacity(RING_SAMPLES);
-        for p in &pts {
-            if let Some(s) = math::world_to_screen(camera, screen, *p) {
-                screen_pts.push(s);
-            }
+    let ring = |pts: Vec<Pos2>, stroke: Stroke| {
+        if pts.len() < 2 { return; }
+        for i in 0..pts.len() {
+            painter.line_segment([pts[i], pts[(i + 1) % pts.len()]], stroke);
         }
-        if screen_pts.len() < 2 { continue; }
-        let color = axis_color(axis, hovered == Some(axis), dragging == Some(axis));
-        for i in 0..screen_pts.len() {
-            let a = screen_pts[i];
-            let b = screen_pts[(i + 1) % screen_pts.len()];
-            painter.line_segment([a, b], Stroke::new(2.0, color));
+    };
+    if let Some(view) = ring_normal(Handle::View, space, rotation, camera) {
+        let highlight = |h: Handle, base: Color32| {
+            if dragging == Some(h) { Color32::WHITE }
+            else if hovered == Some(h) { Color32::from_rgb(255, 200, 60) }
+            else { base }
+        };
+        let ball = ring_on_screen(pivot, view, radius, camera, screen);
+        if ball.len() > 2 && matches!(hovered.or(dragging), Some(Handle::Trackball)) {
+            painter.add(egui::Shape::convex_polygon(ball.clone(), highlight(Handle::Trackball, Color32::TRANSPARENT).gamma_multiply(0.12), Stroke::NONE));
         }
+        ring(ball, Stroke::new(1.0, Color32::from_gray(120)));
+        ring(ring_on_screen(pivot, view, radius * VIEW_RING_SCALE, camera, screen),
+            Stroke::new(2.0, highlight(Handle::View, Color32::from_gray(220))));
+    }
+    for axis in [Axis::X, Axis::Y, Axis::Z] {
+        let dir = axis_dir(axis, space, rotation);
+        let color = axis_color(axis, hovered == Some(Handle::Axis(axis)), dragging == Some(Handle::Axis(axis)));
+        ring(ring_on_screen(pivot, dir, radius, camera, screen), Stroke::new(2.0, color));
     }
 }
 
-pub fn begin_drag(
-    axis: Axis,
-    cursor: Pos2,
-    pivot: Vector3<f32>,
-    space: Space,
-    rotation: Vector3<f32>,
-    camera: &enigma_3d::camera::Camera,
-    screen: Rect,
-) -> Option<Drag> {
-    let dir = axis_dir(axis, space, rotation);
-    let (ray_o, ray_d) = math::unproject(camera, cursor, screen);
-    let p0 = math::ray_plane_intersect(ray_o, ray_d, pivot, dir)?;
-    let start_dir = (p0 - pivot).normalize();
+pub fn begin_drag(handle: Handle, cursor: Pos2, rotation: Vector3<f32>) -> Drag {
     let start_quat = UnitQuaternion::from_euler_angles(rotation.x, rotation.y, rotation.z);
-    Some(Drag::Rotate { axis, start_quat, start_dir })
+    Drag::Rotate { handle, start_quat, start_cursor: cursor }
 }
 
 /// Returns the new Euler rotation to write into `transform.rotation`.
-/// `pivot` is the object's current position — used to define the rotation plane.
+/// The frame's `pivot` defines the rotation plane. `entry` is a typed angle
+/// in degrees about the ring's axis.
 pub fn update_drag(
-    axis: Axis,
+    handle: Handle,
     start_quat: UnitQuaternion<f32>,
-    start_dir: Vector3<f32>,
-    pivot: Vector3<f32>,
-    cursor: Pos2,
-    space: Space,
-    snap: bool,
-    camera: &enigma_3d::camera::Camera,
-    screen: Rect,
+    start_cursor: Pos2,
+    frame: &DragFrame,
+    snap: Option<f32>,
+    entry: Option<f32>,
 ) -> Vector3<f32> {
+    let DragFrame { cursor, pivot, space, camera, screen, .. } = *frame;
     // Anchor the ring direction in the START rotation so it stays put across the drag.
     let start_rotation = {
         let (rx, ry, rz) = start_quat.euler_angles();
         Vector3::new(rx, ry, rz)
     };
-    let dir = axis_dir(axis, space, start_rotation);
-
-    let (ray_o, ray_d) = math::unproject(camera, cursor, screen);
-    let Some(p) = math::ray_plane_intersect(ray_o, ray_d, pivot, dir) else {
-        return start_rotation;
+    let (axis, mut angle) = match ring_normal(handle, space, start_rotation, camera) {
+        Some(dir) => {
+            let toward = |at: Pos2| {
+                let (ray_o, ray_d) = math::unproject(camera, at, screen);
+                math::ray_plane_intersect(ray_o, ray_d, pivot, dir).map(|p| (p - pivot).normalize())
+            };
+            let (Some(start_dir), Some(current_dir)) = (toward(start_cursor), toward(cursor)) else {
+                return start_rotation;
+            };
+            let cos_a = start_dir.dot(&current_dir).clamp(-1.0, 1.0);
+            let sin_a = dir.dot(&start_dir.cross(&current_dir));
+            (dir, entry.map_or(sin_a.atan2(cos_a), f32::to_radians))
+        }
+        None => {
+            let forward = Vector3::from(camera.calculate_direction_vector());
+            let Some(turn) = math::trackball(cursor - start_cursor, forward, TRACKBALL_SPEED) else {
+                return start_rotation;
+            };
+            turn
+        }
     };
-    let current_dir = (p - pivot).normalize();
-
-    let cos_a = start_dir.dot(&current_dir).clamp(-1.0, 1.0);
-    let sin_a = dir.dot(&start_dir.cross(&current_dir));
-    let mut delta_angle = sin_a.atan2(cos_a);
-    if snap {
-        delta_angle = math::snap(delta_angle, std::f32::consts::PI / 12.0);
+    if let (Some(step), None) = (snap, entry) {
+        angle = math::snap(angle, step);
     }
 
-    let axis_unit = Unit::new_normalize(dir);
-    let new_quat = UnitQuaternion::from_axis_angle(&axis_unit, delta_angle) * start_quat;
+    let axis_unit = Unit::new_normalize(axis);
+    let new_quat = UnitQuaternion::from_axis_angle(&axis_unit, angle) * start_quat;
     let (rx, ry, rz) = new_quat.euler_angles();
     Vector3::new(rx, ry, rz)
 }
@@ -110,24 +110,29 @@ pub fn begin_drag(
     })
 }
 
-/// Returns the new scale vector to write into `transform.scale`.
+/// Returns the new scale vector to write into `transform.scale`. `entry` is
+/// a typed factor.
 pub fn update_drag(
     handle: Handle,
     start_scale: Vector3<f32>,
     start_pivot_screen: Pos2,
     start_distance: f32,
     cursor: Pos2,
-    snap: bool,
+    snap: Option<f32>,
+    entry: Option<f32>,
 ) -> Vector3<f32> {
     let current_distance = (cursor - start_pivot_screen).length();
     let mut factor = current_distance / start_distance.max(1e-3);
-    if snap {
-        factor = (math::snap(factor, 0.1)).max(0.1);
+    if let Some(step) = snap {
+        factor = math::snap(factor, step).max(step);
+    }
+    if let Some(typed) = entry {
+        factor = typed;
     }
     match handle {
         Handle::Axis(Axis::X) => Vector3::new(start_scale.x * factor, start_scale.y, start_scale.z),
         Handle::Axis(Axis::Y) => Vector3::new(start_scale.x, start_scale.y * factor, start_scale.z),
         Handle::Axis(Axis::Z) => Vector3::new(start_scale.x, start_scale.y, start_scale.z * factor),
-        Handle::Center => start_scale * factor,
+        _ => start_scale * factor,
     }
 }
@@ -0,0 +1,171 @@
+//! Snapping a translate drag onto other geometry: a vertex of the selection
+//! onto a vertex of another object, or the selection onto whatever surface
+//! the cursor ray hits.
+
+use egui::{Pos2, Rect};
+use enigma_3d::AppState;
+use enigma_3d::camera::Camera;
+use enigma_3d::object::Object;
+use nalgebra::{UnitQuaternion, Vector3};
+use uuid::Uuid;
+
+use crate::editor::gizmo::math;
+use crate::editor::{navigation, picking};
+
+/// How close, in pixels, the cursor has to come to a vertex to snap to it.
+pub const VERTEX_RADIUS: f32 = 16.0;
+
+/// A point on a surface and the normal facing back along the ray.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SurfaceHit {
+    pub point: Vector3<f32>,
+    pub normal: Vector3<f32>,
+}
+
+/// The triangle of an object's shape a ray met first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeshHit {
+    pub object: Uuid,
+    pub shape: usize,
+    pub distance: f32,
+    /// Not normalized, in winding order.
+    pub normal: Vector3<f32>,
+}
+
+/// An object's mesh vertices in world space.
+pub fn world_vertices(object: &Object) -> Vec<Vector3<f32>> {
+    let t = &object.transform;
+    let rotation = UnitQuaternion::from_euler_angles(t.rotation.x, t.rotation.y, t.rotation.z);
+    object.get_shapes().iter()
+        .flat_map(|s| s.vertices.iter())
+        .map(|v| t.position + rotation * Vector3::from(v.position).component_mul(&t.scale))
+        .collect()
+}
+
+/// An object's triangles in world space, each with the index of its shape.
+/// Shapes without indices are read as a plain triangle list.
+pub fn world_triangles(object: &Object) -> Vec<(usize, [Vector3<f32>; 3])> {
+    let t = &object.transform;
+    let rotation = UnitQuaternion::from_euler_angles(t.rotation.x, t.rotation.y, t.rotation.z);
+    let world = |p: [f32; 3]| t.position + rotation * Vector3::from(p).component_mul(&t.scale);
+    let mut triangles = Vec::new();
+    for (index, shape) in object.get_shapes().iter().enumerate() {
+        let vertex = |i: u32| shape.vertices.get(i as usize).map(|v| world(v.position));
+        if shape.indices.is_empty() {
+            triangles.extend(shape.vertices.chunks_exact(3)
+                .map(|c| (index, [world(c[0].position), world(c[1].position), world(c[2].position)])));
+        } else {
+            triangles.extend(shape.indices.chunks_exact(3)
+                .filter_map(|c| Some((index, [vertex(c[0])?, vertex(c[1])?, vertex(c[2])?]))));
+        }
+    }
+    triangles
+}
+
+/// The point whose screen position is nearest `cursor`, if within `radius`.
+pub fn nearest_on_screen(
+    points: impl IntoIterator<Item = (Pos2, Vector3<f32>)>,
+    cursor: Pos2,
+    radius: f32,
+) -> Option<Vector3<f32>> {
+    points.into_iter()
+        .map(|(at, p)| ((at - cursor).length(), p))
+        .filter(|(d, _)| *d <= radius)
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, p)| p)
+}
+
+fn on_screen<'a>(
+    camera: &'a Camera,
+    screen: Rect,
+    points: impl IntoIterator<Item = Vector3<f32>> + 'a,
+) -> impl Iterator<Item = (Pos2, Vector3<f32>)> + 'a {
+    points.into_iter().filter_map(move |p| Some((math::world_to_screen(camera, screen, p)?, p)))
+}
+
+/// Vertex of the dragged objects nearest the cursor, wherever it is on
+/// screen. This is the point vertex snapping carries onto its target.
+pub fn vertex_source(app_state: &AppState, dragged: &[Uuid], camera: &Camera, screen: Rect, cursor: Pos2) -> Option<Vector3<f32>> {
+    let points = app_state.objects.iter()
+        .filter(|o| dragged.contains(&o.get_unique_id()))
+        .flat_map(world_vertices);
+    nearest_on_screen(on_screen(camera, screen, points), cursor, f32::INFINITY)
+}
+
+/// Vertex of any other object within `VERTEX_RADIUS` of the cursor.
+pub fn vertex_target(app_state: &AppState, dragged: &[Uuid], camera: &Camera, screen: Rect, cursor: Pos2) -> Option<Vector3<f32>> {
+    let points = app_state.objects.iter()
+        .filter(|o| !dragged.contains(&o.get_unique_id()))
+        .flat_map(world_vertices);
+    nearest_on_screen(on_screen(camera, screen, points), cursor, VERTEX_RADIUS)
+}
+
+/// Nearest mesh triangle along the ray, skipping the objects in `exclude`.
+pub fn mesh_hit(app_state: &AppState, exclude: &[Uuid], origin: Vector3<f32>, dir: Vector3<f32>) -> Option<MeshHit> {
+    let mut best: Option<MeshHit> = None;
+    for object in app_state.objects.iter().filter(|o| !exclude.contains(&o.get_unique_id())) {
+        let Some(bounds) = navigation::object_bounds(object) else { continue; };
+        // Skip meshes whose box the ray misses or only reaches past the best hit.
+        let Some((enter, _)) = picking::ray_aabb(origin, dir, bounds.min, bounds.max) else { continue; };
+        if best.as_ref().is_some_and(|b| b.distance < enter) { continue; }
+        for (shape, tri) in world_triangles(object) {
+            let Some(t) = math::ray_triangle(origin, dir, tri) else { continue; };
+            if best.as_ref().is_none_or(|b| t < b.distance) {
+                let normal = (tri[1] - tri[0]).cross(&(tri[2] - tri[0]));
+                best = Some(MeshHit { object: object.get_unique_id(), shape, distance: t, normal });
+            }
+        }
+    }
+    best
+}
+
+/// Nearest surface along the ray among the objects not being dragged and
+/// the terrain.
+pub fn surface_hit(app_state: &AppState, dragged: &[Uuid], origin: Vector3<f32>, dir: Vector3<f32>) -> Option<SurfaceHit> {
+    let mut best = mesh_hit(app_state, dragged, origin, dir).map(|h| (h.distance, h.normal));
+    if let Some(t) = picking::active_terrain(app_state).and_then(|def| picking::terrain_hit(&def, origin, dir)) {
+        // `terrain_hit` approximates the terrain by a level plane.
+        if best.is_none_or(|(b, _)| t < b) {
+            best = Some((t, Vector3::y()));
+        }
+    }
+    let (t, normal) = best?;
+    let normal = normal.try_normalize(1e-12)?;
+    let normal = if normal.dot(&dir) > 0.0 { -normal } else { normal };
+    Some(SurfaceHit { point: origin + dir * t, normal })
+}
+
+/// Smallest rotation that turns `up` onto `normal`.
+pub fn align(up: Vector3<f32>, normal: Vector3<f32>) -> UnitQuaternion<f32> {
+    UnitQuaternion::rotation_between(&up, &normal).unwrap_or_else(|| {
+        // Opposite vectors: half a turn about any axis across `up`.
+        let (across, _) = math::screen_basis(up);
+        UnitQuaternion::from_axis_angle(&nalgebra::Unit::new_normalize(across), std::f32::consts::PI)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_on_screen_respects_radius() {
+        let a = Vector3::new(1.0, 0.0, 0.0);
+        let b = Vector3::new(2.0, 0.0, 0.0);
+        let points = [(Pos2::new(10.0, 10.0), a), (Pos2::new(30.0, 10.0), b)];
+        assert_eq!(nearest_on_screen(points, Pos2::new(26.0, 10.0), 16.0), Some(b));
+        assert_eq!(nearest_on_screen(points, Pos2::new(15.0, 60.0), 16.0), None);
+        assert_eq!(nearest_on_screen(points, Pos2::new(15.0, 60.0), f32::INFINITY), Some(a));
+    }
+
+    #[test]
+    fn align_turns_up_onto_normal() {
+        let tilted = Vector3::new(1.0, 1.0, 0.0).normalize();
+        for up in [Vector3::y(), tilted] {
+            for normal in [Vector3::x(), -up, Vector3::new(0.0, 1.0, 1.0).normalize(), up] {
+                let turned = align(up, normal) * up;
+                assert!((turned - normal).norm() < 1e-5, "{up:?} to {normal:?} gave {turned:?}");
+            }
+        }
+    }
+}
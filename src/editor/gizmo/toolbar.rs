@@ -1,7 +1,7 @@
 use egui::{Area, Context, Order, Rect};
 use enigma_3d::AppState;
 
-use crate::editor::state::{EditorRoot, GizmoMode, Space};
+use crate::editor::state::{EditorRoot, GizmoMode, SnapSettings, Space};
 
 pub fn draw(ctx: &Context, rect: Rect, app_state: &mut AppState) {
     let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
@@ -37,6 +37,7 @@ pub fn draw(ctx: &Context, rect: Rect, app_state: &mut AppState) {
                     if ui.button(snap_label).clicked() {
                         g.snap_enabled = !g.snap_enabled;
                     }
+                    snap_controls(ui, g.mode, &mut g.snap);
 
                     let grid_label = if g.grid_enabled { "Grid: On" } else { "Grid: Off" };
                     if ui.button(grid_label).clicked() {
@@ -46,3 +47,27 @@ pub fn draw(ctx: &Context, rect: Rect, app_state: &mut AppState) {
             });
         });
 }
+
+/// The step for the current mode and, while moving, the vertex and surface
+/// toggles.
+fn snap_controls(ui: &mut egui::Ui, mode: GizmoMode, snap: &mut SnapSettings) {
+    let step = match mode {
+        GizmoMode::Translate => Some(egui::DragValue::new(&mut snap.translate).speed(0.05).clamp_range(0.001..=1000.0).suffix(" u")),
+        GizmoMode::Rotate => Some(egui::DragValue::new(&mut snap.rotate_degrees).speed(0.5).clamp_range(0.1..=180.0).suffix("°")),
+        GizmoMode::Scale => Some(egui::DragValue::new(&mut snap.scale).speed(0.01).clamp_range(0.001..=10.0).prefix("×")),
+        GizmoMode::None => None,
+    };
+    if let Some(step) = step {
+        ui.add(step).on_hover_text("Snap increment");
+    }
+    if mode == GizmoMode::Translate {
+        ui.toggle_value(&mut snap.vertex, "Vertex")
+            .on_hover_text("Snap to the nearest vertex under the cursor (or hold V)");
+        ui.toggle_value(&mut snap.surface, "Surface")
+            .on_hover_text("Drop onto the surface under the cursor");
+        if snap.surface {
+            ui.toggle_value(&mut snap.align_to_normal, "Align")
+                .on_hover_text("Turn the object's up axis to the surface normal");
+        }
+    }
+}
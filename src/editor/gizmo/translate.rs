@@ -1,13 +1,18 @@
 use egui::{Color32, Pos2, Rect, Stroke, Ui};
 use nalgebra::{UnitQuaternion, Vector3};
 
-use crate::editor::gizmo::math;
-use crate::editor::state::{Axis, Drag, Space};
+use crate::editor::gizmo::{math, DragFrame};
+use crate::editor::state::{Axis, Drag, Handle, Space};
 
 /// Fraction of viewport vertical extent that the gizmo handles span.
 const SCREEN_FRACTION: f32 = 0.15;
 /// 2D pixel tolerance for cursor-on-axis hit-test.
 const HIT_TOLERANCE: f32 = 8.0;
+/// Plane handle squares, as fractions of the handle length.
+const PLANE_OFFSET: f32 = 0.2;
+const PLANE_SIZE: f32 = 0.25;
+/// Pixel radius of the centre circle that moves in the view plane.
+const VIEW_RADIUS: f32 = 7.0;
 
 pub fn axis_color(axis: Axis, hovered: bool, dragging: bool) -> Color32 {
     if dragging { return Color32::WHITE; }
@@ -45,8 +50,35 @@ pub fn handle_world_size(camera_pos: Vector3<f32>, pivot: Vector3<f32>, fov: f32
     distance * (fov / 2.0).tan() * SCREEN_FRACTION
 }
 
-/// Hit-test cursor against the three axis segments. Returns the axis whose
-/// projected segment is closest to the cursor within HIT_TOLERANCE.
+/// The two axes spanning the plane handle across `normal`.
+pub fn plane_axes(normal: Axis) -> (Axis, Axis) {
+    match normal {
+        Axis::X => (Axis::Y, Axis::Z),
+        Axis::Y => (Axis::X, Axis::Z),
+        Axis::Z => (Axis::X, Axis::Y),
+    }
+}
+
+/// Screen corners of the plane handle across `normal`: a small square
+/// between its two axes, set in from the pivot.
+fn plane_square(
+    normal: Axis,
+    pivot: Vector3<f32>,
+    size: f32,
+    space: Space,
+    rotation: Vector3<f32>,
+    camera: &enigma_3d::camera::Camera,
+    screen: Rect,
+) -> Option<[Pos2; 4]> {
+    let (a, b) = plane_axes(normal);
+    let (a, b) = (axis_dir(a, space, rotation) * size, axis_dir(b, space, rotation) * size);
+    let (near, far) = (PLANE_OFFSET, PLANE_OFFSET + PLANE_SIZE);
+    let corner = |s: f32, t: f32| math::world_to_screen(camera, screen, pivot + a * s + b * t);
+    Some([corner(near, near)?, corner(far, near)?, corner(far, far)?, corner(near, far)?])
+}
+
+/// Hit-test cursor against the handles: the centre (view-plane move) first,
+/// then the plane squares, then the axis segments within HIT_TOLERANCE.
 pub fn hit_test(
     cursor: Pos2,
     pivot: Vector3<f32>,
@@ -55,7 +87,18 @@ pub fn hit_test(
     rotation: Vector3<f32>,
     camera: &enigma_3d::camera::Camera,
     screen: Rect,
-) -> Option<Axis> {
+) -> Option<Handle> {
+    let center = math::world_to_screen(camera, screen, pivot)?;
+    if (cursor - center).length() <= VIEW_RADIUS + 2.0 {
+        return Some(Handle::View);
+    }
+    for normal in [Axis::X, Axis::Y, Axis::Z] {
+        if let Some(square) = plane_square(normal, pivot, size, space, rotation, camera, screen) {
+            if math::point_in_polygon(cursor, &square) {
+                return Some(Handle::Plane(normal));
+            }
+        }
+    }
     let mut best: Option<(Axis, f32)> = None;
     for axis in [Axis::X, Axis::Y, Axis::Z] {
         let dir = axis_dir(axis, space, rotation);
@@ -71,10 +114,10 @@ pub fn hit_test(
             };
         }
     }
-    best.map(|(a, _)| a)
+    best.map(|(a, _)| Handle::Axis(a))
 }
 
-/// Render the three axis lines.
+/// Render the three axis lines, the plane squares and the centre circle.
 pub fn draw(
     ui: &mut Ui,
     rect: Rect,
@@ -83,59 +126,89 @@ pub fn draw(
     space: Space,
     rotation: Vector3<f32>,
     camera: &enigma_3d::camera::Camera,
-    hovered: Option<Axis>,
-    dragging: Option<Axis>,
+    hovered: Option<Handle>,
+    dragging: Option<Handle>,
 ) {
     let painter = ui.painter_at(rect);
     let screen = ui.ctx().screen_rect();
+    for normal in [Axis::X, Axis::Y, Axis::Z] {
+        let Some(square) = plane_square(normal, pivot, size, space, rotation, camera, screen) else { continue };
+        let color = axis_color(normal, hovered == Some(Handle::Plane(normal)), dragging == Some(Handle::Plane(normal)));
+        painter.add(egui::Shape::convex_polygon(square.to_vec(), color.gamma_multiply(0.35), Stroke::new(1.0, color)));
+    }
     for axis in [Axis::X, Axis::Y, Axis::Z] {
         let dir = axis_dir(axis, space, rotation);
         let Some(a) = math::world_to_screen(camera, screen, pivot) else { continue };
         let Some(b) = math::world_to_screen(camera, screen, pivot + dir * size) else { continue };
-        let color = axis_color(axis, hovered == Some(axis), dragging == Some(axis));
+        let color = axis_color(axis, hovered == Some(Handle::Axis(axis)), dragging == Some(Handle::Axis(axis)));
         painter.line_segment([a, b], Stroke::new(3.0, color));
         painter.circle_filled(b, 5.0, color);
     }
+    if let Some(c) = math::world_to_screen(camera, screen, pivot) {
+        let color = if dragging == Some(Handle::View) { Color32::WHITE }
+            else if hovered == Some(Handle::View) { Color32::from_rgb(255, 200, 60) }
+            else { Color32::from_gray(220) };
+        painter.circle_stroke(c, VIEW_RADIUS, Stroke::new(2.0, color));
+    }
+}
+
+/// Start a translate drag on `handle`.
+pub fn begin_drag(handle: Handle, cursor: Pos2, pivot: Vector3<f32>) -> Drag {
+    Drag::Translate { handle, start_pos: pivot, start_cursor: cursor }
 }
 
-/// Start a translate drag on the given axis.
-pub fn begin_drag(
-    axis: Axis,
+/// Where the cursor ray meets the constraint of `handle` through `origin`.
+fn constrained_point(
+    handle: Handle,
+    origin: Vector3<f32>,
     cursor: Pos2,
-    pivot: Vector3<f32>,
     space: Space,
     rotation: Vector3<f32>,
     camera: &enigma_3d::camera::Camera,
     screen: Rect,
-) -> Drag {
-    let dir = axis_dir(axis, space, rotation);
+) -> Option<Vector3<f32>> {
     let (ray_o, ray_d) = math::unproject(camera, cursor, screen);
-    let start_on_axis = math::closest_point_on_line_to_ray(pivot, dir, ray_o, ray_d);
-    Drag::Translate {
-        axis,
-        start_pos: pivot,
-        start_on_axis,
+    match handle {
+        Handle::Axis(axis) => {
+            let dir = axis_dir(axis, space, rotation);
+            Some(math::closest_point_on_line_to_ray(origin, dir, ray_o, ray_d))
+        }
+        Handle::Plane(normal) => math::ray_plane_intersect(ray_o, ray_d, origin, axis_dir(normal, space, rotation)),
+        Handle::View => math::ray_plane_intersect(ray_o, ray_d, origin, Vector3::from(camera.calculate_direction_vector())),
+        _ => None,
     }
 }
 
-/// Update an in-progress translate drag. Returns the new position.
+/// Update an in-progress translate drag. Returns the new position. `entry`
+/// is a typed distance; it only applies along an axis.
 pub fn update_drag(
-    axis: Axis,
+    handle: Handle,
     start_pos: Vector3<f32>,
-    start_on_axis: Vector3<f32>,
-    cursor: Pos2,
-    space: Space,
-    rotation: Vector3<f32>,
-    snap: bool,
-    camera: &enigma_3d::camera::Camera,
-    screen: Rect,
+    start_cursor: Pos2,
+    frame: &DragFrame,
+    snap: Option<f32>,
+    entry: Option<f32>,
 ) -> Vector3<f32> {
-    let dir = axis_dir(axis, space, rotation);
-    let (ray_o, ray_d) = math::unproject(camera, cursor, screen);
-    let current_on_axis = math::closest_point_on_line_to_ray(start_pos, dir, ray_o, ray_d);
-    let mut delta = (current_on_axis - start_on_axis).dot(&dir);
-    if snap {
-        delta = math::snap(delta, 1.0);
+    let DragFrame { cursor, space, rotation, camera, screen, .. } = *frame;
+    if let (Handle::Axis(axis), Some(distance)) = (handle, entry) {
+        return start_pos + axis_dir(axis, space, rotation) * distance;
     }
-    start_pos + dir * delta
+    let point = |at| constrained_point(handle, start_pos, at, space, rotation, camera, screen);
+    let (Some(from), Some(to)) = (point(start_cursor), point(cursor)) else { return start_pos; };
+    let delta = to - from;
+    // Snap along the handle's own axes; the view plane snaps in world axes.
+    let axes: Vec<Vector3<f32>> = match handle {
+        Handle::Axis(axis) => vec![axis_dir(axis, space, rotation)],
+        Handle::Plane(normal) => {
+            let (a, b) = plane_axes(normal);
+            vec![axis_dir(a, space, rotation), axis_dir(b, space, rotation)]
+        }
+        _ => vec![Vector3::x(), Vector3::y(), Vector3::z()],
+    };
+    start_pos + axes.iter()
+        .map(|dir| {
+            let along = delta.dot(dir);
+            dir * snap.map_or(along, |step| math::snap(along, step))
+        })
+        .sum::<Vector3<f32>>()
 }
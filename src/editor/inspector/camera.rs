@@ -1,11 +1,21 @@
 use egui::{DragValue, Ui};
 use enigma_3d::AppState;
 
+use crate::editor::cameras;
+use crate::editor::state::EditorRoot;
+
 pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
-    let Some(cam) = app_state.camera.as_mut() else {
+    let align = ui.button("Align to view")
+        .on_hover_text("Move the game camera to where the viewport is looking (Ctrl+Alt+0)")
+        .clicked();
+    if align {
+        cameras::align_game_camera_to_view(app_state);
+    }
+    let Some(cam) = cameras::game_camera_mut(app_state) else {
         ui.label("(no camera in scene)");
         return;
     };
+    let before = (cam.get_position(), cam.get_rotation(), cam.fov, cam.near, cam.far);
 
     egui::CollapsingHeader::new("Transform").default_open(true).show(ui, |ui| {
         let mut p = cam.get_position();
@@ -34,4 +44,10 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
         ui.add(DragValue::new(&mut cam.near).speed(0.01).prefix("near "));
         ui.add(DragValue::new(&mut cam.far).speed(1.0).prefix("far "));
     });
+    let after = (cam.get_position(), cam.get_rotation(), cam.fov, cam.near, cam.far);
+    if after != before {
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            root.editor.dirty = true;
+        }
+    }
 }
@@ -0,0 +1,755 @@
+//! Inspector fields described instead of hand-drawn.
+//!
+//! A type implements `Inspectable` by listing its fields: label, group,
+//! tooltip, widget (with speed and range) and how to read and write the
+//! value. `Inspector` draws them in a grid and gives every field the same
+//! extras: a ↺ button when it differs from the default, and a right-click
+//! menu on the label with Reset, Copy and Paste. Copied values paste into
+//! any field of the same kind, on any object.
+//!
+//! The `field!` macro covers plain struct fields; fields inside enums or
+//! options use `Field::new` with their own getter and setter.
+//!
+//! `Inspector::show_many` edits several targets at once. A field whose
+//! targets disagree shows "—", and an edit is written to every target; for
+//! vectors only the edited component changes, so setting Y leaves each
+//! target's X and Z alone.
+
+use std::ops::RangeInclusive;
+
+use egui::{DragValue, Ui};
+use enigma_3d::AppState;
+use uuid::Uuid;
+
+use crate::editor::state::{EditorRoot, ResourceKind};
+
+/// A field's value, detached from the type it came from.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Value {
+    Bool(bool),
+    Float(f32),
+    Int(i64),
+    Vec2([f32; 2]),
+    Vec3([f32; 3]),
+    Text(String),
+    /// Index into the field's `Widget::Choice` options.
+    Choice(usize),
+    Asset(Option<Uuid>),
+}
+
+impl Value {
+    pub fn same_kind(&self, other: &Value) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Bool(v) => write!(f, "{v}"),
+            Value::Float(v) => write!(f, "{v}"),
+            Value::Int(v) => write!(f, "{v}"),
+            Value::Vec2([x, y]) => write!(f, "{x}, {y}"),
+            Value::Vec3([x, y, z]) => write!(f, "{x}, {y}, {z}"),
+            Value::Text(v) => write!(f, "{v}"),
+            Value::Choice(i) => write!(f, "{i}"),
+            Value::Asset(Some(uuid)) => write!(f, "{uuid}"),
+            Value::Asset(None) => Ok(()),
+        }
+    }
+}
+
+/// Which components of a merged value differ between targets. Scalars only
+/// use the first flag.
+pub type Mixed = [bool; 3];
+
+/// One value standing for every target's value of a field. Mixed text
+/// shows empty.
+pub fn merge(values: &[Value]) -> Option<(Value, Mixed)> {
+    let (first, rest) = values.split_first()?;
+    let mut merged = first.clone();
+    let mut mixed = [false; 3];
+    for value in rest {
+        match (&merged, value) {
+            (Value::Vec2(a), Value::Vec2(b)) => differing(&mut mixed, a, b),
+            (Value::Vec3(a), Value::Vec3(b)) => differing(&mut mixed, a, b),
+            (a, b) => mixed[0] |= a != b,
+        }
+    }
+    if let (Value::Text(text), true) = (&mut merged, mixed[0]) {
+        text.clear();
+    }
+    Some((merged, mixed))
+}
+
+fn differing(mixed: &mut Mixed, a: &[f32], b: &[f32]) {
+    for ((m, a), b) in mixed.iter_mut().zip(a).zip(b) {
+        *m |= a != b;
+    }
+}
+
+/// A target's `own` value after the merged value was edited from `before`
+/// to `after`: vectors take only the edited components, anything else is
+/// replaced.
+pub fn blend(own: Value, before: &Value, after: &Value) -> Value {
+    match (own, before, after) {
+        (Value::Vec2(mut own), Value::Vec2(b), Value::Vec2(a)) => {
+            take_edited(&mut own, b, a);
+            Value::Vec2(own)
+        }
+        (Value::Vec3(mut own), Value::Vec3(b), Value::Vec3(a)) => {
+            take_edited(&mut own, b, a);
+            Value::Vec3(own)
+        }
+        (_, _, after) => after.clone(),
+    }
+}
+
+fn take_edited(own: &mut [f32], before: &[f32], after: &[f32]) {
+    for ((own, b), a) in own.iter_mut().zip(before).zip(after) {
+        if a != b {
+            *own = *a;
+        }
+    }
+}
+
+/// A number typed into a field that stands for several values: `2` sets
+/// each to 2, `+1` or `-=1` offsets each, `*2` multiplies each.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum NumberEdit {
+    Set(f32),
+    Offset(f32),
+    Scale(f32),
+}
+
+impl NumberEdit {
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().trim_start_matches('—').trim();
+        let number = |s: &str| s.trim().parse::<f32>().ok().filter(|v| v.is_finite());
+        if let Some(rest) = text.strip_prefix("+=").or_else(|| text.strip_prefix('+')) {
+            number(rest).map(NumberEdit::Offset)
+        } else if let Some(rest) = text.strip_prefix("-=") {
+            number(rest).map(|v| NumberEdit::Offset(-v))
+        } else if let Some(rest) = text.strip_prefix("*=").or_else(|| text.strip_prefix('*')) {
+            number(rest).map(NumberEdit::Scale)
+        } else {
+            number(text).map(NumberEdit::Set)
+        }
+    }
+
+    pub fn apply(self, value: f32) -> f32 {
+        match self {
+            NumberEdit::Set(v) => v,
+            NumberEdit::Offset(v) => value + v,
+            NumberEdit::Scale(v) => value * v,
+        }
+    }
+}
+
+/// Rust types a `Value` stands for; used by `field!`.
+pub trait FieldValue: Sized {
+    fn into_value(self) -> Value;
+    fn from_value(value: Value) -> Option<Self>;
+}
+
+macro_rules! field_value {
+    ($ty:ty, $variant:ident) => {
+        impl FieldValue for $ty {
+            fn into_value(self) -> Value {
+                Value::$variant(self)
+            }
+            fn from_value(value: Value) -> Option<Self> {
+                match value {
+                    Value::$variant(v) => Some(v),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+field_value!(bool, Bool);
+field_value!(f32, Float);
+field_value!(i64, Int);
+field_value!([f32; 2], Vec2);
+field_value!([f32; 3], Vec3);
+field_value!(String, Text);
+field_value!(Option<Uuid>, Asset);
+
+impl FieldValue for u32 {
+    fn into_value(self) -> Value {
+        Value::Int(self as i64)
+    }
+    fn from_value(value: Value) -> Option<Self> {
+        match value {
+            Value::Int(v) => Some(v.clamp(0, u32::MAX as i64) as u32),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AssetKind {
+    Material,
+    Texture,
+    Shader,
+}
+
+#[derive(Clone, Debug)]
+pub enum Widget {
+    /// Float, Int, Vec2 or Vec3; `prefixes` label the components.
+    Drag { speed: f64, range: RangeInclusive<f64>, prefixes: &'static [&'static str] },
+    Slider(RangeInclusive<f64>),
+    Checkbox,
+    /// RGB in a Vec3.
+    Color,
+    Text,
+    Choice(&'static [&'static str]),
+    /// A project material, texture or shader; `none` names the empty choice.
+    Asset { kind: AssetKind, none: &'static str },
+}
+
+impl Widget {
+    pub fn drag(speed: f64) -> Self {
+        Widget::Drag { speed, range: f64::NEG_INFINITY..=f64::INFINITY, prefixes: &["x ", "y ", "z "] }
+    }
+
+    pub fn range(self, range: RangeInclusive<f64>) -> Self {
+        match self {
+            Widget::Drag { speed, prefixes, .. } => Widget::Drag { speed, range, prefixes },
+            other => other,
+        }
+    }
+
+    pub fn prefixes(self, prefixes: &'static [&'static str]) -> Self {
+        match self {
+            Widget::Drag { speed, range, .. } => Widget::Drag { speed, range, prefixes },
+            other => other,
+        }
+    }
+
+    /// Whether `value` fits this widget, e.g. for pasting.
+    pub fn accepts(&self, value: &Value) -> bool {
+        match (self, value) {
+            (Widget::Drag { .. }, Value::Float(_) | Value::Int(_) | Value::Vec2(_) | Value::Vec3(_)) => true,
+            (Widget::Slider(_), Value::Float(_)) => true,
+            (Widget::Checkbox, Value::Bool(_)) => true,
+            (Widget::Color, Value::Vec3(_)) => true,
+            (Widget::Text, Value::Text(_)) => true,
+            (Widget::Choice(options), Value::Choice(i)) => *i < options.len(),
+            (Widget::Asset { .. }, Value::Asset(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+type Getter<T> = Box<dyn Fn(&T) -> Value>;
+type Setter<T> = Box<dyn Fn(&mut T, Value)>;
+type Visible<T> = Box<dyn Fn(&T) -> bool>;
+
+pub struct Field<T> {
+    pub label: String,
+    /// Collapsing section the field is listed under; "" for none.
+    pub group: &'static str,
+    pub tooltip: &'static str,
+    pub widget: Widget,
+    /// What Reset puts back; None hides Reset.
+    pub default: Option<Value>,
+    get: Getter<T>,
+    set: Setter<T>,
+    visible: Option<Visible<T>>,
+    /// Belongs to one target alone, like a name; left out when several
+    /// targets are edited together.
+    pub unique: bool,
+}
+
+impl<T> Field<T> {
+    pub fn new(
+        label: impl Into<String>,
+        widget: Widget,
+        get: impl Fn(&T) -> Value + 'static,
+        set: impl Fn(&mut T, Value) + 'static,
+    ) -> Self {
+        Field {
+            label: label.into(),
+            group: "",
+            tooltip: "",
+            widget,
+            default: None,
+            get: Box::new(get),
+            set: Box::new(set),
+            visible: None,
+            unique: false,
+        }
+    }
+
+    pub fn group(mut self, group: &'static str) -> Self {
+        self.group = group;
+        self
+    }
+
+    pub fn tooltip(mut self, tooltip: &'static str) -> Self {
+        self.tooltip = tooltip;
+        self
+    }
+
+    pub fn default(mut self, value: Value) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
+    /// Only shown while `visible` holds, e.g. a shape's radius.
+    pub fn visible_if(mut self, visible: impl Fn(&T) -> bool + 'static) -> Self {
+        self.visible = Some(Box::new(visible));
+        self
+    }
+
+    pub fn get(&self, target: &T) -> Value {
+        (self.get)(target)
+    }
+
+    pub fn set(&self, target: &mut T, value: Value) {
+        (self.set)(target, value)
+    }
+
+    pub fn is_visible(&self, target: &T) -> bool {
+        self.visible.as_ref().is_none_or(|v| v(target))
+    }
+}
+
+/// A `Field` for the struct field at `path`, reading and writing it through
+/// `FieldValue`. Used inside `impl Inspectable`.
+macro_rules! field {
+    ($label:expr, $widget:expr, $($path:ident).+) => {
+        $crate::editor::inspector::inspectable::Field::<Self>::new(
+            $label,
+            $widget,
+            |t| $crate::editor::inspector::inspectable::FieldValue::into_value(t.$($path).+.clone()),
+            |t, v| {
+                if let Some(v) = $crate::editor::inspector::inspectable::FieldValue::from_value(v) {
+                    t.$($path).+ = v;
+                }
+            },
+        )
+    };
+}
+pub(crate) use field;
+
+pub trait Inspectable {
+    /// Groups that start collapsed.
+    const CLOSED_GROUPS: &'static [&'static str] = &[];
+
+    /// The fields in display order. Takes `self` for types whose fields
+    /// vary, like script components.
+    fn fields(&self) -> Vec<Field<Self>> where Self: Sized;
+}
+
+/// Take every field's default from `defaults`.
+pub fn with_defaults<T>(mut fields: Vec<Field<T>>, defaults: &T) -> Vec<Field<T>> {
+    for field in &mut fields {
+        field.default = Some(field.get(defaults));
+    }
+    fields
+}
+
+/// Project assets offered by `Widget::Asset`.
+#[derive(Default)]
+pub struct Assets {
+    pub materials: Vec<(Uuid, String)>,
+    pub textures: Vec<(Uuid, String)>,
+    pub shaders: Vec<(Uuid, String)>,
+}
+
+impl Assets {
+    pub fn of(&self, kind: AssetKind) -> &[(Uuid, String)] {
+        match kind {
+            AssetKind::Material => &self.materials,
+            AssetKind::Texture => &self.textures,
+            AssetKind::Shader => &self.shaders,
+        }
+    }
+}
+
+/// Draws `Inspectable`s. Made per panel per frame; `finish` keeps what was
+/// copied for the next frame.
+pub struct Inspector {
+    pub assets: Assets,
+    clipboard: Option<Value>,
+}
+
+impl Inspector {
+    pub fn new(app_state: &AppState) -> Self {
+        let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else {
+            return Inspector { assets: Assets::default(), clipboard: None };
+        };
+        let assets = root.project.as_ref().map(|p| {
+            let resources = |kind: ResourceKind| p.manifest.iter()
+                .filter(|e| e.kind == kind)
+                .map(|e| (e.uuid, e.name.clone()))
+                .collect();
+            Assets {
+                materials: p.materials.iter()
+                    .filter(|m| !m.name.starts_with("INTERNAL::"))
+                    .map(|m| (m.uuid, m.name.clone()))
+                    .collect(),
+                textures: resources(ResourceKind::Texture),
+                shaders: resources(ResourceKind::Shader),
+            }
+        }).unwrap_or_default();
+        Inspector { assets, clipboard: root.editor.field_clipboard.clone() }
+    }
+
+    pub fn finish(self, app_state: &mut AppState) {
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            root.editor.field_clipboard = self.clipboard;
+        }
+    }
+
+    /// Every field, grouped under collapsing headers. Returns whether
+    /// anything changed.
+    pub fn show<T: Inspectable>(&mut self, ui: &mut Ui, target: &mut T) -> bool {
+        self.show_many(ui, std::slice::from_mut(target))
+    }
+
+    /// `show` for several targets edited together, listing the fields of the
+    /// first. Fields the targets disagree on show "—".
+    pub fn show_many<T: Inspectable>(&mut self, ui: &mut Ui, targets: &mut [T]) -> bool {
+        let Some(first) = targets.first() else { return false; };
+        let fields = first.fields();
+        let mut groups: Vec<&'static str> = Vec::new();
+        for field in &fields {
+            if !groups.contains(&field.group) {
+                groups.push(field.group);
+            }
+        }
+        let mut changed = false;
+        for group in groups {
+            if group.is_empty() {
+                changed |= self.grid(ui, targets, &fields, group);
+            } else {
+                egui::CollapsingHeader::new(group)
+                    .default_open(!T::CLOSED_GROUPS.contains(&group))
+                    .show(ui, |ui| changed |= self.grid(ui, targets, &fields, group));
+            }
+        }
+        changed
+    }
+
+    /// The fields of one group, without a header, for inspectors that mix
+    /// described fields with their own widgets.
+    pub fn show_group<T: Inspectable>(&mut self, ui: &mut Ui, target: &mut T, group: &str) -> bool {
+        let fields = target.fields();
+        self.grid(ui, std::slice::from_mut(target), &fields, group)
+    }
+
+    fn grid<T>(&mut self, ui: &mut Ui, targets: &mut [T], fields: &[Field<T>], group: &str) -> bool {
+        let mut changed = false;
+        egui::Grid::new(("fields", group)).num_columns(2).show(ui, |ui| {
+            for field in fields.iter().filter(|f| f.group == group) {
+                if field.unique && targets.len() > 1 { continue; }
+                if !targets.iter().all(|t| field.is_visible(t)) { continue; }
+                let values: Vec<Value> = targets.iter().map(|t| field.get(t)).collect();
+                let Some((before, mixed)) = merge(&values) else { continue; };
+                ui.push_id((group, &field.label), |ui| {
+                    let mut value = before.clone();
+                    let change = self.row(ui, field, &mut value, mixed);
+                    for target in targets.iter_mut() {
+                        match change {
+                            Change::None => break,
+                            Change::Edited => {
+                                let own = field.get(target);
+                                field.set(target, blend(own, &before, &value));
+                            }
+                            Change::Replaced => field.set(target, value.clone()),
+                        }
+                    }
+                    changed |= change != Change::None;
+                });
+                ui.end_row();
+            }
+        });
+        changed
+    }
+
+    /// Label and widget of one field, and how `value` was changed.
+    fn row<T>(&mut self, ui: &mut Ui, field: &Field<T>, value: &mut Value, mixed: Mixed) -> Change {
+        let hint = if field.tooltip.is_empty() {
+            "Right-click to reset, copy or paste".to_string()
+        } else {
+            format!("{}\n\nRight-click to reset, copy or paste", field.tooltip)
+        };
+        let label = ui.add(egui::Label::new(&field.label).sense(egui::Sense::click())).on_hover_text(hint);
+        let is_mixed = mixed.contains(&true);
+        let default = field.default.as_ref().filter(|d| *d != value || is_mixed);
+
+        let mut change = Change::None;
+        ui.horizontal(|ui| {
+            if edit_mixed(ui, &field.widget, value, mixed, &self.assets) {
+                change = Change::Edited;
+            }
+            if let Some(default) = default {
+                if ui.small_button("↺").on_hover_text(format!("Reset to {default}")).clicked() {
+                    *value = default.clone();
+                    change = Change::Replaced;
+                }
+            }
+        });
+
+        let paste = self.clipboard.as_ref()
+            .filter(|c| c.same_kind(value) && field.widget.accepts(c))
+            .cloned();
+        label.context_menu(|ui| {
+            if ui.add_enabled(default.is_some(), egui::Button::new("Reset to Default")).clicked() {
+                if let Some(default) = default {
+                    *value = default.clone();
+                    change = Change::Replaced;
+                }
+                ui.close_menu();
+            }
+            if ui.add_enabled(!is_mixed, egui::Button::new("Copy")).clicked() {
+                ui.output_mut(|o| o.copied_text = value.to_string());
+                self.clipboard = Some(value.clone());
+                ui.close_menu();
+            }
+            if ui.add_enabled(paste.is_some(), egui::Button::new("Paste")).clicked() {
+                if let Some(pasted) = paste {
+                    *value = pasted;
+                    change = Change::Replaced;
+                }
+                ui.close_menu();
+            }
+        });
+        change
+    }
+}
+
+/// How a row changed its value. A widget edit only touches what was edited;
+/// reset and paste replace the whole value on every target.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Change {
+    None,
+    Edited,
+    Replaced,
+}
+
+/// The widget for `value`. Returns whether it changed.
+pub fn edit_value(ui: &mut Ui, widget: &Widget, value: &mut Value, assets: &Assets) -> bool {
+    edit_mixed(ui, widget, value, [false; 3], assets)
+}
+
+/// `edit_value` for a merged value, showing "—" for its mixed components.
+pub fn edit_mixed(ui: &mut Ui, widget: &Widget, value: &mut Value, mixed: Mixed, assets: &Assets) -> bool {
+    match (widget, value) {
+        (Widget::Drag { speed, range, .. }, Value::Float(v)) => {
+            ui.add(drag(v, *speed, range, "", mixed[0])).changed()
+        }
+        (Widget::Drag { speed, range, .. }, Value::Int(v)) => {
+            ui.add(drag(v, *speed, range, "", mixed[0])).changed()
+        }
+        (Widget::Drag { speed, range, prefixes }, Value::Vec2(v)) => {
+            drag_components(ui, v, *speed, range, prefixes, mixed)
+        }
+        (Widget::Drag { speed, range, prefixes }, Value::Vec3(v)) => {
+            drag_components(ui, v, *speed, range, prefixes, mixed)
+        }
+        (Widget::Slider(range), Value::Float(v)) => {
+            let slider = egui::Slider::new(v, *range.start() as f32..=*range.end() as f32);
+            let slider = if mixed[0] { slider.custom_formatter(|_, _| "—".to_string()) } else { slider };
+            ui.add(slider).changed()
+        }
+        (Widget::Checkbox, Value::Bool(v)) => ui.checkbox(v, if mixed[0] { "—" } else { "" }).changed(),
+        (Widget::Color, Value::Vec3(v)) => {
+            let changed = ui.color_edit_button_rgb(v).changed();
+            if mixed.contains(&true) {
+                ui.weak("—");
+            }
+            changed
+        }
+        (Widget::Text, Value::Text(v)) => {
+            ui.add(egui::TextEdit::singleline(v).hint_text(if mixed[0] { "—" } else { "" })).changed()
+        }
+        (Widget::Choice(options), Value::Choice(index)) => {
+            let selected = if mixed[0] { "—" } else { options.get(*index).copied().unwrap_or("?") };
+            let mut changed = false;
+            egui::ComboBox::from_id_source("choice")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for (i, option) in options.iter().enumerate() {
+                        if ui.selectable_label(!mixed[0] && *index == i, *option).clicked() {
+                            *index = i;
+                            changed = true;
+                        }
+                    }
+                });
+            changed
+        }
+        (Widget::Asset { kind, none }, Value::Asset(slot)) => {
+            let options = assets.of(*kind);
+            let current = if mixed[0] {
+                "—"
+            } else {
+                slot.and_then(|u| options.iter().find(|(uu, _)| *uu == u).map(|(_, n)| n.as_str()))
+                    .unwrap_or(none)
+            };
+            let mut changed = false;
+            egui::ComboBox::from_id_source("asset")
+                .selected_text(current)
+                .show_ui(ui, |ui| {
+                    let choices = std::iter::once((None, *none))
+                        .chain(options.iter().map(|(uuid, name)| (Some(*uuid), name.as_str())));
+                    for (choice, name) in choices {
+                        if ui.selectable_label(!mixed[0] && *slot == choice, name).clicked() {
+                            *slot = choice;
+                            changed = true;
+                        }
+                    }
+                });
+            changed
+        }
+        (_, value) => {
+            ui.weak(format!("({value})"));
+            false
+        }
+    }
+}
+
+/// A drag value that reads "—" while `mixed`; typing over the dash works.
+fn drag<'a, N: egui::emath::Numeric>(
+    value: &'a mut N,
+    speed: f64,
+    range: &RangeInclusive<f64>,
+    prefix: &str,
+    mixed: bool,
+) -> DragValue<'a> {
+    let drag = DragValue::new(value).speed(speed).clamp_range(range.clone()).prefix(prefix);
+    if mixed {
+        drag.custom_formatter(|_, _| "—".to_string())
+            .custom_parser(|text| text.trim().trim_start_matches('—').trim().parse().ok())
+    } else {
+        drag
+    }
+}
+
+fn drag_components(
+    ui: &mut Ui,
+    v: &mut [f32],
+    speed: f64,
+    range: &RangeInclusive<f64>,
+    prefixes: &[&str],
+    mixed: Mixed,
+) -> bool {
+    let mut changed = false;
+    for (i, c) in v.iter_mut().enumerate() {
+        let prefix = prefixes.get(i).copied().unwrap_or_default();
+        changed |= ui.add(drag(c, speed, range, prefix, mixed[i])).changed();
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, PartialEq, Debug)]
+    struct Probe {
+        size: f32,
+        count: u32,
+        shape: Option<f32>,
+    }
+
+    impl Inspectable for Probe {
+        fn fields(&self) -> Vec<Field<Self>> {
+            with_defaults(vec![
+                field!("Size", Widget::drag(0.1).range(0.0..=10.0), size),
+                field!("Count", Widget::drag(1.0), count),
+                Field::new("Radius", Widget::drag(0.1),
+                    |t: &Probe| Value::Float(t.shape.unwrap_or_default()),
+                    |t, v| if let (Some(r), Value::Float(v)) = (t.shape.as_mut(), v) { *r = v })
+                    .visible_if(|t| t.shape.is_some()),
+            ], &Probe { size: 1.0, count: 3, shape: Some(0.5) })
+        }
+    }
+
+    #[test]
+    fn fields_read_write_and_reset() {
+        let mut probe = Probe { size: 4.0, count: 9, shape: None };
+        let fields = probe.fields();
+        assert_eq!(fields[0].get(&probe), Value::Float(4.0));
+        fields[1].set(&mut probe, Value::Int(-5));
+        assert_eq!(probe.count, 0, "u32 fields clamp at zero");
+        fields[0].set(&mut probe, Value::Text("nope".into()));
+        assert_eq!(probe.size, 4.0, "wrong kinds are ignored");
+        assert!(!fields[2].is_visible(&probe));
+
+        for field in &fields {
+            field.set(&mut probe, field.default.clone().unwrap());
+        }
+        assert_eq!(probe, Probe { size: 1.0, count: 3, shape: None });
+    }
+
+    #[test]
+    fn paste_needs_a_matching_widget() {
+        assert!(Widget::drag(1.0).accepts(&Value::Vec3([0.0; 3])));
+        assert!(Widget::Color.accepts(&Value::Vec3([0.0; 3])));
+        assert!(!Widget::Color.accepts(&Value::Float(1.0)));
+        assert!(Widget::Choice(&["a", "b"]).accepts(&Value::Choice(1)));
+        assert!(!Widget::Choice(&["a", "b"]).accepts(&Value::Choice(2)));
+        assert!(Value::Asset(None).same_kind(&Value::Asset(Some(Uuid::new_v4()))));
+        assert!(!Value::Int(1).same_kind(&Value::Float(1.0)));
+    }
+
+    #[test]
+    fn merge_marks_mixed_components() {
+        let (merged, mixed) = merge(&[Value::Vec3([1.0, 2.0, 3.0]), Value::Vec3([1.0, 5.0, 3.0])]).unwrap();
+        assert_eq!((merged, mixed), (Value::Vec3([1.0, 2.0, 3.0]), [false, true, false]));
+        let (merged, mixed) = merge(&[Value::Text("a".into()), Value::Text("b".into())]).unwrap();
+        assert_eq!((merged, mixed[0]), (Value::Text(String::new()), true));
+        assert_eq!(merge(&[Value::Float(0.5), Value::Float(0.5)]), Some((Value::Float(0.5), [false; 3])));
+        assert_eq!(merge(&[]), None);
+    }
+
+    #[test]
+    fn edits_reach_every_target() {
+        let mut probes = vec![
+            Probe { size: 1.0, count: 1, shape: None },
+            Probe { size: 2.0, count: 1, shape: Some(0.5) },
+        ];
+        let fields = probes[0].fields();
+        let (before, _) = merge(&probes.iter().map(|p| fields[0].get(p)).collect::<Vec<_>>()).unwrap();
+        for probe in &mut probes {
+            let own = fields[0].get(probe);
+            fields[0].set(probe, blend(own, &before, &Value::Float(0.7)));
+        }
+        assert!(probes.iter().all(|p| p.size == 0.7));
+
+        let before = Value::Vec3([1.0, 2.0, 3.0]);
+        let after = Value::Vec3([1.0, 9.0, 3.0]);
+        assert_eq!(blend(Value::Vec3([4.0, 5.0, 6.0]), &before, &after), Value::Vec3([4.0, 9.0, 6.0]));
+    }
+
+    #[test]
+    fn number_edits() {
+        assert_eq!(NumberEdit::parse("2"), Some(NumberEdit::Set(2.0)));
+        assert_eq!(NumberEdit::parse("-1.5"), Some(NumberEdit::Set(-1.5)));
+        assert_eq!(NumberEdit::parse("+1"), Some(NumberEdit::Offset(1.0)));
+        assert_eq!(NumberEdit::parse("+-0.5"), Some(NumberEdit::Offset(-0.5)));
+        assert_eq!(NumberEdit::parse(" -= 2 "), Some(NumberEdit::Offset(-2.0)));
+        assert_eq!(NumberEdit::parse("*2"), Some(NumberEdit::Scale(2.0)));
+        assert_eq!(NumberEdit::parse("—+1"), Some(NumberEdit::Offset(1.0)));
+        assert_eq!(NumberEdit::parse("abc"), None);
+        assert_eq!(NumberEdit::parse("+"), None);
+        assert_eq!(NumberEdit::Offset(1.0).apply(2.0), 3.0);
+        assert_eq!(NumberEdit::Scale(2.0).apply(1.5), 3.0);
+    }
+
+    #[test]
+    fn copied_text() {
+        assert_eq!(Value::Vec3([1.0, 0.5, -2.0]).to_string(), "1, 0.5, -2");
+        assert_eq!(Value::Asset(None).to_string(), "");
+    }
+}
@@ -1,186 +1,124 @@
-use egui::{DragValue, Ui};
+use egui::Ui;
 use enigma_3d::AppState;
 use uuid::Uuid;
 
-use crate::editor::state::{EditorRoot, ResourceKind, ShaderChoice};
+use crate::editor::inspector::inspectable::{field, with_defaults, AssetKind, Field, Inspectable, Inspector, Value, Widget};
+use crate::editor::state::{EditorRoot, MaterialDef, ShaderChoice};
 
-pub fn draw(ui: &mut Ui, app_state: &mut AppState, material_uuid: Uuid) {
-    let (mut def_clone, textures) = {
-        let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
-        let Some(project) = root.project.as_ref() else { return; };
-        let Some(d) = project.materials.iter().find(|m| m.uuid == material_uuid) else {
-            ui.label("(material not found)");
-            return;
-        };
-        let textures: Vec<(Uuid, String)> = project.manifest.iter()
-            .filter(|e| e.kind == ResourceKind::Texture)
-            .map(|e| (e.uuid, e.name.clone()))
-            .collect();
-        (d.clone(), textures)
-    };
-
-    let mut changed = false;
+const SHADERS: &[&str] = &["PBR Lit", "Unlit", "Custom"];
 
-    ui.horizontal(|ui| {
-        ui.label("Name");
-        changed |= ui.text_edit_singleline(&mut def_clone.name).changed();
-    });
-
-    let shaders: Vec<(Uuid, String)> = app_state
-        .get_state_data_value::<EditorRoot>("editor")
-        .and_then(|r| r.project.as_ref())
-        .map(|p| p.manifest.iter()
-            .filter(|e| e.kind == ResourceKind::Shader)
-            .map(|e| (e.uuid, e.name.clone()))
-            .collect())
-        .unwrap_or_default();
+fn shader_index(shader: &ShaderChoice) -> usize {
+    match shader {
+        ShaderChoice::PbrLit => 0,
+        ShaderChoice::Unlit => 1,
+        ShaderChoice::Custom { .. } => 2,
+    }
+}
 
-    ui.horizontal(|ui| {
-        ui.label("Shader");
-        let label = match def_clone.shader {
-            ShaderChoice::PbrLit => "PBR Lit",
-            ShaderChoice::Unlit => "Unlit",
-            ShaderChoice::Custom { .. } => "Custom",
-        };
-        egui::ComboBox::from_id_source("material_shader")
-            .selected_text(label)
-            .show_ui(ui, |ui| {
-                if ui.selectable_label(matches!(def_clone.shader, ShaderChoice::PbrLit), "PBR Lit").clicked() {
-                    def_clone.shader = ShaderChoice::PbrLit;
-                    changed = true;
-                }
-                if ui.selectable_label(matches!(def_clone.shader, ShaderChoice::Unlit), "Unlit").clicked() {
-                    def_clone.shader = ShaderChoice::Unlit;
-                    changed = true;
-                }
-                if ui.selectable_label(matches!(def_clone.shader, ShaderChoice::Custom { .. }), "Custom").clicked() {
-                    if !matches!(def_clone.shader, ShaderChoice::Custom { .. }) {
-                        def_clone.shader = ShaderChoice::Custom {
-                            vertex: None,
-                            fragment: None,
-                            geometry: None,
-                        };
-                        changed = true;
-                    }
-                }
-            });
-    });
+/// Vertex, fragment and geometry source of a custom shader.
+fn custom_stages(shader: &ShaderChoice) -> [Option<Uuid>; 3] {
+    match shader {
+        ShaderChoice::Custom { vertex, fragment, geometry } => [*vertex, *fragment, *geometry],
+        _ => [None; 3],
+    }
+}
 
-    if let ShaderChoice::Custom { vertex, fragment, geometry } = &mut def_clone.shader {
-        ui.label("Custom shader sources (defaults used if empty)");
-        changed |= shader_slot(ui, "Vertex", vertex, &shaders);
-        changed |= shader_slot(ui, "Fragment", fragment, &shaders);
-        changed |= shader_slot(ui, "Geometry", geometry, &shaders);
+fn custom_stage_mut(shader: &mut ShaderChoice, stage: usize) -> Option<&mut Option<Uuid>> {
+    match shader {
+        ShaderChoice::Custom { vertex, fragment, geometry } => Some(match stage {
+            0 => vertex,
+            1 => fragment,
+            _ => geometry,
+        }),
+        _ => None,
     }
+}
 
-    ui.separator();
-    ui.label("Texture slots");
-    changed |= texture_slot(ui, "Albedo", &mut def_clone.albedo, &textures);
-    if matches!(def_clone.shader, ShaderChoice::PbrLit) {
-        changed |= texture_slot(ui, "Normal", &mut def_clone.normal, &textures);
-        changed |= texture_slot(ui, "Roughness", &mut def_clone.roughness, &textures);
-        changed |= texture_slot(ui, "Metallic", &mut def_clone.metallic, &textures);
+impl Inspectable for MaterialDef {
+    fn fields(&self) -> Vec<Field<Self>> {
+        let texture = |none| Widget::Asset { kind: AssetKind::Texture, none };
+        let pbr_only = |t: &MaterialDef| matches!(t.shader, ShaderChoice::PbrLit);
+        let mut fields = vec![
+            field!("Name", Widget::Text, name).unique(),
+            Field::new("Shader", Widget::Choice(SHADERS),
+                |t: &MaterialDef| Value::Choice(shader_index(&t.shader)),
+                |t, v| {
+                    let Value::Choice(i) = v else { return; };
+                    if i == shader_index(&t.shader) { return; }
+                    t.shader = match i {
+                        0 => ShaderChoice::PbrLit,
+                        1 => ShaderChoice::Unlit,
+                        _ => ShaderChoice::Custom { vertex: None, fragment: None, geometry: None },
+                    };
+                }),
+        ];
+        for (stage, label) in ["Vertex", "Fragment", "Geometry"].into_iter().enumerate() {
+            fields.push(Field::new(label, Widget::Asset { kind: AssetKind::Shader, none: "(default)" },
+                move |t: &MaterialDef| Value::Asset(custom_stages(&t.shader)[stage]),
+                move |t, v| {
+                    if let (Some(slot), Value::Asset(v)) = (custom_stage_mut(&mut t.shader, stage), v) {
+                        *slot = v;
+                    }
+                })
+                .group("Custom Shader")
+                .tooltip("Leave at (default) to use the built-in source for this stage.")
+                .visible_if(|t| matches!(t.shader, ShaderChoice::Custom { .. })));
+        }
+        fields.extend([
+            field!("Albedo", texture("(none)"), albedo).group("Textures"),
+            field!("Normal", texture("(none)"), normal).group("Textures").visible_if(pbr_only),
+            field!("Roughness", texture("(none)"), roughness).group("Textures").visible_if(pbr_only),
+            field!("Metallic", texture("(none)"), metallic).group("Textures").visible_if(pbr_only),
+            field!("Emissive", texture("(none)"), emissive).group("Textures"),
+            field!("Tiling", Widget::drag(0.05).prefixes(&["u ", "v "]), uv_tiling).group("UV"),
+            field!("Offset", Widget::drag(0.01).prefixes(&["u ", "v "]), uv_offset).group("UV"),
+            field!("Color", Widget::Color, color).group("Parameters"),
+            field!("Emissive", Widget::drag(0.05).range(0.0..=100.0), emissive_strength).group("Parameters"),
+            field!("Roughness", Widget::Slider(0.0..=1.0), roughness_strength).group("Parameters"),
+            field!("Metallic", Widget::Slider(0.0..=1.0), metallic_strength).group("Parameters"),
+            field!("Normal", Widget::drag(0.01).range(0.0..=10.0), normal_strength).group("Parameters")
+                .tooltip("Strength of the normal map."),
+            field!("Transparent", Widget::Checkbox, transparent).group("Parameters"),
+            field!("Transparency", Widget::Slider(0.0..=1.0), transparency_strength).group("Parameters")
+                .visible_if(|t| t.transparent),
+        ]);
+        with_defaults(fields, &MaterialDef::default_pbr(self.name.clone()))
     }
-    changed |= texture_slot(ui, "Emissive", &mut def_clone.emissive, &textures);
+}
 
-    ui.separator();
-    ui.label("UV Tiling / Offset");
-    ui.horizontal(|ui| {
-        ui.label("Tiling");
-        changed |= ui.add(DragValue::new(&mut def_clone.uv_tiling[0]).speed(0.05).prefix("u ")).changed();
-        changed |= ui.add(DragValue::new(&mut def_clone.uv_tiling[1]).speed(0.05).prefix("v ")).changed();
-    });
-    ui.horizontal(|ui| {
-        ui.label("Offset");
-        changed |= ui.add(DragValue::new(&mut def_clone.uv_offset[0]).speed(0.01).prefix("u ")).changed();
-        changed |= ui.add(DragValue::new(&mut def_clone.uv_offset[1]).speed(0.01).prefix("v ")).changed();
-    });
+pub fn draw(ui: &mut Ui, app_state: &mut AppState, material_uuid: Uuid) {
+    draw_many(ui, app_state, &[material_uuid]);
+}
 
-    ui.separator();
-    ui.label("Parameters");
-    ui.horizontal(|ui| {
-        ui.label("Color");
-        changed |= ui.color_edit_button_rgb(&mut def_clone.color).changed();
-    });
-    changed |= ui.add(DragValue::new(&mut def_clone.emissive_strength).speed(0.05).prefix("emissive ")).changed();
-    changed |= ui.add(DragValue::new(&mut def_clone.roughness_strength).speed(0.01).prefix("roughness ")).changed();
-    changed |= ui.add(DragValue::new(&mut def_clone.metallic_strength).speed(0.01).prefix("metallic ")).changed();
-    changed |= ui.add(DragValue::new(&mut def_clone.normal_strength).speed(0.01).prefix("normal ")).changed();
-    changed |= ui.checkbox(&mut def_clone.transparent, "Transparent").changed();
-    if def_clone.transparent {
-        changed |= ui.add(DragValue::new(&mut def_clone.transparency_strength).speed(0.01).prefix("transparency ")).changed();
+/// Several materials edited together; fields they disagree on show "—".
+pub fn draw_many(ui: &mut Ui, app_state: &mut AppState, material_uuids: &[Uuid]) {
+    let mut defs: Vec<MaterialDef> = {
+        let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
+        let Some(project) = root.project.as_ref() else { return; };
+        project.materials.iter().filter(|m| material_uuids.contains(&m.uuid)).cloned().collect()
+    };
+    if defs.is_empty() {
+        ui.label("(material not found)");
+        return;
     }
+    if defs.len() > 1 {
+        ui.label(format!("Editing {} materials", defs.len()));
+    }
+
+    let mut inspector = Inspector::new(app_state);
+    let changed = inspector.show_many(ui, &mut defs);
+    inspector.finish(app_state);
 
     if changed {
         if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
             if let Some(project) = root.project.as_mut() {
-                if let Some(d) = project.materials.iter_mut().find(|m| m.uuid == material_uuid) {
-                    *d = def_clone;
+                for def in defs {
+                    if let Some(d) = project.materials.iter_mut().find(|m| m.uuid == def.uuid) {
+                        *d = def;
+                    }
                 }
                 root.editor.dirty = true;
             }
         }
     }
 }
-
-fn shader_slot(
-    ui: &mut Ui,
-    label: &str,
-    slot: &mut Option<Uuid>,
-    shaders: &[(Uuid, String)],
-) -> bool {
-    let mut changed = false;
-    ui.horizontal(|ui| {
-        ui.label(label);
-        let current = slot
-            .and_then(|u| shaders.iter().find(|(uu, _)| *uu == u).map(|(_, n)| n.clone()))
-            .unwrap_or_else(|| "(default)".into());
-        egui::ComboBox::from_id_source(format!("shader-slot-{label}"))
-            .selected_text(current)
-            .show_ui(ui, |ui| {
-                if ui.selectable_label(slot.is_none(), "(default)").clicked() {
-                    *slot = None;
-                    changed = true;
-                }
-                for (uuid, name) in shaders {
-                    let is = *slot == Some(*uuid);
-                    if ui.selectable_label(is, name).clicked() {
-                        *slot = Some(*uuid);
-                        changed = true;
-                    }
-                }
-            });
-    });
-    changed
-}
-
-fn texture_slot(
-    ui: &mut Ui,
-    label: &str,
-    slot: &mut Option<Uuid>,
-    textures: &[(Uuid, String)],
-) -> bool {
-    let mut changed = false;
-    ui.horizontal(|ui| {
-        ui.label(label);
-        let current = slot
-            .and_then(|u| textures.iter().find(|(uu, _)| *uu == u).map(|(_, n)| n.clone()))
-            .unwrap_or_else(|| "(none)".into());
-        egui::ComboBox::from_id_source(format!("slot-{label}"))
-            .selected_text(current)
-            .show_ui(ui, |ui| {
-                if ui.selectable_label(slot.is_none(), "(none)").clicked() {
-                    *slot = None;
-                    changed = true;
-                }
-                for (uuid, name) in textures {
-                    let is = *slot == Some(*uuid);
-                    if ui.selectable_label(is, name).clicked() {
-                        *slot = Some(*uuid);
-                        changed = true;
-                    }
-                }
-            });
-    });
-    changed
-}
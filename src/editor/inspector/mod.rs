@@ -1,3 +1,4 @@
+pub mod inspectable;
 pub mod animation;
 pub mod transform;
 pub mod mesh_material;
@@ -9,3 +10,6 @@ pub mod scene_settings;
 pub mod particle_editor;
 pub mod particle_instance;
 pub mod terrain_editor;
+pub mod script_editor;
+pub mod script_component;
+pub mod selection;
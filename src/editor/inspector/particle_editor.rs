@@ -1,11 +1,302 @@
 use egui::{DragValue, Ui};
 use enigma_3d::AppState;
 use enigma_3d::particle::{
-    BlendMode, ColorRange, EmitterShape, FlipbookConfig, InitialVelocity, Range, RenderStyle,
+    BlendMode, ColorRange, EmitterShape, FlipbookConfig, InitialVelocity, ParticleSystemConfig, Range, RenderStyle,
 };
 use uuid::Uuid;
 
-use crate::editor::state::{EditorRoot, ResourceKind};
+use crate::editor::inspector::inspectable::{
+    edit_value, field, with_defaults, AssetKind, Field, Inspectable, Inspector, Value, Widget,
+};
+use crate::editor::state::{EditorRoot, ParticleSystemDef};
+
+const SHAPES: &[&str] = &["Point", "Sphere", "Box", "Cone", "Disk"];
+const VELOCITIES: &[&str] = &["Outward", "Direction", "Cone", "Hemisphere"];
+const BLEND_MODES: &[&str] = &["Additive", "Alpha", "Premultiplied"];
+const MIN_MAX: &[&str] = &["min ", "max "];
+
+fn shape_index(shape: &EmitterShape) -> usize {
+    match shape {
+        EmitterShape::Point => 0,
+        EmitterShape::Sphere { .. } => 1,
+        EmitterShape::Box { .. } => 2,
+        EmitterShape::Cone { .. } => 3,
+        EmitterShape::Disk { .. } => 4,
+    }
+}
+
+fn velocity_index(velocity: &InitialVelocity) -> usize {
+    match velocity {
+        InitialVelocity::Outward { .. } => 0,
+        InitialVelocity::Direction { .. } => 1,
+        InitialVelocity::Cone { .. } => 2,
+        InitialVelocity::Hemisphere { .. } => 3,
+    }
+}
+
+fn velocity_speed(velocity: &mut InitialVelocity) -> &mut Range<f32> {
+    match velocity {
+        InitialVelocity::Outward { speed }
+        | InitialVelocity::Direction { speed, .. }
+        | InitialVelocity::Cone { speed, .. }
+        | InitialVelocity::Hemisphere { speed, .. } => speed,
+    }
+}
+
+fn range_value(range: &Range<f32>) -> Value {
+    Value::Vec2([range.min, range.max])
+}
+
+fn set_range(range: &mut Range<f32>, value: Value) {
+    if let Value::Vec2([min, max]) = value {
+        *range = Range::new(min, max);
+    }
+}
+
+fn float(value: &Value) -> Option<f32> {
+    match value {
+        Value::Float(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn vec3(value: &Value) -> Option<[f32; 3]> {
+    match value {
+        Value::Vec3(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// A float inside one variant of an enum field: read through `get`, written
+/// through `get_mut`, and hidden while the enum is another variant.
+fn variant_float<T: 'static>(
+    label: &'static str,
+    widget: Widget,
+    get: fn(&T) -> Option<f32>,
+    get_mut: fn(&mut T) -> Option<&mut f32>,
+) -> Field<T> {
+    Field::new(label, widget,
+        move |t: &T| Value::Float(get(t).unwrap_or_default()),
+        move |t, v| if let (Some(slot), Some(v)) = (get_mut(t), float(&v)) { *slot = v })
+        .visible_if(move |t| get(t).is_some())
+}
+
+/// `variant_float` for a vector.
+fn variant_vec3<T: 'static>(
+    label: &'static str,
+    widget: Widget,
+    get: fn(&T) -> Option<[f32; 3]>,
+    get_mut: fn(&mut T) -> Option<&mut [f32; 3]>,
+) -> Field<T> {
+    Field::new(label, widget,
+        move |t: &T| Value::Vec3(get(t).unwrap_or_default()),
+        move |t, v| if let (Some(slot), Some(v)) = (get_mut(t), vec3(&v)) { *slot = v })
+        .visible_if(move |t| get(t).is_some())
+}
+
+impl Inspectable for ParticleSystemConfig {
+    fn fields(&self) -> Vec<Field<Self>> {
+        type C = ParticleSystemConfig;
+        let sprite = |t: &C| matches!(t.render, RenderStyle::Sprite { .. });
+        with_defaults(vec![
+            field!("Name", Widget::Text, name).unique(),
+
+            field!("Max particles", Widget::drag(1.0).range(1.0..=100_000.0), max_particles).group("Emission"),
+            field!("Loop", Widget::Checkbox, looped).group("Emission"),
+            field!("Duration", Widget::drag(0.1).range(0.0..=3600.0), duration).group("Emission")
+                .tooltip("Seconds of one cycle."),
+            field!("Emission rate", Widget::drag(0.5).range(0.0..=100_000.0), emission_rate).group("Emission")
+                .tooltip("Particles per second."),
+            field!("Prewarm", Widget::Checkbox, prewarm).group("Emission")
+                .tooltip("Start as if one cycle had already run."),
+
+            Field::new("Shape", Widget::Choice(SHAPES),
+                |t: &C| Value::Choice(shape_index(&t.emitter_shape)),
+                |t, v| {
+                    let Value::Choice(i) = v else { return; };
+                    if i == shape_index(&t.emitter_shape) { return; }
+                    t.emitter_shape = match i {
+                        0 => EmitterShape::Point,
+                        1 => EmitterShape::Sphere { radius: 0.5 },
+                        2 => EmitterShape::Box { half_extents: [0.5, 0.5, 0.5] },
+                        3 => EmitterShape::Cone { angle: 0.5, height: 1.0 },
+                        _ => EmitterShape::Disk { radius: 0.5 },
+                    };
+                })
+                .group("Emitter Shape"),
+            variant_float("Radius", Widget::drag(0.05).range(0.0..=10_000.0),
+                |t: &C| match t.emitter_shape {
+                    EmitterShape::Sphere { radius } | EmitterShape::Disk { radius } => Some(radius),
+                    _ => None,
+                },
+                |t| match &mut t.emitter_shape {
+                    EmitterShape::Sphere { radius } | EmitterShape::Disk { radius } => Some(radius),
+                    _ => None,
+                })
+                .group("Emitter Shape"),
+            variant_vec3("Half extents", Widget::drag(0.05).range(0.0..=10_000.0),
+                |t: &C| match t.emitter_shape {
+                    EmitterShape::Box { half_extents } => Some(half_extents),
+                    _ => None,
+                },
+                |t| match &mut t.emitter_shape {
+                    EmitterShape::Box { half_extents } => Some(half_extents),
+                    _ => None,
+                })
+                .group("Emitter Shape"),
+            variant_float("Angle (rad)", Widget::drag(0.05).range(0.0..=std::f64::consts::PI),
+                |t: &C| match t.emitter_shape {
+                    EmitterShape::Cone { angle, .. } => Some(angle),
+                    _ => None,
+                },
+                |t| match &mut t.emitter_shape {
+                    EmitterShape::Cone { angle, .. } => Some(angle),
+                    _ => None,
+                })
+                .group("Emitter Shape"),
+            variant_float("Height", Widget::drag(0.05).range(0.0..=10_000.0),
+                |t: &C| match t.emitter_shape {
+                    EmitterShape::Cone { height, .. } => Some(height),
+                    _ => None,
+                },
+                |t| match &mut t.emitter_shape {
+                    EmitterShape::Cone { height, .. } => Some(height),
+                    _ => None,
+                })
+                .group("Emitter Shape"),
+
+            Field::new("Mode", Widget::Choice(VELOCITIES),
+                |t: &C| Value::Choice(velocity_index(&t.initial_velocity)),
+                |t, v| {
+                    let Value::Choice(i) = v else { return; };
+                    if i == velocity_index(&t.initial_velocity) { return; }
+                    let speed = Range::new(1.0, 2.0);
+                    t.initial_velocity = match i {
+                        0 => InitialVelocity::Outward { speed },
+                        1 => InitialVelocity::Direction { direction: [0.0, 1.0, 0.0], speed },
+                        2 => InitialVelocity::Cone { direction: [0.0, 1.0, 0.0], angle: 0.5, speed },
+                        _ => InitialVelocity::Hemisphere { normal: [0.0, 1.0, 0.0], speed },
+                    };
+                })
+                .group("Initial Velocity"),
+            variant_vec3("Direction", Widget::drag(0.05),
+                |t: &C| match t.initial_velocity {
+                    InitialVelocity::Direction { direction, .. } | InitialVelocity::Cone { direction, .. } => Some(direction),
+                    _ => None,
+                },
+                |t| match &mut t.initial_velocity {
+                    InitialVelocity::Direction { direction, .. } | InitialVelocity::Cone { direction, .. } => Some(direction),
+                    _ => None,
+                })
+                .group("Initial Velocity"),
+            variant_vec3("Normal", Widget::drag(0.05),
+                |t: &C| match t.initial_velocity {
+                    InitialVelocity::Hemisphere { normal, .. } => Some(normal),
+                    _ => None,
+                },
+                |t| match &mut t.initial_velocity {
+                    InitialVelocity::Hemisphere { normal, .. } => Some(normal),
+                    _ => None,
+                })
+                .group("Initial Velocity"),
+            variant_float("Angle (rad)", Widget::drag(0.05).range(0.0..=std::f64::consts::PI),
+                |t: &C| match t.initial_velocity {
+                    InitialVelocity::Cone { angle, .. } => Some(angle),
+                    _ => None,
+                },
+                |t| match &mut t.initial_velocity {
+                    InitialVelocity::Cone { angle, .. } => Some(angle),
+                    _ => None,
+                })
+                .group("Initial Velocity"),
+            Field::new("Speed", Widget::drag(0.1).prefixes(MIN_MAX),
+                |t: &C| range_value(velocity_speed(&mut t.initial_velocity.clone())),
+                |t, v| set_range(velocity_speed(&mut t.initial_velocity), v))
+                .group("Initial Velocity"),
+
+            Field::new("Lifetime", Widget::drag(0.1).range(0.0..=3600.0).prefixes(MIN_MAX),
+                |t: &C| range_value(&t.initial_lifetime),
+                |t, v| set_range(&mut t.initial_lifetime, v))
+                .group("Initial Particle")
+                .tooltip("Seconds each particle lives, picked between min and max."),
+            Field::new("Size", Widget::drag(0.05).range(0.0..=10_000.0).prefixes(MIN_MAX),
+                |t: &C| range_value(&t.initial_size),
+                |t, v| set_range(&mut t.initial_size, v))
+                .group("Initial Particle"),
+            Field::new("Rotation (rad)", Widget::drag(0.1).prefixes(MIN_MAX),
+                |t: &C| range_value(&t.initial_rotation),
+                |t, v| set_range(&mut t.initial_rotation, v))
+                .group("Initial Particle"),
+
+            Field::new("Gravity", Widget::Checkbox,
+                |t: &C| Value::Bool(t.gravity.is_some()),
+                |t, v| if let Value::Bool(on) = v {
+                    if on != t.gravity.is_some() {
+                        t.gravity = on.then_some([0.0, -9.81, 0.0]);
+                    }
+                })
+                .group("Forces"),
+            variant_vec3("Acceleration", Widget::drag(0.1), |t: &C| t.gravity, |t| t.gravity.as_mut())
+                .group("Forces"),
+            Field::new("Drag", Widget::Checkbox,
+                |t: &C| Value::Bool(t.drag.is_some()),
+                |t, v| if let Value::Bool(on) = v {
+                    if on != t.drag.is_some() {
+                        t.drag = on.then_some(0.1);
+                    }
+                })
+                .group("Forces"),
+            variant_float("Drag amount", Widget::drag(0.01).range(0.0..=100.0), |t: &C| t.drag, |t| t.drag.as_mut())
+                .group("Forces")
+                .tooltip("Fraction of velocity lost per second."),
+
+            Field::new("Blend", Widget::Choice(BLEND_MODES),
+                |t: &C| Value::Choice(match t.render {
+                    RenderStyle::Sprite { blend_mode: BlendMode::Additive, .. } => 0,
+                    RenderStyle::Sprite { blend_mode: BlendMode::PremultipliedAlpha, .. } => 2,
+                    _ => 1,
+                }),
+                |t, v| if let (RenderStyle::Sprite { blend_mode, .. }, Value::Choice(i)) = (&mut t.render, v) {
+                    *blend_mode = match i {
+                        0 => BlendMode::Additive,
+                        2 => BlendMode::PremultipliedAlpha,
+                        _ => BlendMode::Alpha,
+                    };
+                })
+                .group("Render")
+                .visible_if(sprite),
+            Field::new("Soft particles", Widget::Checkbox,
+                |t: &C| Value::Bool(matches!(t.render, RenderStyle::Sprite { soft_particles: true, .. })),
+                |t, v| if let (RenderStyle::Sprite { soft_particles, .. }, Value::Bool(on)) = (&mut t.render, v) {
+                    *soft_particles = on;
+                })
+                .group("Render")
+                .tooltip("Fade out where particles meet scene geometry.")
+                .visible_if(sprite),
+            variant_float("Fade distance", Widget::drag(0.05).range(0.0..=100.0),
+                |t: &C| match t.render {
+                    RenderStyle::Sprite { soft_particles: true, soft_fade_distance, .. } => Some(soft_fade_distance),
+                    _ => None,
+                },
+                |t| match &mut t.render {
+                    RenderStyle::Sprite { soft_particles: true, soft_fade_distance, .. } => Some(soft_fade_distance),
+                    _ => None,
+                })
+                .group("Render"),
+            variant_float("Velocity stretch", Widget::drag(0.05).range(0.0..=100.0),
+                |t: &C| match t.render {
+                    RenderStyle::Sprite { velocity_stretch, .. } => Some(velocity_stretch),
+                    _ => None,
+                },
+                |t| match &mut t.render {
+                    RenderStyle::Sprite { velocity_stretch, .. } => Some(velocity_stretch),
+                    _ => None,
+                })
+                .group("Render")
+                .tooltip("Stretch sprites along their velocity."),
+        ], &ParticleSystemDef::new_default(String::new()).config)
+    }
+}
 
 pub fn draw(ui: &mut Ui, app_state: &mut AppState, uuid: Uuid) {
     let mut def_clone = {
@@ -18,136 +309,56 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState, uuid: Uuid) {
         def.clone()
     };
 
-    let materials: Vec<(Uuid, String)> = app_state
-        .get_state_data_value::<EditorRoot>("editor")
-        .and_then(|r| r.project.as_ref())
-        .map(|p| p.materials.iter()
-            .filter(|m| !m.name.starts_with("INTERNAL::"))
-            .map(|m| (m.uuid, m.name.clone())).collect())
-        .unwrap_or_default();
-    let textures: Vec<(Uuid, String)> = app_state
-        .get_state_data_value::<EditorRoot>("editor")
-        .and_then(|r| r.project.as_ref())
-        .map(|p| p.manifest.iter()
-            .filter(|e| e.kind == ResourceKind::Texture)
-            .map(|e| (e.uuid, e.name.clone())).collect())
-        .unwrap_or_default();
-
-    let mut changed = false;
+    let mut inspector = Inspector::new(app_state);
+    let mut changed = inspector.show_group(ui, &mut def_clone.config, "");
 
-    ui.horizontal(|ui| {
-        ui.label("Name");
-        changed |= ui.text_edit_singleline(&mut def_clone.config.name).changed();
-    });
-
-    ui.horizontal(|ui| {
+    egui::Grid::new("particle_assets").num_columns(2).show(ui, |ui| {
         ui.label("Texture");
-        let current = def_clone.texture
-            .and_then(|u| textures.iter().find(|(uu, _)| *uu == u).map(|(_, n)| n.clone()))
-            .unwrap_or_else(|| "(none)".to_string());
-        egui::ComboBox::from_id_source("particle_texture")
-            .selected_text(current)
-            .show_ui(ui, |ui| {
-                if ui.selectable_label(def_clone.texture.is_none(), "(none)").clicked() {
-                    def_clone.texture = None;
-                    changed = true;
-                }
-                for (uuid, name) in &textures {
-                    let selected = def_clone.texture == Some(*uuid);
-                    if ui.selectable_label(selected, name).clicked() {
-                        def_clone.texture = Some(*uuid);
-                        changed = true;
-                    }
-                }
-            });
+        let mut texture = Value::Asset(def_clone.texture);
+        let widget = Widget::Asset { kind: AssetKind::Texture, none: "(none)" };
+        if edit_value(ui, &widget, &mut texture, &inspector.assets) {
+            if let Value::Asset(texture) = texture {
+                def_clone.texture = texture;
+                changed = true;
+            }
+        }
+        ui.end_row();
     });
 
     egui::CollapsingHeader::new("Material override (advanced)").default_open(false).show(ui, |ui| {
         ui.weak("Custom material must use a particle shader, or rendering will crash.");
-        ui.horizontal(|ui| {
+        egui::Grid::new("particle_material").num_columns(2).show(ui, |ui| {
             ui.label("Material");
-            let current = def_clone.material
-                .and_then(|u| materials.iter().find(|(uu, _)| *uu == u).map(|(_, n)| n.clone()))
-                .unwrap_or_else(|| "(use texture above)".to_string());
-            egui::ComboBox::from_id_source("particle_material")
-                .selected_text(current)
-                .show_ui(ui, |ui| {
-                    if ui.selectable_label(def_clone.material.is_none(), "(use texture above)").clicked() {
-                        def_clone.material = None;
-                        changed = true;
-                    }
-                    for (uuid, name) in &materials {
-                        let selected = def_clone.material == Some(*uuid);
-                        if ui.selectable_label(selected, name).clicked() {
-                            def_clone.material = Some(*uuid);
-                            changed = true;
-                        }
-                    }
-                });
+            let mut material = Value::Asset(def_clone.material);
+            let widget = Widget::Asset { kind: AssetKind::Material, none: "(use texture above)" };
+            if edit_value(ui, &widget, &mut material, &inspector.assets) {
+                if let Value::Asset(material) = material {
+                    def_clone.material = material;
+                    changed = true;
+                }
+            }
+            ui.end_row();
         });
     });
 
     let cfg = &mut def_clone.config;
-
-    egui::CollapsingHeader::new("Emission").default_open(true).show(ui, |ui| {
-        ui.horizontal(|ui| {
-            ui.label("Max particles");
-            changed |= ui.add(DragValue::new(&mut cfg.max_particles).speed(1.0)).changed();
-        });
-        ui.horizontal(|ui| {
-            changed |= ui.checkbox(&mut cfg.looped, "Loop").changed();
-            ui.label("Duration");
-            changed |= ui.add(DragValue::new(&mut cfg.duration).speed(0.1)).changed();
-        });
-        ui.horizontal(|ui| {
-            ui.label("Emission rate (p/s)");
-            changed |= ui.add(DragValue::new(&mut cfg.emission_rate).speed(0.5)).changed();
-        });
-        changed |= ui.checkbox(&mut cfg.prewarm, "Prewarm").changed();
-    });
-
-    egui::CollapsingHeader::new("Emitter Shape").default_open(true).show(ui, |ui| {
-        changed |= emitter_shape_editor(ui, &mut cfg.emitter_shape);
-    });
-
-    egui::CollapsingHeader::new("Initial Velocity").default_open(true).show(ui, |ui| {
-        changed |= initial_velocity_editor(ui, &mut cfg.initial_velocity);
-    });
-
-    egui::CollapsingHeader::new("Initial Particle").default_open(true).show(ui, |ui| {
-        changed |= range_editor(ui, "Lifetime", &mut cfg.initial_lifetime, 0.1);
-        changed |= range_editor(ui, "Size", &mut cfg.initial_size, 0.05);
-        changed |= range_editor(ui, "Rotation (rad)", &mut cfg.initial_rotation, 0.1);
-        changed |= color_range_editor(ui, "Color", &mut cfg.initial_color);
-    });
-
-    egui::CollapsingHeader::new("Forces").default_open(false).show(ui, |ui| {
-        let mut has_gravity = cfg.gravity.is_some();
-        if ui.checkbox(&mut has_gravity, "Gravity").changed() {
-            cfg.gravity = if has_gravity { Some([0.0, -9.81, 0.0]) } else { None };
-            changed = true;
-        }
-        if let Some(g) = cfg.gravity.as_mut() {
-            ui.horizontal(|ui| {
-                changed |= ui.add(DragValue::new(&mut g[0]).speed(0.1).prefix("x ")).changed();
-                changed |= ui.add(DragValue::new(&mut g[1]).speed(0.1).prefix("y ")).changed();
-                changed |= ui.add(DragValue::new(&mut g[2]).speed(0.1).prefix("z ")).changed();
+    for group in ["Emission", "Emitter Shape", "Initial Velocity", "Initial Particle", "Forces", "Render"] {
+        egui::CollapsingHeader::new(group)
+            .default_open(!ParticleSystemConfig::CLOSED_GROUPS.contains(&group))
+            .show(ui, |ui| {
+                if group == "Render" {
+                    changed |= render_style_editor(ui, &mut cfg.render);
+                }
+                changed |= inspector.show_group(ui, cfg, group);
+                if group == "Initial Particle" {
+                    changed |= color_range_editor(ui, "Color", &mut cfg.initial_color);
+                }
+                if group == "Render" {
+                    changed |= flipbook_editor(ui, &mut cfg.render);
+                }
             });
-        }
-
-        let mut has_drag = cfg.drag.is_some();
-        if ui.checkbox(&mut has_drag, "Drag").changed() {
-            cfg.drag = if has_drag { Some(0.1) } else { None };
-            changed = true;
-        }
-        if let Some(d) = cfg.drag.as_mut() {
-            changed |= ui.add(DragValue::new(d).speed(0.01)).changed();
-        }
-    });
-
-    egui::CollapsingHeader::new("Render").default_open(true).show(ui, |ui| {
-        changed |= render_style_editor(ui, &mut cfg.render);
-    });
+    }
+    inspector.finish(app_state);
 
     if changed {
         if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
@@ -161,155 +372,6 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState, uuid: Uuid) {
     }
 }
 
-fn range_editor(ui: &mut Ui, label: &str, range: &mut Range<f32>, speed: f32) -> bool {
-    let mut changed = false;
-    ui.horizontal(|ui| {
-        ui.label(label);
-        changed |= ui.add(DragValue::new(&mut range.min).speed(speed).prefix("min ")).changed();
-        changed |= ui.add(DragValue::new(&mut range.max).speed(speed).prefix("max ")).changed();
-    });
-    changed
-}
-
-fn emitter_shape_editor(ui: &mut Ui, shape: &mut EmitterShape) -> bool {
-    let mut changed = false;
-    let current = match shape {
-        EmitterShape::Point => "Point",
-        EmitterShape::Sphere { .. } => "Sphere",
-        EmitterShape::Box { .. } => "Box",
-        EmitterShape::Cone { .. } => "Cone",
-        EmitterShape::Disk { .. } => "Disk",
-    };
-    egui::ComboBox::from_id_source("emitter_shape").selected_text(current).show_ui(ui, |ui| {
-        if ui.selectable_label(matches!(shape, EmitterShape::Point), "Point").clicked() {
-            *shape = EmitterShape::Point; changed = true;
-        }
-        if ui.selectable_label(matches!(shape, EmitterShape::Sphere { .. }), "Sphere").clicked() {
-            if !matches!(shape, EmitterShape::Sphere { .. }) {
-                *shape = EmitterShape::Sphere { radius: 0.5 }; changed = true;
-            }
-        }
-        if ui.selectable_label(matches!(shape, EmitterShape::Box { .. }), "Box").clicked() {
-            if !matches!(shape, EmitterShape::Box { .. }) {
-                *shape = EmitterShape::Box { half_extents: [0.5, 0.5, 0.5] }; changed = true;
-            }
-        }
-        if ui.selectable_label(matches!(shape, EmitterShape::Cone { .. }), "Cone").clicked() {
-            if !matches!(shape, EmitterShape::Cone { .. }) {
-                *shape = EmitterShape::Cone { angle: 0.5, height: 1.0 }; changed = true;
-            }
-        }
-        if ui.selectable_label(matches!(shape, EmitterShape::Disk { .. }), "Disk").clicked() {
-            if !matches!(shape, EmitterShape::Disk { .. }) {
-                *shape = EmitterShape::Disk { radius: 0.5 }; changed = true;
-            }
-        }
-    });
-
-    match shape {
-        EmitterShape::Point => {}
-        EmitterShape::Sphere { radius } => {
-            ui.horizontal(|ui| {
-                ui.label("Radius");
-                changed |= ui.add(DragValue::new(radius).speed(0.05)).changed();
-            });
-        }
-        EmitterShape::Box { half_extents } => {
-            ui.horizontal(|ui| {
-                ui.label("Half extents");
-                changed |= ui.add(DragValue::new(&mut half_extents[0]).speed(0.05).prefix("x ")).changed();
-                changed |= ui.add(DragValue::new(&mut half_extents[1]).speed(0.05).prefix("y ")).changed();
-                changed |= ui.add(DragValue::new(&mut half_extents[2]).speed(0.05).prefix("z ")).changed();
-            });
-        }
-        EmitterShape::Cone { angle, height } => {
-            ui.horizontal(|ui| {
-                ui.label("Angle (rad)");
-                changed |= ui.add(DragValue::new(angle).speed(0.05)).changed();
-                ui.label("Height");
-                changed |= ui.add(DragValue::new(height).speed(0.05)).changed();
-            });
-        }
-        EmitterShape::Disk { radius } => {
-            ui.horizontal(|ui| {
-                ui.label("Radius");
-                changed |= ui.add(DragValue::new(radius).speed(0.05)).changed();
-            });
-        }
-    }
-    changed
-}
-
-fn initial_velocity_editor(ui: &mut Ui, v: &mut InitialVelocity) -> bool {
-    let mut changed = false;
-    let current = match v {
-        InitialVelocity::Outward { .. } => "Outward",
-        InitialVelocity::Direction { .. } => "Direction",
-        InitialVelocity::Cone { .. } => "Cone",
-        InitialVelocity::Hemisphere { .. } => "Hemisphere",
-    };
-    egui::ComboBox::from_id_source("init_velocity").selected_text(current).show_ui(ui, |ui| {
-        if ui.selectable_label(matches!(v, InitialVelocity::Outward { .. }), "Outward").clicked() {
-            if !matches!(v, InitialVelocity::Outward { .. }) {
-                *v = InitialVelocity::Outward { speed: Range::new(1.0, 2.0) }; changed = true;
-            }
-        }
-        if ui.selectable_label(matches!(v, InitialVelocity::Direction { .. }), "Direction").clicked() {
-            if !matches!(v, InitialVelocity::Direction { .. }) {
-                *v = InitialVelocity::Direction { direction: [0.0, 1.0, 0.0], speed: Range::new(1.0, 2.0) }; changed = true;
-            }
-        }
-        if ui.selectable_label(matches!(v, InitialVelocity::Cone { .. }), "Cone").clicked() {
-            if !matches!(v, InitialVelocity::Cone { .. }) {
-                *v = InitialVelocity::Cone { direction: [0.0, 1.0, 0.0], angle: 0.5, speed: Range::new(1.0, 2.0) }; changed = true;
-            }
-        }
-        if ui.selectable_label(matches!(v, InitialVelocity::Hemisphere { .. }), "Hemisphere").clicked() {
-            if !matches!(v, InitialVelocity::Hemisphere { .. }) {
-                *v = InitialVelocity::Hemisphere { normal: [0.0, 1.0, 0.0], speed: Range::new(1.0, 2.0) }; changed = true;
-            }
-        }
-    });
-
-    match v {
-        InitialVelocity::Outward { speed } => {
-            changed |= range_editor(ui, "Speed", speed, 0.1);
-        }
-        InitialVelocity::Direction { direction, speed } => {
-            ui.horizontal(|ui| {
-                ui.label("Direction");
-                changed |= ui.add(DragValue::new(&mut direction[0]).speed(0.05).prefix("x ")).changed();
-                changed |= ui.add(DragValue::new(&mut direction[1]).speed(0.05).prefix("y ")).changed();
-                changed |= ui.add(DragValue::new(&mut direction[2]).speed(0.05).prefix("z ")).changed();
-            });
-            changed |= range_editor(ui, "Speed", speed, 0.1);
-        }
-        InitialVelocity::Cone { direction, angle, speed } => {
-            ui.horizontal(|ui| {
-                ui.label("Direction");
-                changed |= ui.add(DragValue::new(&mut direction[0]).speed(0.05).prefix("x ")).changed();
-                changed |= ui.add(DragValue::new(&mut direction[1]).speed(0.05).prefix("y ")).changed();
-                changed |= ui.add(DragValue::new(&mut direction[2]).speed(0.05).prefix("z ")).changed();
-            });
-            ui.horizontal(|ui| {
-                ui.label("Angle (rad)");
-                changed |= ui.add(DragValue::new(angle).speed(0.05)).changed();
-            });
-            changed |= range_editor(ui, "Speed", speed, 0.1);
-        }
-        InitialVelocity::Hemisphere { normal, speed } => {
-            ui.horizontal(|ui| {
-                ui.label("Normal");
-                changed |= ui.add(DragValue::new(&mut normal[0]).speed(0.05).prefix("x ")).changed();
-                changed |= ui.add(DragValue::new(&mut normal[1]).speed(0.05).prefix("y ")).changed();
-                changed |= ui.add(DragValue::new(&mut normal[2]).speed(0.05).prefix("z ")).changed();
-            });
-            changed |= range_editor(ui, "Speed", speed, 0.1);
-        }
-    }
-    changed
-}
-
 fn color_range_editor(ui: &mut Ui, label: &str, c: &mut ColorRange) -> bool {
     let mut changed = false;
     let current = match c {
@@ -399,70 +461,48 @@ fn render_style_editor(ui: &mut Ui, r: &mut RenderStyle) -> bool {
             }
         });
     });
+    changed
+}
 
-    if let RenderStyle::Sprite { flipbook, blend_mode, soft_particles, soft_fade_distance, velocity_stretch } = r {
-        let blend_label = match blend_mode {
-            BlendMode::Additive => "Additive",
-            BlendMode::Alpha => "Alpha",
-            BlendMode::PremultipliedAlpha => "Premultiplied Alpha",
+fn flipbook_editor(ui: &mut Ui, r: &mut RenderStyle) -> bool {
+    let mut changed = false;
+    let RenderStyle::Sprite { flipbook, .. } = r else { return false; };
+    let mut has_flipbook = flipbook.is_some();
+    if ui.checkbox(&mut has_flipbook, "Flipbook (sprite sheet)").changed() {
+        *flipbook = if has_flipbook {
+            Some(FlipbookConfig {
+                cols: 4,
+                rows: 4,
+                frame_count: 16,
+                fps: 10.0,
+                blend: false,
+                randomize_start_frame: false,
+                fixed_frame: None,
+            })
+        } else {
+            None
         };
-        ui.horizontal(|ui| {
-            ui.label("Blend");
-            egui::ComboBox::from_id_source("blend_mode").selected_text(blend_label).show_ui(ui, |ui| {
-                if ui.selectable_label(matches!(blend_mode, BlendMode::Additive), "Additive").clicked() {
-                    *blend_mode = BlendMode::Additive; changed = true;
-                }
-                if ui.selectable_label(matches!(blend_mode, BlendMode::Alpha), "Alpha").clicked() {
-                    *blend_mode = BlendMode::Alpha; changed = true;
-                }
-                if ui.selectable_label(matches!(blend_mode, BlendMode::PremultipliedAlpha), "Premultiplied").clicked() {
-                    *blend_mode = BlendMode::PremultipliedAlpha; changed = true;
-                }
+        changed = true;
+    }
+    if let Some(fb) = flipbook.as_mut() {
+        ui.indent("flipbook_inner", |ui| {
+            ui.horizontal(|ui| {
+                changed |= ui.add(DragValue::new(&mut fb.cols).speed(1.0).prefix("cols ")).changed();
+                changed |= ui.add(DragValue::new(&mut fb.rows).speed(1.0).prefix("rows ")).changed();
             });
+            changed |= ui.add(DragValue::new(&mut fb.frame_count).speed(1.0).prefix("frame count ")).changed();
+            changed |= ui.add(DragValue::new(&mut fb.fps).speed(0.5).prefix("fps ")).changed();
+            changed |= ui.checkbox(&mut fb.blend, "Blend frames").changed();
+            changed |= ui.checkbox(&mut fb.randomize_start_frame, "Random start frame").changed();
+            let mut has_fixed = fb.fixed_frame.is_some();
+            if ui.checkbox(&mut has_fixed, "Lock to single frame").changed() {
+                fb.fixed_frame = if has_fixed { Some(0) } else { None };
+                changed = true;
+            }
+            if let Some(f) = fb.fixed_frame.as_mut() {
+                changed |= ui.add(DragValue::new(f).speed(1.0).prefix("frame ")).changed();
+            }
         });
-        changed |= ui.checkbox(soft_particles, "Soft particles").changed();
-        if *soft_particles {
-            changed |= ui.add(DragValue::new(soft_fade_distance).speed(0.05).prefix("fade ")).changed();
-        }
-        changed |= ui.add(DragValue::new(velocity_stretch).speed(0.05).prefix("velocity stretch ")).changed();
-
-        let mut has_flipbook = flipbook.is_some();
-        if ui.checkbox(&mut has_flipbook, "Flipbook (sprite sheet)").changed() {
-            *flipbook = if has_flipbook {
-                Some(FlipbookConfig {
-                    cols: 4,
-                    rows: 4,
-                    frame_count: 16,
-                    fps: 10.0,
-                    blend: false,
-                    randomize_start_frame: false,
-                    fixed_frame: None,
-                })
-            } else {
-                None
-            };
-            changed = true;
-        }
-        if let Some(fb) = flipbook.as_mut() {
-            ui.indent("flipbook_inner", |ui| {
-                ui.horizontal(|ui| {
-                    changed |= ui.add(DragValue::new(&mut fb.cols).speed(1.0).prefix("cols ")).changed();
-                    changed |= ui.add(DragValue::new(&mut fb.rows).speed(1.0).prefix("rows ")).changed();
-                });
-                changed |= ui.add(DragValue::new(&mut fb.frame_count).speed(1.0).prefix("frame count ")).changed();
-                changed |= ui.add(DragValue::new(&mut fb.fps).speed(0.5).prefix("fps ")).changed();
-                changed |= ui.checkbox(&mut fb.blend, "Blend frames").changed();
-                changed |= ui.checkbox(&mut fb.randomize_start_frame, "Random start frame").changed();
-                let mut has_fixed = fb.fixed_frame.is_some();
-                if ui.checkbox(&mut has_fixed, "Lock to single frame").changed() {
-                    fb.fixed_frame = if has_fixed { Some(0) } else { None };
-                    changed = true;
-                }
-                if let Some(f) = fb.fixed_frame.as_mut() {
-                    changed |= ui.add(DragValue::new(f).speed(1.0).prefix("frame ")).changed();
-                }
-            });
-        }
     }
     changed
 }
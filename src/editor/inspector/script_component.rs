@@ -0,0 +1,157 @@
+use egui::Ui;
+use enigma_3d::AppState;
+use uuid::Uuid;
+
+use crate::editor::inspector::inspectable::{Field, Inspectable, Inspector, Value, Widget};
+use crate::editor::state::{EditorRoot, ScriptComponent, ScriptField, ScriptValue, Selection};
+
+/// A component's resolved fields, with the script's own values as defaults.
+struct ComponentFields {
+    fields: Vec<ScriptField>,
+    defaults: Vec<ScriptField>,
+}
+
+fn to_value(value: &ScriptValue) -> Value {
+    match value {
+        ScriptValue::Float(v) => Value::Float(*v),
+        ScriptValue::Int(v) => Value::Int(*v),
+        ScriptValue::Bool(v) => Value::Bool(*v),
+        ScriptValue::Text(v) => Value::Text(v.clone()),
+        ScriptValue::Vec3(v) => Value::Vec3(*v),
+    }
+}
+
+fn from_value(value: Value) -> Option<ScriptValue> {
+    Some(match value {
+        Value::Float(v) => ScriptValue::Float(v),
+        Value::Int(v) => ScriptValue::Int(v),
+        Value::Bool(v) => ScriptValue::Bool(v),
+        Value::Text(v) => ScriptValue::Text(v),
+        Value::Vec3(v) => ScriptValue::Vec3(v),
+        _ => return None,
+    })
+}
+
+fn widget(value: &ScriptValue) -> Widget {
+    match value {
+        ScriptValue::Float(_) | ScriptValue::Vec3(_) => Widget::drag(0.05),
+        ScriptValue::Int(_) => Widget::drag(1.0),
+        ScriptValue::Bool(_) => Widget::Checkbox,
+        ScriptValue::Text(_) => Widget::Text,
+    }
+}
+
+impl Inspectable for ComponentFields {
+    fn fields(&self) -> Vec<Field<Self>> {
+        self.fields.iter().enumerate().map(|(i, field)| {
+            let described = Field::new(field.name.clone(), widget(&field.value),
+                move |t: &Self| to_value(&t.fields[i].value),
+                move |t, v| {
+                    let slot = &mut t.fields[i].value;
+                    if let Some(v) = from_value(v).filter(|v| v.type_name() == slot.type_name()) {
+                        *slot = v;
+                    }
+                });
+            match self.defaults.iter().find(|d| d.name == field.name) {
+                Some(default) => described.default(to_value(&default.value)),
+                None => described,
+            }
+        }).collect()
+    }
+}
+
+pub fn draw(ui: &mut Ui, app_state: &mut AppState, object_uuid: Uuid) {
+    egui::CollapsingHeader::new("Scripts").default_open(true).show(ui, |ui| {
+        let (scene_uuid, scripts, mut attached) = {
+            let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
+            let Some(project) = root.project.as_ref() else { return; };
+            let Some(scene_uuid) = project.scenes.get(project.active_scene_index).map(|s| s.uuid) else { return; };
+            let scripts: Vec<(Uuid, String)> = project.scripts.iter()
+                .map(|s| (s.uuid, s.name.clone()))
+                .collect();
+            // (script uuid, type name, resolved fields with the def's defaults)
+            let attached: Vec<(Uuid, String, ComponentFields)> = project
+                .components_for_object(scene_uuid, object_uuid)
+                .into_iter()
+                .filter_map(|c| {
+                    let def = project.scripts.iter().find(|s| s.uuid == c.script)?;
+                    let fields = ComponentFields { fields: c.resolved_fields(def), defaults: def.fields.clone() };
+                    Some((def.uuid, def.name.clone(), fields))
+                })
+                .collect();
+            (scene_uuid, scripts, attached)
+        };
+
+        let mut edited: Option<(Uuid, Vec<ScriptField>)> = None;
+        let mut detach: Option<Uuid> = None;
+        let mut attach: Option<Uuid> = None;
+        let mut open_def: Option<Uuid> = None;
+
+        let mut inspector = Inspector::new(app_state);
+        for (script_uuid, name, fields) in attached.iter_mut() {
+            ui.push_id(*script_uuid, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.link(name.as_str()).on_hover_text("Edit the script's fields").clicked() {
+                        open_def = Some(*script_uuid);
+                    }
+                    if ui.small_button("×").on_hover_text("Detach").clicked() {
+                        detach = Some(*script_uuid);
+                    }
+                });
+                if inspector.show(ui, fields) {
+                    edited = Some((*script_uuid, fields.fields.clone()));
+                }
+            });
+            ui.separator();
+        }
+        inspector.finish(app_state);
+
+        let available: Vec<&(Uuid, String)> = scripts.iter()
+            .filter(|(u, _)| !attached.iter().any(|(a, _, _)| a == u))
+            .collect();
+        if available.is_empty() {
+            if scripts.is_empty() {
+                ui.label("Create scripts in the resource browser's Scripts tab.");
+            }
+        } else {
+            egui::ComboBox::from_id_source("script_attach")
+                .selected_text("+ Add Script")
+                .show_ui(ui, |ui| {
+                    for (uuid, name) in available {
+                        if ui.selectable_label(false, name).clicked() {
+                            attach = Some(*uuid);
+                        }
+                    }
+                });
+        }
+
+        if edited.is_none() && detach.is_none() && attach.is_none() && open_def.is_none() {
+            return;
+        }
+        let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+        if let Some(def) = open_def {
+            root.editor.selection.set(Selection::Script(def));
+        }
+        let Some(project) = root.project.as_mut() else { return; };
+        let matches = |c: &ScriptComponent, script: Uuid| {
+            c.scene == scene_uuid && c.object == object_uuid && c.script == script
+        };
+        if let Some((script, fields)) = edited {
+            if let Some(c) = project.script_components.iter_mut().find(|c| matches(c, script)) {
+                c.fields = fields;
+            }
+        }
+        if let Some(script) = detach {
+            project.script_components.retain(|c| !matches(c, script));
+        }
+        if let Some(script) = attach {
+            project.script_components.push(ScriptComponent {
+                scene: scene_uuid,
+                object: object_uuid,
+                script,
+                fields: Vec::new(),
+            });
+        }
+        root.editor.dirty = true;
+    });
+}
@@ -0,0 +1,93 @@
+use egui::{DragValue, Ui};
+use enigma_3d::AppState;
+use uuid::Uuid;
+
+use crate::editor::state::{EditorRoot, ScriptField, ScriptValue};
+
+const FIELD_TYPES: [&str; 5] = ["Float", "Int", "Bool", "Text", "Vec3"];
+
+pub fn draw(ui: &mut Ui, app_state: &mut AppState, script_uuid: Uuid) {
+    let mut def_clone = {
+        let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
+        let Some(project) = root.project.as_ref() else { return; };
+        let Some(d) = project.scripts.iter().find(|s| s.uuid == script_uuid) else {
+            ui.label("(script not found)");
+            return;
+        };
+        d.clone()
+    };
+
+    ui.heading("Script");
+    ui.label(format!("Type: {}", def_clone.name));
+    ui.label(format!("Path: {}", def_clone.relative_path));
+    ui.label("Read the fields in `from_fields`, e.g. `fields.float(\"speed\", 1.0)`.");
+    ui.separator();
+
+    let mut changed = false;
+    let mut remove: Option<usize> = None;
+    egui::CollapsingHeader::new("Exposed Fields").default_open(true).show(ui, |ui| {
+        for (idx, field) in def_clone.fields.iter_mut().enumerate() {
+            ui.push_id(idx, |ui| {
+                ui.horizontal(|ui| {
+                    changed |= ui.add(egui::TextEdit::singleline(&mut field.name).desired_width(100.0)).changed();
+                    egui::ComboBox::from_id_source("script_field_type")
+                        .selected_text(field.value.type_name())
+                        .show_ui(ui, |ui| {
+                            for ty in FIELD_TYPES {
+                                if ui.selectable_label(field.value.type_name() == ty, ty).clicked()
+                                    && field.value.type_name() != ty
+                                {
+                                    field.value = ScriptValue::default_of(ty);
+                                    changed = true;
+                                }
+                            }
+                        });
+                    if ui.small_button("×").on_hover_text("Remove field").clicked() {
+                        remove = Some(idx);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Default");
+                    changed |= value_edit(ui, &mut field.value);
+                });
+            });
+        }
+        if ui.button("+ Add Field").clicked() {
+            let name = format!("field_{}", def_clone.fields.len() + 1);
+            def_clone.fields.push(ScriptField { name, value: ScriptValue::Float(0.0) });
+            changed = true;
+        }
+    });
+    if let Some(idx) = remove {
+        def_clone.fields.remove(idx);
+        changed = true;
+    }
+
+    if changed {
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            if let Some(project) = root.project.as_mut() {
+                if let Some(d) = project.scripts.iter_mut().find(|s| s.uuid == script_uuid) {
+                    *d = def_clone;
+                }
+                root.editor.dirty = true;
+            }
+        }
+    }
+}
+
+/// Widget for one field value. Returns whether it changed.
+pub fn value_edit(ui: &mut Ui, value: &mut ScriptValue) -> bool {
+    match value {
+        ScriptValue::Float(v) => ui.add(DragValue::new(v).speed(0.05)).changed(),
+        ScriptValue::Int(v) => ui.add(DragValue::new(v)).changed(),
+        ScriptValue::Bool(v) => ui.checkbox(v, "").changed(),
+        ScriptValue::Text(v) => ui.text_edit_singleline(v).changed(),
+        ScriptValue::Vec3(v) => {
+            let mut changed = false;
+            changed |= ui.add(DragValue::new(&mut v[0]).speed(0.05).prefix("x ")).changed();
+            changed |= ui.add(DragValue::new(&mut v[1]).speed(0.05).prefix("y ")).changed();
+            changed |= ui.add(DragValue::new(&mut v[2]).speed(0.05).prefix("z ")).changed();
+            changed
+        }
+    }
+}
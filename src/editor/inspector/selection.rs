@@ -0,0 +1,81 @@
+use egui::Ui;
+use enigma_3d::AppState;
+use uuid::Uuid;
+
+use crate::editor::actions;
+use crate::editor::inspector::{material_editor, transform};
+use crate::editor::state::{EditorRoot, Modal, PendingDelete, Selection};
+
+/// Summary and bulk actions for a multi-selection, then the fields the
+/// selected items share, edited on all of them at once.
+pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
+    let (selection, materials) = {
+        let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
+        let materials: Vec<(Uuid, String)> = root.project.as_ref()
+            .map(|p| p.materials.iter()
+                .filter(|m| !m.name.starts_with("INTERNAL::"))
+                .map(|m| (m.uuid, m.name.clone()))
+                .collect())
+            .unwrap_or_default();
+        (root.editor.selection.clone(), materials)
+    };
+    let material_uuids = selection.materials();
+    if !material_uuids.is_empty() {
+        material_editor::draw_many(ui, app_state, &material_uuids);
+        return;
+    }
+    let object_uuids = selection.scene_objects();
+    let items = selection.items();
+    let objects = object_uuids.len();
+    let lights = items.iter().filter(|i| matches!(i, Selection::Light(_))).count();
+    let particles = items.iter().filter(|i| matches!(i, Selection::ParticleInstance(_))).count();
+
+    let mut duplicate = false;
+    let mut delete = false;
+    let mut assign: Option<Uuid> = None;
+    egui::CollapsingHeader::new(format!("Selection ({})", items.len())).default_open(true).show(ui, |ui| {
+        for (count, noun) in [(objects, "object"), (lights, "light"), (particles, "particle instance")] {
+            if count > 0 {
+                ui.label(format!("{count} {noun}{}", if count == 1 { "" } else { "s" }));
+            }
+        }
+        ui.horizontal(|ui| {
+            duplicate = ui.button("Duplicate").on_hover_text("Ctrl+D").clicked();
+            delete = ui.button("Delete").on_hover_text("Del").clicked();
+        });
+        if objects > 0 {
+            ui.horizontal(|ui| {
+                ui.label("Material");
+                egui::ComboBox::from_id_source("selection_material")
+                    .selected_text("Assign to all…")
+                    .show_ui(ui, |ui| {
+                        for (uuid, name) in &materials {
+                            if ui.selectable_label(false, name).clicked() {
+                                assign = Some(*uuid);
+                            }
+                        }
+                    });
+            });
+        }
+    });
+
+    if objects > 0 {
+        transform::draw_for_objects(ui, app_state, &object_uuids);
+    }
+
+    if duplicate {
+        actions::duplicate_selection(app_state);
+    }
+    if let Some(material) = assign {
+        actions::assign_material_to_selection(app_state, material);
+    }
+    if delete {
+        let pending: Vec<PendingDelete> = items.iter().filter_map(PendingDelete::for_selection).collect();
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            root.editor.modal = Some(Modal::ConfirmDelete {
+                label: format!("{} selected items", pending.len()),
+                pending: PendingDelete::Many(pending),
+            });
+        }
+    }
+}
@@ -1,9 +1,43 @@
-use egui::{DragValue, Ui};
+use egui::Ui;
 use enigma_3d::AppState;
-use uuid::Uuid;
 
+use crate::editor::inspector::inspectable::{field, with_defaults, AssetKind, Field, Inspectable, Inspector, Widget};
 use crate::editor::state::{EditorRoot, TerrainDef};
 
+impl Inspectable for TerrainDef {
+    const CLOSED_GROUPS: &'static [&'static str] = &["Mesh", "Colors"];
+
+    fn fields(&self) -> Vec<Field<Self>> {
+        with_defaults(vec![
+            field!("Position", Widget::drag(0.1), position).group("Position"),
+            field!("Width", Widget::drag(1.0).range(1.0..=100_000.0), width).group("Size"),
+            field!("Depth", Widget::drag(1.0).range(1.0..=100_000.0), depth).group("Size"),
+            field!("Max height", Widget::drag(0.5).range(0.0..=10_000.0), max_height).group("Size"),
+            field!("Resolution", Widget::drag(1.0).range(2.0..=4096.0), resolution).group("Mesh")
+                .tooltip("Vertices per side. Must be divisible by the tile count."),
+            field!("Tiles per side", Widget::drag(1.0).range(1.0..=64.0), tile_count).group("Mesh")
+                .tooltip("The terrain is split into this many tiles per side for culling."),
+            field!("Scale", Widget::drag(0.005).range(0.0..=10.0), noise_scale).group("Noise")
+                .tooltip("Noise frequency; smaller values give broader hills."),
+            field!("Amplitude", Widget::drag(0.05).range(0.0..=100.0), noise_amplitude).group("Noise"),
+            field!("Octaves", Widget::drag(1.0).range(1.0..=12.0), noise_octaves).group("Noise"),
+            field!("Persistence", Widget::Slider(0.0..=1.0), noise_persistence).group("Noise")
+                .tooltip("How much each octave contributes relative to the previous one."),
+            field!("Material", Widget::Asset { kind: AssetKind::Material, none: "(vertex colors)" }, material)
+                .group("Material")
+                .tooltip("When set, the terrain shader samples the material's albedo."),
+            field!("Flat low", Widget::Color, color_flat_low).group("Colors"),
+            field!("Flat high", Widget::Color, color_flat_high).group("Colors"),
+            field!("Slope", Widget::Color, color_slope).group("Colors"),
+            field!("Slope threshold", Widget::Slider(0.0..=1.0), slope_threshold).group("Colors")
+                .tooltip("Surfaces steeper than this use the slope color."),
+            field!("Height mid", Widget::Slider(0.0..=1.0), height_mid).group("Colors")
+                .tooltip("Relative height where flat low blends into flat high."),
+            field!("UV scale", Widget::drag(0.5).range(0.0..=1000.0), uv_scale).group("Colors"),
+        ], &TerrainDef::new_default())
+    }
+}
+
 pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
     let mut def_clone: Option<TerrainDef> = {
         let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
@@ -12,6 +46,7 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
         scene.and_then(|s| s.terrain.clone())
     };
 
+    let mut inspector = Inspector::new(app_state);
     let mut changed = false;
     let mut clear_terrain = false;
     let mut enable_terrain = false;
@@ -31,82 +66,10 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
                 }
             });
 
-            egui::CollapsingHeader::new("Position").default_open(true).show(ui, |ui| {
-                ui.horizontal(|ui| {
-                    changed |= ui.add(DragValue::new(&mut def.position[0]).speed(0.1).prefix("x ")).changed();
-                    changed |= ui.add(DragValue::new(&mut def.position[1]).speed(0.1).prefix("y ")).changed();
-                    changed |= ui.add(DragValue::new(&mut def.position[2]).speed(0.1).prefix("z ")).changed();
-                });
-            });
-
-            egui::CollapsingHeader::new("Size").default_open(true).show(ui, |ui| {
-                changed |= ui.add(DragValue::new(&mut def.width).speed(1.0).prefix("width ")).changed();
-                changed |= ui.add(DragValue::new(&mut def.depth).speed(1.0).prefix("depth ")).changed();
-                changed |= ui.add(DragValue::new(&mut def.max_height).speed(0.5).prefix("max height ")).changed();
-            });
-
-            egui::CollapsingHeader::new("Mesh").default_open(false).show(ui, |ui| {
-                changed |= ui.add(DragValue::new(&mut def.resolution).speed(1.0).prefix("resolution ")).changed();
-                changed |= ui.add(DragValue::new(&mut def.tile_count).speed(1.0).prefix("tiles per side ")).changed();
-                ui.weak("resolution must be divisible by tile count");
-            });
-
-            egui::CollapsingHeader::new("Noise").default_open(true).show(ui, |ui| {
-                changed |= ui.add(DragValue::new(&mut def.noise_scale).speed(0.005).prefix("scale ")).changed();
-                changed |= ui.add(DragValue::new(&mut def.noise_amplitude).speed(0.05).prefix("amplitude ")).changed();
-                changed |= ui.add(DragValue::new(&mut def.noise_octaves).speed(1.0).prefix("octaves ")).changed();
-                changed |= ui.add(DragValue::new(&mut def.noise_persistence).speed(0.05).prefix("persistence ")).changed();
-            });
-
-            egui::CollapsingHeader::new("Material").default_open(true).show(ui, |ui| {
-                let materials: Vec<(Uuid, String)> = app_state
-                    .get_state_data_value::<EditorRoot>("editor")
-                    .and_then(|r| r.project.as_ref())
-                    .map(|p| p.materials.iter().map(|m| (m.uuid, m.name.clone())).collect())
-                    .unwrap_or_default();
-                let current = def.material
-                    .and_then(|u| materials.iter().find(|(uu, _)| *uu == u).map(|(_, n)| n.clone()))
-                    .unwrap_or_else(|| "(vertex colors)".to_string());
-                ui.horizontal(|ui| {
-                    ui.label("Material:");
-                    egui::ComboBox::from_id_source("terrain_material")
-                        .selected_text(current)
-                        .show_ui(ui, |ui| {
-                            if ui.selectable_label(def.material.is_none(), "(vertex colors)").clicked() {
-                                def.material = None;
-                                changed = true;
-                            }
-                            for (uuid, name) in &materials {
-                                let selected = def.material == Some(*uuid);
-                                if ui.selectable_label(selected, name).clicked() {
-                                    def.material = Some(*uuid);
-                                    changed = true;
-                                }
-                            }
-                        });
-                });
-                ui.weak("when set, the terrain shader samples the material's albedo");
-            });
-
-            egui::CollapsingHeader::new("Colors").default_open(false).show(ui, |ui| {
-                ui.horizontal(|ui| {
-                    ui.label("Flat low");
-                    changed |= ui.color_edit_button_rgb(&mut def.color_flat_low).changed();
-                });
-                ui.horizontal(|ui| {
-                    ui.label("Flat high");
-                    changed |= ui.color_edit_button_rgb(&mut def.color_flat_high).changed();
-                });
-                ui.horizontal(|ui| {
-                    ui.label("Slope");
-                    changed |= ui.color_edit_button_rgb(&mut def.color_slope).changed();
-                });
-                changed |= ui.add(DragValue::new(&mut def.slope_threshold).speed(0.01).prefix("slope threshold ")).changed();
-                changed |= ui.add(DragValue::new(&mut def.height_mid).speed(0.01).prefix("height mid ")).changed();
-                changed |= ui.add(DragValue::new(&mut def.uv_scale).speed(0.5).prefix("uv scale ")).changed();
-            });
+            changed |= inspector.show(ui, def);
         }
     }
+    inspector.finish(app_state);
 
     if changed || clear_terrain || enable_terrain {
         if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
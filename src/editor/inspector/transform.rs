@@ -1,9 +1,24 @@
+use std::cell::Cell;
+
 use egui::{DragValue, Ui};
 use enigma_3d::AppState;
+use nalgebra::UnitQuaternion;
 use uuid::Uuid;
 
+use crate::editor::inspector::inspectable::NumberEdit;
+use crate::editor::scene_graph::{self, Trs};
+use crate::editor::visibility;
+
+/// Components closer than this count as equal when comparing objects.
+const SAME: f32 = 1e-4;
+
 pub fn draw_for_object(ui: &mut Ui, app_state: &mut AppState, uuid: Uuid) {
+    if let Some(parent) = scene_graph::parent_world(app_state, uuid) {
+        draw_local(ui, app_state, uuid, parent);
+        return;
+    }
     let Some(obj) = app_state.objects.iter_mut().find(|o| o.get_unique_id() == uuid) else { return; };
+    let before = Trs::of(obj);
 
     egui::CollapsingHeader::new("Transform").default_open(true).show(ui, |ui| {
         let mut position: [f32; 3] = obj.transform.position.into();
@@ -21,6 +36,152 @@ pub fn draw_for_object(ui: &mut Ui, app_state: &mut AppState, uuid: Uuid) {
             obj.transform.set_scale(scale);
         }
     });
+    scene_graph::carry_children(app_state, uuid, before);
+}
+
+/// Transform of a child object, shown and edited relative to its parent.
+fn draw_local(ui: &mut Ui, app_state: &mut AppState, uuid: Uuid, parent: Trs) {
+    let parent_name = scene_graph::parent(app_state, uuid)
+        .and_then(|p| visibility::find(app_state, p))
+        .map(|o| o.name.clone())
+        .unwrap_or_default();
+    let Some(before) = app_state.objects.iter().find(|o| o.get_unique_id() == uuid).map(Trs::of) else { return; };
+    let mut local = scene_graph::to_local(&parent, &before);
+
+    let mut changed = false;
+    egui::CollapsingHeader::new("Transform").default_open(true).show(ui, |ui| {
+        ui.weak(format!("Relative to {parent_name}"));
+        let mut position: [f32; 3] = local.position.into();
+        if vec3_edit(ui, "Position", &mut position, 0.05) {
+            local.position = position.into();
+            changed = true;
+        }
+
+        let (rx, ry, rz) = local.rotation.euler_angles();
+        let mut rotation = [rx.to_degrees(), ry.to_degrees(), rz.to_degrees()];
+        if vec3_edit(ui, "Rotation (deg)", &mut rotation, 1.0) {
+            let r = rotation.map(f32::to_radians);
+            local.rotation = UnitQuaternion::from_euler_angles(r[0], r[1], r[2]);
+            changed = true;
+        }
+
+        let mut scale: [f32; 3] = local.scale.into();
+        if vec3_edit(ui, "Scale", &mut scale, 0.05) {
+            local.scale = scale.into();
+            changed = true;
+        }
+    });
+
+    if changed {
+        if let Some(obj) = app_state.objects.iter_mut().find(|o| o.get_unique_id() == uuid) {
+            scene_graph::to_world(&parent, &local).apply_to(obj);
+        }
+        scene_graph::carry_children(app_state, uuid, before);
+    }
+}
+
+/// Position, rotation (deg) and scale of an object as the single-object
+/// inspector shows them: relative to its parent, if it has one.
+fn components(app_state: &AppState, uuid: Uuid) -> Option<[[f32; 3]; 3]> {
+    let obj = app_state.objects.iter().find(|o| o.get_unique_id() == uuid)?;
+    let Some(parent) = scene_graph::parent_world(app_state, uuid) else {
+        return Some([obj.transform.position.into(), obj.transform.get_rotation().into(), obj.transform.scale.into()]);
+    };
+    let local = scene_graph::to_local(&parent, &Trs::of(obj));
+    let (rx, ry, rz) = local.rotation.euler_angles();
+    Some([local.position.into(), [rx.to_degrees(), ry.to_degrees(), rz.to_degrees()], local.scale.into()])
+}
+
+/// Write components from `components` back to the object.
+fn set_components(app_state: &mut AppState, uuid: Uuid, c: [[f32; 3]; 3]) {
+    let parent = scene_graph::parent_world(app_state, uuid);
+    let Some(obj) = app_state.objects.iter_mut().find(|o| o.get_unique_id() == uuid) else { return; };
+    match parent {
+        Some(parent) => {
+            let r = c[1].map(f32::to_radians);
+            let local = Trs {
+                position: c[0].into(),
+                rotation: UnitQuaternion::from_euler_angles(r[0], r[1], r[2]),
+                scale: c[2].into(),
+            };
+            scene_graph::to_world(&parent, &local).apply_to(obj);
+        }
+        None => {
+            obj.transform.set_position(c[0]);
+            obj.transform.set_rotation(c[1]);
+            obj.transform.set_scale(c[2]);
+        }
+    }
+}
+
+fn depth(app_state: &AppState, mut uuid: Uuid) -> usize {
+    let mut depth = 0;
+    while let Some(parent) = scene_graph::parent(app_state, uuid) {
+        uuid = parent;
+        depth += 1;
+    }
+    depth
+}
+
+/// Transforms of several objects edited together, each relative to its
+/// parent as in the single-object inspector. Components they
+/// disagree on show "—". Typing a number sets it on every object, `+1` or
+/// `-=1` offsets each and `*2` multiplies each; dragging offsets each.
+pub fn draw_for_objects(ui: &mut Ui, app_state: &mut AppState, uuids: &[Uuid]) {
+    // Parents first, so moving a parent doesn't undo its selected children.
+    let mut uuids: Vec<Uuid> = uuids.iter().copied()
+        .filter(|u| !visibility::is_locked(app_state, *u))
+        .collect();
+    uuids.sort_by_key(|u| depth(app_state, *u));
+    let starts: Vec<(Uuid, [[f32; 3]; 3])> = uuids.iter()
+        .filter_map(|u| components(app_state, *u).map(|c| (*u, c)))
+        .collect();
+    let Some((_, first)) = starts.first() else { return; };
+
+    // (row, axis, edit)
+    let mut edit: Option<(usize, usize, NumberEdit)> = None;
+    egui::CollapsingHeader::new("Transform").default_open(true).show(ui, |ui| {
+        ui.weak(format!("{} objects, each relative to its parent", starts.len()))
+            .on_hover_text("Type 2 to set, +1 or -=1 to offset, *2 to multiply each object");
+        egui::Grid::new("multi_transform").num_columns(2).show(ui, |ui| {
+            let rows = [("Local position", 0.05), ("Local rotation (deg)", 1.0), ("Local scale", 0.05)];
+            for (row, (label, speed)) in rows.into_iter().enumerate() {
+                ui.label(label);
+                ui.horizontal(|ui| {
+                    for (axis, prefix) in ["x ", "y ", "z "].into_iter().enumerate() {
+                        let start = first[row][axis];
+                        let mixed = starts.iter().any(|(_, c)| (c[row][axis] - start).abs() > SAME);
+                        let typed = Cell::new(None);
+                        let mut shown = start;
+                        let drag = DragValue::new(&mut shown).speed(speed).prefix(prefix)
+                            .custom_parser(|text| {
+                                let parsed = NumberEdit::parse(text)?;
+                                typed.set(Some(parsed));
+                                Some(parsed.apply(start) as f64)
+                            });
+                        let drag = if mixed { drag.custom_formatter(|_, _| "—".to_string()) } else { drag };
+                        let changed = ui.add(drag).changed();
+                        if let Some(parsed) = typed.get() {
+                            edit = Some((row, axis, parsed));
+                        } else if changed {
+                            edit = Some((row, axis, NumberEdit::Offset(shown - start)));
+                        }
+                    }
+                });
+                ui.end_row();
+            }
+        });
+    });
+
+    let Some((row, axis, edit)) = edit else { return; };
+    // A parent edited earlier in the loop carried its children, which
+    // keeps their local components as captured.
+    for (uuid, mut start) in starts {
+        start[row][axis] = edit.apply(start[row][axis]);
+        let Some(before) = app_state.objects.iter().find(|o| o.get_unique_id() == uuid).map(Trs::of) else { continue; };
+        set_components(app_state, uuid, start);
+        scene_graph::carry_children(app_state, uuid, before);
+    }
 }
 
 pub fn draw_for_light(ui: &mut Ui, app_state: &mut AppState, index: usize) {
@@ -0,0 +1,115 @@
+//! Stable identity for scene lights.
+//!
+//! The engine keeps lights as a plain `Vec<Light>` without ids or names, so
+//! the editor keeps a `LightMeta` per light in `EditorState::lights`, index
+//! for index, and writes the list into the scene file beside the engine's
+//! data (see `project::scene::to_json`).
+
+use enigma_3d::AppState;
+use enigma_3d::light::Light;
+use uuid::Uuid;
+
+use crate::editor::state::{EditorRoot, LightMeta};
+
+/// Per-frame: give lights added since the last frame an identity and drop
+/// entries for lights that are gone.
+pub fn reconcile(app_state: &mut AppState) {
+    let names: Vec<&'static str> = app_state.light.iter().map(kind_name).collect();
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    if root.editor.lights.len() != names.len() {
+        sync(&mut root.editor.lights, &names);
+    }
+}
+
+/// Resize `meta` to one entry per light; new entries get a fresh uuid and a
+/// numbered name after their kind.
+pub fn sync(meta: &mut Vec<LightMeta>, kinds: &[&str]) {
+    meta.truncate(kinds.len());
+    for kind in &kinds[meta.len()..] {
+        let n = (1..).find(|n| !meta.iter().any(|m| m.name == format!("{kind} {n}"))).unwrap_or(1);
+        meta.push(LightMeta { uuid: Uuid::new_v4(), name: format!("{kind} {n}") });
+    }
+}
+
+fn kind_name(light: &Light) -> &'static str {
+    if light.is_directional() { "Directional Light" } else { "Point Light" }
+}
+
+/// Current index of the light `uuid` in `app_state.light`.
+pub fn index_of(app_state: &AppState, uuid: Uuid) -> Option<usize> {
+    let root = app_state.get_state_data_value::<EditorRoot>("editor")?;
+    root.editor.lights.iter()
+        .position(|m| m.uuid == uuid)
+        .filter(|i| *i < app_state.light.len())
+}
+
+pub fn uuid_at(app_state: &AppState, index: usize) -> Option<Uuid> {
+    let root = app_state.get_state_data_value::<EditorRoot>("editor")?;
+    root.editor.lights.get(index).map(|m| m.uuid)
+}
+
+pub fn get(app_state: &AppState, uuid: Uuid) -> Option<&Light> {
+    index_of(app_state, uuid).and_then(|i| app_state.light.get(i))
+}
+
+pub fn get_mut(app_state: &mut AppState, uuid: Uuid) -> Option<&mut Light> {
+    let index = index_of(app_state, uuid)?;
+    app_state.light.get_mut(index)
+}
+
+pub fn rename(app_state: &mut AppState, uuid: Uuid, name: String) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    if let Some(meta) = root.editor.lights.iter_mut().find(|m| m.uuid == uuid) {
+        meta.name = name;
+        root.editor.dirty = true;
+    }
+}
+
+/// Remove the light `uuid` and its identity together.
+pub fn remove(app_state: &mut AppState, uuid: Uuid) {
+    let Some(index) = index_of(app_state, uuid) else { return; };
+    app_state.light.remove(index);
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.lights.remove(index);
+    }
+}
+
+/// Add `light` and return its new uuid.
+pub fn add(app_state: &mut AppState, light: Light, name: Option<String>) -> Option<Uuid> {
+    app_state.add_light(light, enigma_3d::light::LightEmissionType::Source);
+    reconcile(app_state);
+    let uuid = uuid_at(app_state, app_state.light.len().checked_sub(1)?)?;
+    if let Some(name) = name {
+        rename(app_state, uuid, name);
+    }
+    Some(uuid)
+}
+
+/// Replace the identities after a scene load; `meta` comes from the scene
+/// file and may be missing or stale.
+pub fn load(app_state: &mut AppState, meta: Vec<LightMeta>) {
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.lights = meta;
+    }
+    reconcile(app_state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync_pads_with_numbered_names_and_truncates() {
+        let mut meta = vec![LightMeta { uuid: Uuid::new_v4(), name: "Point Light 1".into() }];
+        let keep = meta[0].uuid;
+        sync(&mut meta, &["Point Light", "Point Light", "Directional Light"]);
+        let names: Vec<&str> = meta.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["Point Light 1", "Point Light 2", "Directional Light 1"]);
+        assert_eq!(meta[0].uuid, keep);
+        assert_ne!(meta[1].uuid, meta[2].uuid);
+
+        sync(&mut meta, &["Point Light"]);
+        assert_eq!(meta.len(), 1);
+        assert_eq!(meta[0].uuid, keep);
+    }
+}
@@ -0,0 +1,397 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use enigma_3d::AppState;
+use enigma_3d::particle::ParticleSystemConfig;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::editor::logging::{self, Source};
+use crate::editor::state::{EditorRoot, LightMeta, LiveLink, MaterialDef};
+
+/// Address of the editor's listener, passed to the game by `cargo run`.
+pub const ADDR_ENV: &str = "ENIGMA_LIVE_LINK";
+/// Game-side client, written once and then owned by the game.
+pub const RUNTIME_FILE: &str = "src/enigma_live_link.rs";
+/// Writes run on the UI thread. A game that stops reading is dropped after
+/// this long rather than freezing the editor.
+const WRITE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Editor -> game. One JSON object per line; the game's copy of these types
+/// lives in `enigma_live_link.rs` and must stay in sync.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum EditorMessage {
+    Transform { object: Uuid, position: [f32; 3], rotation: [f32; 3], scale: [f32; 3] },
+    /// Sent first on every connection: the uuids of the lights in the order
+    /// the game loaded them, which is how it tells its lights apart.
+    Lights { lights: Vec<Uuid> },
+    /// A light's values; the game adds the light if it doesn't know it.
+    Light { light: Uuid, position: [f32; 3], color: [f32; 3], intensity: f32, direction: [f32; 3], cast_shadow: bool },
+    RemoveLight { light: Uuid },
+    Material {
+        material: Uuid,
+        color: [f32; 3],
+        emissive_strength: f32,
+        roughness_strength: f32,
+        metallic_strength: f32,
+        normal_strength: f32,
+        transparent: bool,
+        transparency_strength: f32,
+        uv_tiling: [f32; 2],
+        uv_offset: [f32; 2],
+    },
+    /// New config for every placed instance of one particle system.
+    Particles { instances: Vec<Uuid>, config: ParticleSystemConfig },
+}
+
+/// Game -> editor.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum GameMessage {
+    Hello { pid: u32 },
+    State { objects: Vec<ObjectState> },
+}
+
+/// An object as the running game currently sees it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ObjectState {
+    pub uuid: Uuid,
+    pub name: String,
+    pub position: [f32; 3],
+    pub rotation: [f32; 3],
+    pub scale: [f32; 3],
+}
+
+/// Events from the listener, tagged with the connection they came from so
+/// a game that went away can't disturb the one that replaced it.
+pub enum LinkEvent {
+    Connected(u64, TcpStream),
+    Message(u64, GameMessage),
+    Disconnected(u64),
+}
+
+/// What a pushed value belongs to; the key of `LiveLink::sent`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SyncKey {
+    Object(Uuid),
+    Light(Uuid),
+    Material(Uuid),
+    Particles(Uuid),
+}
+
+/// Bind the loopback listener. Games connect one after another; each new
+/// connection replaces the previous one.
+pub fn listen() -> std::io::Result<LiveLink> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for (id, stream) in (1..).zip(listener.incoming()) {
+            let Ok(stream) = stream else { continue; };
+            let Ok(writer) = stream.try_clone() else { continue; };
+            if tx.send(LinkEvent::Connected(id, writer)).is_err() { return; }
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for line in BufReader::new(stream).lines() {
+                    let Ok(line) = line else { break; };
+                    match serde_json::from_str::<GameMessage>(&line) {
+                        Ok(message) => {
+                            if tx.send(LinkEvent::Message(id, message)).is_err() { return; }
+                        }
+                        Err(e) => logging::warn(Source::LiveLink, format!("Bad message from the game: {e}")),
+                    }
+                }
+                let _ = tx.send(LinkEvent::Disconnected(id));
+            });
+        }
+    });
+    Ok(LiveLink {
+        addr,
+        rx,
+        stream: None,
+        connection: 0,
+        game_pid: None,
+        objects: Vec::new(),
+        sent: HashMap::new(),
+        push_enabled: true,
+        run_lights: Vec::new(),
+    })
+}
+
+/// The listener address, starting the listener on first use.
+pub fn address(app_state: &mut AppState) -> Option<SocketAddr> {
+    let root = app_state.get_state_data_value_mut::<EditorRoot>("editor")?;
+    if root.editor.live_link.is_none() {
+        match listen() {
+            Ok(link) => root.editor.live_link = Some(link),
+            Err(e) => {
+                logging::error(Source::LiveLink, format!("Could not listen: {e}"));
+                return None;
+            }
+        }
+    }
+    root.editor.live_link.as_ref().map(|l| l.addr)
+}
+
+/// Remember the lights as Run saved them, for the game that connects next.
+pub fn remember_run_lights(app_state: &mut AppState) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let lights = root.editor.lights.iter().map(|m| m.uuid).collect();
+    if let Some(link) = root.editor.live_link.as_mut() {
+        link.run_lights = lights;
+    }
+}
+
+pub fn is_connected(app_state: &AppState) -> bool {
+    app_state.get_state_data_value::<EditorRoot>("editor")
+        .and_then(|r| r.editor.live_link.as_ref())
+        .map(|l| l.stream.is_some())
+        .unwrap_or(false)
+}
+
+/// Per-frame driver: take in the game's messages, then push whatever changed
+/// in the editor since the last frame.
+pub fn tick(app_state: &mut AppState) {
+    let mut sent = {
+        let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+        let playing = root.editor.play.is_some();
+        let Some(link) = root.editor.live_link.as_mut() else { return; };
+        while let Ok(event) = link.rx.try_recv() {
+            match event {
+                LinkEvent::Connected(id, mut stream) => {
+                    link.connection = id;
+                    link.stream = None;
+                    // The game loaded what was saved at Run; resend everything
+                    // so edits made during the build arrive too, and remove
+                    // the lights deleted since.
+                    link.game_pid = None;
+                    link.objects.clear();
+                    link.sent = link.run_lights.iter().map(|u| (SyncKey::Light(*u), 0)).collect();
+                    let lights = EditorMessage::Lights { lights: link.run_lights.clone() };
+                    let greeted = stream.set_write_timeout(Some(WRITE_TIMEOUT))
+                        .and_then(|()| write_messages(&mut stream, &[lights]));
+                    match greeted {
+                        Ok(()) => link.stream = Some(stream),
+                        Err(e) => logging::warn(Source::LiveLink, format!("Could not greet the game: {e}")),
+                    }
+                }
+                LinkEvent::Message(id, _) | LinkEvent::Disconnected(id) if id != link.connection => {}
+                LinkEvent::Message(_, GameMessage::Hello { pid }) => link.game_pid = Some(pid),
+                LinkEvent::Message(_, GameMessage::State { objects }) => link.objects = objects,
+                LinkEvent::Disconnected(_) => {
+                    link.stream = None;
+                    link.game_pid = None;
+                    link.objects.clear();
+                }
+            }
+        }
+        // Play mode moves things around on its own; don't mirror the simulation.
+        if link.stream.is_none() || !link.push_enabled || playing { return; }
+        std::mem::take(&mut link.sent)
+    };
+
+    let messages = {
+        let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
+        let Some(project) = root.project.as_ref() else { return; };
+        collect_changes(app_state, project, &root.editor.lights, &mut sent)
+    };
+
+    let Some(link) = app_state.get_state_data_value_mut::<EditorRoot>("editor")
+        .and_then(|r| r.editor.live_link.as_mut()) else { return; };
+    link.sent = sent;
+    if messages.is_empty() { return; }
+    let Some(stream) = link.stream.as_mut() else { return; };
+    if let Err(e) = write_messages(stream, &messages) {
+        logging::warn(Source::LiveLink, format!("Send failed, disconnected: {e}"));
+        // A timed-out write may have left half a line; close the socket so
+        // the game doesn't read on from it.
+        let _ = stream.shutdown(std::net::Shutdown::Both);
+        link.stream = None;
+        link.objects.clear();
+    }
+}
+
+fn write_messages(stream: &mut TcpStream, messages: &[EditorMessage]) -> std::io::Result<()> {
+    let mut buf = String::new();
+    for message in messages {
+        buf.push_str(&serde_json::to_string(message).map_err(std::io::Error::other)?);
+        buf.push('\n');
+    }
+    stream.write_all(buf.as_bytes())
+}
+
+/// Messages for everything whose hash differs from the one last sent.
+fn collect_changes(
+    app_state: &AppState,
+    project: &crate::editor::state::ProjectState,
+    lights: &[LightMeta],
+    sent: &mut HashMap<SyncKey, u64>,
+) -> Vec<EditorMessage> {
+    let mut out = Vec::new();
+    for obj in &app_state.objects {
+        let message = EditorMessage::Transform {
+            object: obj.get_unique_id(),
+            position: obj.transform.position.into(),
+            rotation: obj.transform.get_rotation().into(),
+            scale: obj.transform.scale.into(),
+        };
+        push_if_changed(&mut out, sent, SyncKey::Object(obj.get_unique_id()), message);
+    }
+    for (meta, light) in lights.iter().zip(&app_state.light) {
+        let message = EditorMessage::Light {
+            light: meta.uuid,
+            position: light.position,
+            color: light.color,
+            intensity: light.intensity,
+            direction: light.direction,
+            cast_shadow: light.cast_shadow,
+        };
+        push_if_changed(&mut out, sent, SyncKey::Light(meta.uuid), message);
+    }
+    out.extend(removed_lights(lights, sent));
+    for def in &project.materials {
+        push_if_changed(&mut out, sent, SyncKey::Material(def.uuid), material_message(def));
+    }
+    if let Some(scene) = project.scenes.get(project.active_scene_index) {
+        for def in &project.particle_systems {
+            let instances: Vec<Uuid> = scene.particle_instances.iter()
+                .filter(|i| i.def_uuid == def.uuid)
+                .map(|i| i.uuid)
+                .collect();
+            if instances.is_empty() { continue; }
+            let mut config = def.config.clone();
+            super::sanitize_particle_config(&mut config);
+            push_if_changed(&mut out, sent, SyncKey::Particles(def.uuid), EditorMessage::Particles { instances, config });
+        }
+    }
+    out
+}
+
+/// Removals for the lights sent before that are no longer in `lights`.
+fn removed_lights(lights: &[LightMeta], sent: &mut HashMap<SyncKey, u64>) -> Vec<EditorMessage> {
+    let removed: Vec<Uuid> = sent.keys()
+        .filter_map(|k| match k {
+            SyncKey::Light(uuid) if !lights.iter().any(|m| m.uuid == *uuid) => Some(*uuid),
+            _ => None,
+        })
+        .collect();
+    removed.into_iter()
+        .map(|light| {
+            sent.remove(&SyncKey::Light(light));
+            EditorMessage::RemoveLight { light }
+        })
+        .collect()
+}
+
+fn material_message(def: &MaterialDef) -> EditorMessage {
+    EditorMessage::Material {
+        material: def.uuid,
+        color: def.color,
+        emissive_strength: def.emissive_strength,
+        roughness_strength: def.roughness_strength,
+        metallic_strength: def.metallic_strength,
+        normal_strength: def.normal_strength,
+        transparent: def.transparent,
+        transparency_strength: def.transparency_strength,
+        uv_tiling: def.uv_tiling,
+        uv_offset: def.uv_offset,
+    }
+}
+
+fn push_if_changed(out: &mut Vec<EditorMessage>, sent: &mut HashMap<SyncKey, u64>, key: SyncKey, message: EditorMessage) {
+    // Floats aren't Hash; the encoded message is what goes over the wire anyway.
+    let Ok(json) = serde_json::to_string(&message) else { return; };
+    let mut h = DefaultHasher::new();
+    json.hash(&mut h);
+    let hash = h.finish();
+    if sent.get(&key) == Some(&hash) { return; }
+    sent.insert(key, hash);
+    out.push(message);
+}
+
+/// Write the game-side client if the project doesn't have one yet.
+pub fn ensure_runtime(root: &Path) -> std::io::Result<()> {
+    let path = root.join(RUNTIME_FILE);
+    if path.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, crate::resources::LIVE_LINK_RS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn messages_use_tagged_json() {
+        let object = Uuid::new_v4();
+        let message = EditorMessage::Transform { object, position: [1.0, 2.0, 3.0], rotation: [0.0; 3], scale: [1.0; 3] };
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(json.starts_with(r#"{"type":"Transform","object":""#));
+        let decoded: EditorMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+
+        let line = format!(r#"{{"type":"State","objects":[{{"uuid":"{object}","name":"cube","position":[0,1,0],"rotation":[0,0,0],"scale":[1,1,1]}}]}}"#);
+        let GameMessage::State { objects } = serde_json::from_str(&line).unwrap() else { panic!("expected state") };
+        assert_eq!(objects[0].uuid, object);
+        assert_eq!(objects[0].position, [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn push_if_changed_skips_unchanged_values() {
+        let mut sent = HashMap::new();
+        let mut out = Vec::new();
+        let def = MaterialDef::default_pbr("m".into());
+        let key = SyncKey::Material(def.uuid);
+        push_if_changed(&mut out, &mut sent, key, material_message(&def));
+        push_if_changed(&mut out, &mut sent, key, material_message(&def));
+        assert_eq!(out.len(), 1);
+        let mut changed = def.clone();
+        changed.color = [0.2, 0.4, 0.6];
+        push_if_changed(&mut out, &mut sent, key, material_message(&changed));
+        assert_eq!(out.len(), 2);
+    }
+
+    #[test]
+    fn deleted_lights_are_removed_once() {
+        let kept = LightMeta { uuid: Uuid::new_v4(), name: "Point Light 1".into() };
+        let gone = Uuid::new_v4();
+        let mut sent = HashMap::from([(SyncKey::Light(kept.uuid), 1), (SyncKey::Light(gone), 0)]);
+        let out = removed_lights(std::slice::from_ref(&kept), &mut sent);
+        assert!(matches!(out.as_slice(), [EditorMessage::RemoveLight { light }] if *light == gone));
+        assert!(removed_lights(std::slice::from_ref(&kept), &mut sent).is_empty());
+        assert!(sent.contains_key(&SyncKey::Light(kept.uuid)));
+    }
+
+    #[test]
+    fn listener_reports_connection_and_messages() {
+        let link = listen().unwrap();
+        let mut game = TcpStream::connect(link.addr).unwrap();
+        game.write_all(b"{\"type\":\"Hello\",\"pid\":42}\n").unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut connected = false;
+        let mut hello = None;
+        while hello.is_none() && Instant::now() < deadline {
+            match link.rx.recv_timeout(Duration::from_millis(100)) {
+                Ok(LinkEvent::Connected(..)) => connected = true,
+                Ok(LinkEvent::Message(_, m)) => hello = Some(m),
+                _ => {}
+            }
+        }
+        assert!(connected);
+        assert_eq!(hello, Some(GameMessage::Hello { pid: 42 }));
+    }
+}
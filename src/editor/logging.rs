@@ -0,0 +1,237 @@
+//! The editor's log.
+//!
+//! Anything worth telling the user goes through `error`, `warn` or `info`
+//! with a source tag instead of `eprintln!`. Entries are queued globally, so
+//! background threads and code without the `AppState` can log too, and
+//! `drain` moves them into the Console panel once per frame. New errors
+//! also pop up as toasts. Everything is still echoed to stderr.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use enigma_3d::AppState;
+
+use crate::editor::state::EditorRoot;
+
+/// Entries the console keeps; the oldest go first.
+const MAX_ENTRIES: usize = 1000;
+/// Toasts on screen at once.
+const MAX_TOASTS: usize = 4;
+pub const TOAST_SECS: f32 = 6.0;
+
+static PENDING: Mutex<Vec<LogEntry>> = Mutex::new(Vec::new());
+static START: OnceLock<Instant> = OnceLock::new();
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Level {
+    Info,
+    Warning,
+    Error,
+}
+
+/// What the entry is about; the console filters by it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Source {
+    Project,
+    Scene,
+    Import,
+    Material,
+    Terrain,
+    Particles,
+    Save,
+    Build,
+    Play,
+    LiveLink,
+    Prefs,
+}
+
+impl Source {
+    pub const ALL: [Source; 11] = [
+        Source::Project, Source::Scene, Source::Import, Source::Material, Source::Terrain,
+        Source::Particles, Source::Save, Source::Build, Source::Play, Source::LiveLink, Source::Prefs,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Source::Project => "project",
+            Source::Scene => "scene",
+            Source::Import => "import",
+            Source::Material => "material",
+            Source::Terrain => "terrain",
+            Source::Particles => "particles",
+            Source::Save => "save",
+            Source::Build => "build",
+            Source::Play => "play",
+            Source::LiveLink => "live link",
+            Source::Prefs => "prefs",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogEntry {
+    pub level: Level,
+    pub source: Source,
+    pub message: String,
+    /// Seconds since the editor started.
+    pub at: f32,
+    /// Times this entry was logged in a row.
+    pub count: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsoleFilter {
+    pub info: bool,
+    pub warnings: bool,
+    pub errors: bool,
+    /// None shows every source.
+    pub source: Option<Source>,
+    pub text: String,
+}
+
+impl Default for ConsoleFilter {
+    fn default() -> Self {
+        Self { info: true, warnings: true, errors: true, source: None, text: String::new() }
+    }
+}
+
+impl ConsoleFilter {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        let level = match entry.level {
+            Level::Info => self.info,
+            Level::Warning => self.warnings,
+            Level::Error => self.errors,
+        };
+        level
+            && self.source.is_none_or(|s| s == entry.source)
+            && (self.text.is_empty() || entry.message.to_lowercase().contains(&self.text.to_lowercase()))
+    }
+}
+
+#[derive(Default)]
+pub struct Console {
+    pub entries: VecDeque<LogEntry>,
+    pub filter: ConsoleFilter,
+}
+
+impl Console {
+    /// Add `entry`, folding it into the last one when it repeats. True if
+    /// it made a new row.
+    pub fn push(&mut self, entry: LogEntry) -> bool {
+        if let Some(last) = self.entries.back_mut() {
+            if (last.level, last.source, &last.message) == (entry.level, entry.source, &entry.message) {
+                last.count += entry.count;
+                last.at = entry.at;
+                return false;
+            }
+        }
+        self.entries.push_back(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        true
+    }
+
+    /// (errors, warnings, info) over everything kept.
+    pub fn counts(&self) -> (usize, usize, usize) {
+        self.entries.iter().fold((0, 0, 0), |(e, w, i), entry| match entry.level {
+            Level::Error => (e + 1, w, i),
+            Level::Warning => (e, w + 1, i),
+            Level::Info => (e, w, i + 1),
+        })
+    }
+}
+
+/// An error shown in the corner for `TOAST_SECS`.
+pub struct Toast {
+    pub source: Source,
+    pub message: String,
+    pub shown_at: Instant,
+}
+
+pub fn error(source: Source, message: impl Into<String>) {
+    record(Level::Error, source, message.into());
+}
+
+pub fn warn(source: Source, message: impl Into<String>) {
+    record(Level::Warning, source, message.into());
+}
+
+pub fn info(source: Source, message: impl Into<String>) {
+    record(Level::Info, source, message.into());
+}
+
+fn record(level: Level, source: Source, message: String) {
+    eprintln!("[{level:?}] {}: {message}", source.label());
+    let at = START.get_or_init(Instant::now).elapsed().as_secs_f32();
+    if let Ok(mut pending) = PENDING.lock() {
+        pending.push(LogEntry { level, source, message, at, count: 1 });
+    }
+}
+
+/// Per-frame: move what was logged since the last frame into the console.
+/// New errors raise a toast, unless a dialog is already up.
+pub fn drain(app_state: &mut AppState) {
+    START.get_or_init(Instant::now);
+    let entries = match PENDING.lock() {
+        Ok(mut pending) if !pending.is_empty() => std::mem::take(&mut *pending),
+        _ => return,
+    };
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let editor = &mut root.editor;
+    for entry in entries {
+        let toast = entry.level == Level::Error && editor.modal.is_none();
+        let (source, message) = (entry.source, entry.message.clone());
+        if editor.console.push(entry) && toast {
+            editor.toasts.push(Toast { source, message, shown_at: Instant::now() });
+        }
+    }
+    let overflow = editor.toasts.len().saturating_sub(MAX_TOASTS);
+    editor.toasts.drain(..overflow);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: Level, source: Source, message: &str) -> LogEntry {
+        LogEntry { level, source, message: message.into(), at: 0.0, count: 1 }
+    }
+
+    #[test]
+    fn repeats_fold_into_one_row() {
+        let mut console = Console::default();
+        assert!(console.push(entry(Level::Error, Source::Material, "texture missing")));
+        assert!(!console.push(LogEntry { at: 2.0, ..entry(Level::Error, Source::Material, "texture missing") }));
+        assert!(console.push(entry(Level::Error, Source::Terrain, "texture missing")));
+        assert!(console.push(entry(Level::Error, Source::Material, "texture missing")));
+        assert_eq!(console.entries.len(), 3);
+        assert_eq!((console.entries[0].count, console.entries[0].at), (2, 2.0));
+        assert_eq!(console.counts(), (3, 0, 0));
+    }
+
+    #[test]
+    fn oldest_entries_are_dropped() {
+        let mut console = Console::default();
+        for i in 0..MAX_ENTRIES + 5 {
+            console.push(entry(Level::Info, Source::Project, &i.to_string()));
+        }
+        assert_eq!(console.entries.len(), MAX_ENTRIES);
+        assert_eq!(console.entries[0].message, "5");
+    }
+
+    #[test]
+    fn filter_by_level_source_and_text() {
+        let mut filter = ConsoleFilter::default();
+        let save = entry(Level::Error, Source::Save, "Could not write scene.json");
+        let import = entry(Level::Warning, Source::Import, "unsupported format");
+        assert!(filter.matches(&save) && filter.matches(&import));
+        filter.warnings = false;
+        assert!(!filter.matches(&import));
+        filter.source = Some(Source::Import);
+        assert!(!filter.matches(&save));
+        filter = ConsoleFilter { text: "SCENE".into(), ..Default::default() };
+        assert!(filter.matches(&save) && !filter.matches(&import));
+    }
+}
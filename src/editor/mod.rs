@@ -3,12 +3,29 @@ pub mod actions;
 pub mod panels;
 pub mod inspector;
 pub mod gizmo;
+pub mod play;
+pub mod diagnostics;
+pub mod live_link;
+pub mod scene_graph;
+pub mod visibility;
+pub mod lights;
+pub mod picking;
+pub mod cameras;
+pub mod navigation;
+pub mod bookmarks;
+pub mod prefs;
+pub mod drag_drop;
+pub mod cursor_3d;
+pub mod commands;
+pub mod dock;
+pub mod logging;
 
 use std::collections::HashMap;
 
 use egui::Context;
 use enigma_3d::AppState;
 
+use crate::editor::logging::Source;
 use crate::editor::state::{EditorRoot, Modal, PendingDelete};
 
 pub fn draw(ctx: &Context, app_state: &mut AppState) {
@@ -16,6 +33,9 @@ pub fn draw(ctx: &Context, app_state: &mut AppState) {
     crate::editor::actions::poll_job(app_state);
     crate::project::poll_project_load(app_state);
     crate::project::poll_save_job(app_state);
+    play::tick(ctx, app_state);
+    visibility::reconcile(app_state);
+    lights::reconcile(app_state);
     reconcile_materials(app_state);
     apply_material_assignments(app_state);
     reconcile_skybox(app_state);
@@ -23,6 +43,8 @@ pub fn draw(ctx: &Context, app_state: &mut AppState) {
     reconcile_particle_preview(app_state);
     reconcile_particle_instances(app_state);
     reconcile_terrain(app_state);
+    live_link::tick(app_state);
+    logging::drain(app_state);
 
     // Keep repainting while a job is running so the spinner animates and
     // the poll picks up completion promptly.
@@ -37,57 +59,53 @@ pub fn draw(ctx: &Context, app_state: &mut AppState) {
         panels::toolbar::draw(ui, app_state);
     });
 
-    egui::SidePanel::left("hierarchy")
-        .default_width(220.0)
-        .min_width(160.0)
-        .resizable(true)
-        .show(ctx, |ui| {
-            egui::ScrollArea::vertical()
-                .auto_shrink([false, false])
-                .show(ui, |ui| panels::hierarchy::draw(ui, app_state));
-        });
-
-    egui::SidePanel::right("inspector")
-        .default_width(320.0)
-        .min_width(240.0)
-        .resizable(true)
-        .show(ctx, |ui| {
-            egui::ScrollArea::vertical()
-                .auto_shrink([false, false])
-                .show(ui, |ui| panels::inspector::draw(ui, app_state));
-        });
-
-    egui::TopBottomPanel::bottom("resource_browser")
-        .default_height(220.0)
-        .min_height(80.0)
-        .resizable(true)
-        .show(ctx, |ui| {
-            panels::resource_browser::draw(ui, app_state);
-        });
-
-    egui::CentralPanel::default()
-        .frame(egui::Frame::none())
-        .show(ctx, |ui| {
-            panels::viewport::draw(ui, app_state);
+    // Panels open where they were left last session; see `prefs`.
+    let Some(welcome) = app_state.get_state_data_value_mut::<EditorRoot>("editor").map(|r| {
+        // Saved in physical pixels, which is what the window is created with.
+        r.editor.layout.window = (ctx.screen_rect().size() * ctx.pixels_per_point()).into();
+        r.project.is_none() && r.editor.project_load.is_none()
+    }) else { return; };
+    dock::draw(ctx, app_state);
+
+    if welcome {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            panels::welcome::draw(ui, app_state);
         });
+    } else {
+        egui::CentralPanel::default()
+            .frame(egui::Frame::none())
+            .show(ctx, |ui| {
+                panels::viewport::draw(ui, app_state);
+            });
+    }
 
     process_modals(ctx, app_state);
     draw_job_overlay(ctx, app_state);
+    panels::job_history::draw(ctx, app_state);
+    panels::run_configs::draw(ctx, app_state);
+    panels::live_link::draw(ctx, app_state);
+    panels::keybindings::draw(ctx, app_state);
+    panels::preferences::draw(ctx, app_state);
+    panels::command_palette::draw(ctx, app_state);
+    panels::console::draw_toasts(ctx, app_state);
+    prefs::autosave(ctx, app_state);
 }
 
 fn draw_job_overlay(ctx: &Context, app_state: &mut AppState) {
-    let (label, elapsed, lines) = {
+    let (label, elapsed, lines, cancellable, queued) = {
         let Some(r) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
+        let queued: Vec<&str> = r.editor.job_queue.iter().map(|k| k.label()).collect();
         if let Some(j) = r.editor.job.as_ref() {
-            (j.label.clone(), j.started_at.elapsed(), j.lines.clone())
+            (j.label.clone(), j.started_at.elapsed(), j.lines.clone(), !j.cancelled, queued)
         } else if let Some(j) = r.editor.project_load.as_ref() {
-            (j.label.clone(), j.started_at.elapsed(), j.lines.clone())
+            (j.label.clone(), j.started_at.elapsed(), j.lines.clone(), false, Vec::new())
         } else if let Some(j) = r.editor.save_job.as_ref() {
-            (j.label.clone(), j.started_at.elapsed(), j.lines.clone())
+            (j.label.clone(), j.started_at.elapsed(), j.lines.clone(), false, Vec::new())
         } else {
             return;
         }
     };
+    let mut cancel = false;
     egui::Window::new("cargo")
         .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
         .collapsible(false)
@@ -99,9 +117,15 @@ fn draw_job_overlay(ctx: &Context, app_state: &mut AppState) {
                 ui.add(egui::Spinner::new().size(20.0));
                 ui.heading(format!("{label}…"));
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if cancellable && ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
                     ui.monospace(format!("{:.1}s", elapsed.as_secs_f32()));
                 });
             });
+            if !queued.is_empty() {
+                ui.weak(format!("Next: {}", queued.join(" → ")));
+            }
             ui.separator();
             egui::ScrollArea::vertical()
                 .stick_to_bottom(true)
@@ -116,6 +140,9 @@ fn draw_job_overlay(ctx: &Context, app_state: &mut AppState) {
                     }
                 });
         });
+    if cancel {
+        crate::editor::actions::cancel_job(app_state);
+    }
 }
 
 fn process_modals(ctx: &Context, app_state: &mut AppState) {
@@ -126,7 +153,11 @@ fn process_modals(ctx: &Context, app_state: &mut AppState) {
     let Some(modal) = modal else { return; };
 
     let mut close = false;
-    egui::Window::new("Dialog")
+    let title = match &modal {
+        Modal::ImportError(_) => "Import failed",
+        _ => "Dialog",
+    };
+    egui::Window::new(title)
         .collapsible(false)
         .resizable(false)
         .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
@@ -146,7 +177,7 @@ fn process_modals(ctx: &Context, app_state: &mut AppState) {
                             if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
                                 if let Some(proj) = r.project.as_mut() {
                                     if let Err(e) = crate::project::scene::new_scene(proj, draft.trim().to_string()) {
-                                        eprintln!("new scene failed: {e:?}");
+                                        logging::error(Source::Scene, format!("New scene failed: {e:?}"));
                                     } else {
                                         r.editor.dirty = true;
                                     }
@@ -157,6 +188,33 @@ fn process_modals(ctx: &Context, app_state: &mut AppState) {
                         if ui.button("Cancel").clicked() { close = true; }
                     });
                 }
+                Modal::NewScriptName(mut draft) => {
+                    ui.label("Script type name:");
+                    let response = ui.text_edit_singleline(&mut draft);
+                    response.request_focus();
+                    if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+                        r.editor.modal = Some(Modal::NewScriptName(draft.clone()));
+                    }
+                    ui.horizontal(|ui| {
+                        let create = ui.button("Create").clicked()
+                            || (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)));
+                        if create && !draft.trim().is_empty() {
+                            if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+                                if let Some(proj) = r.project.as_mut() {
+                                    match crate::project::script::create(proj, draft.trim()) {
+                                        Ok(uuid) => {
+                                            r.editor.selection.set(crate::editor::state::Selection::Script(uuid));
+                                            r.editor.dirty = true;
+                                        }
+                                        Err(e) => logging::error(Source::Project, format!("New script failed: {e:?}")),
+                                    }
+                                }
+                            }
+                            close = true;
+                        }
+                        if ui.button("Cancel").clicked() { close = true; }
+                    });
+                }
                 Modal::ConfirmDelete { label, pending } => {
                     ui.label(format!("Delete {label}?"));
                     ui.horizontal(|ui| {
@@ -206,12 +264,18 @@ fn apply_pending_delete(app_state: &mut AppState, p: PendingDelete) {
         }
         PendingDelete::SceneObject(uuid) => {
             app_state.objects.retain(|o| o.get_unique_id() != uuid);
-        }
-        PendingDelete::Light(idx) => {
-            if idx < app_state.light.len() {
-                app_state.light.remove(idx);
+            if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+                r.editor.hidden_stash.retain(|h| h.object.get_unique_id() != uuid);
+                if let Some(project) = r.project.as_mut() {
+                    project.script_components.retain(|c| c.object != uuid);
+                    // Children stay where they are, detached.
+                    project.object_parents.retain(|l| l.child != uuid && l.parent != uuid);
+                }
             }
         }
+        PendingDelete::Light(uuid) => {
+            lights::remove(app_state, uuid);
+        }
         PendingDelete::AmbientLight => {
             app_state.ambient_light = None;
         }
@@ -227,6 +291,20 @@ fn apply_pending_delete(app_state: &mut AppState, p: PendingDelete) {
             }
             app_state.particle_systems.retain(|s| s.handle != uuid);
         }
+        PendingDelete::Script(uuid) => {
+            if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+                if let Some(project) = r.project.as_mut() {
+                    if let Err(e) = crate::project::script::delete(project, uuid) {
+                        logging::error(Source::Project, format!("Deleting the script failed: {e:?}"));
+                    }
+                }
+            }
+        }
+        PendingDelete::Many(items) => {
+            for item in items {
+                apply_pending_delete(app_state, item);
+            }
+        }
         PendingDelete::ParticleInstance(uuid) => {
             if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
                 if let Some(project) = r.project.as_mut() {
@@ -240,7 +318,7 @@ fn apply_pending_delete(app_state: &mut AppState, p: PendingDelete) {
         }
     }
     if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-        r.editor.selection = crate::editor::state::Selection::None;
+        r.editor.selection.set(crate::editor::state::Selection::None);
         r.editor.dirty = true;
     }
 }
@@ -414,7 +492,7 @@ fn reconcile_particle_preview(app_state: &mut AppState) {
 
     let selected_def: Option<uuid::Uuid> = {
         let Some(r) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
-        match &r.editor.selection {
+        match r.editor.selection.primary() {
             Selection::Particle(u) => Some(*u),
             _ => None,
         }
@@ -426,7 +504,7 @@ fn reconcile_particle_preview(app_state: &mut AppState) {
         let Some(project) = r.project.as_ref() else { return; };
         let sprite_default = r.editor.internal_particle_sprite_material;
         let ribbon_default = r.editor.internal_particle_ribbon_material;
-        match &r.editor.selection {
+        match r.editor.selection.primary() {
             Selection::Particle(u) => {
                 let def = project.particle_systems.iter().find(|p| p.uuid == *u);
                 match def {
@@ -484,9 +562,11 @@ fn reconcile_particle_preview(app_state: &mut AppState) {
                 }
             }
             Err(e) => {
-                eprintln!("particle config invalid: {e:?}");
+                logging::error(Source::Particles, format!("Invalid particle system: {e:?}"));
+                // Marked as applied so the same config isn't retried (and
+                // reported) every frame; the next edit tries again.
                 if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-                    r.editor.previewed_particle = None;
+                    r.editor.previewed_particle = Some((uuid, hash));
                 }
             }
         }
@@ -653,7 +733,10 @@ fn reconcile_particle_instances(app_state: &mut AppState) {
                 let _ = m;
             }
             Err(e) => {
-                eprintln!("particle instance build failed: {e:?}");
+                logging::error(Source::Particles, format!("Could not build a particle instance: {e:?}"));
+                if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+                    r.editor.applied_particle_instances.insert(inst_uuid, hash);
+                }
             }
         }
     }
@@ -711,10 +794,14 @@ fn reconcile_terrain(app_state: &mut AppState) {
         config.uv_scale = def.uv_scale;
         config.custom_noise = None;
 
-        // tile_count must divide resolution; clamp gracefully.
+        // tile_count must divide resolution; keep the last good terrain
+        // until the def is fixed.
         if config.tile_count == 0 || config.resolution % config.tile_count != 0 {
-            eprintln!("terrain: resolution {} must be divisible by tile_count {}",
-                config.resolution, config.tile_count);
+            logging::warn(Source::Terrain, format!("Resolution {} must be divisible by the tile count {}",
+                config.resolution, config.tile_count));
+            if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+                r.editor.applied_terrain = new_hash;
+            }
             return;
         }
 
@@ -766,7 +853,7 @@ fn reconcile_skybox(app_state: &mut AppState) {
             match crate::project::resource::bytes(p, uuid) {
                 Ok(b) => b,
                 Err(e) => {
-                    eprintln!("skybox texture load failed: {e:?}");
+                    logging::error(Source::Scene, format!("Could not load the skybox texture: {e:?}"));
                     if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
                         r.editor.applied_skybox = desired;
                     }
@@ -822,9 +909,18 @@ fn reconcile_materials(app_state: &mut AppState) {
         Some(r) => std::mem::take(&mut r.editor.material_cache),
         None => return,
     };
-    let _ = crate::project::material::reconcile(&project, app_state, &mut cache);
+    // Unrealized materials retry every frame; report each only when it
+    // starts failing.
+    let failures = crate::project::material::reconcile(&project, app_state, &mut cache).unwrap_or_default();
     if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
         r.editor.material_cache = cache;
+        let failing: std::collections::HashSet<uuid::Uuid> = failures.iter().map(|(u, _)| *u).collect();
+        for (uuid, e) in &failures {
+            if r.editor.material_failures.contains(uuid) { continue; }
+            let name = project.materials.iter().find(|m| m.uuid == *uuid).map_or("?", |m| m.name.as_str());
+            logging::error(Source::Material, format!("Could not realize \"{name}\": {e:?}"));
+        }
+        r.editor.material_failures = failing;
     }
 }
 
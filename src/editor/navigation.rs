@@ -0,0 +1,418 @@
+//! Editor camera navigation: orbit, view cube presets, orthographic views
+//! and framing by bounding box.
+//!
+//! The engine camera is perspective only, so the orthographic projection is
+//! approximated: the field of view is narrowed to `ORTHO_FOV` and the camera
+//! backs off until the view covers the same height at the focus point. The
+//! picking and gizmo math keep working unchanged.
+
+use egui::{Color32, Pos2, Rect, Stroke, Ui, Vec2};
+use enigma_3d::AppState;
+use enigma_3d::camera::Camera;
+use enigma_3d::object::Object;
+use nalgebra::Vector3;
+
+use crate::editor::{bookmarks, cameras, cursor_3d, gizmo, lights};
+use crate::editor::state::{EditorRoot, Navigation, Projection, Selection, ViewPreset};
+
+/// Field of view standing in for an orthographic projection, in radians.
+pub const ORTHO_FOV: f32 = 0.02;
+const ORBIT_SENSITIVITY: f32 = 0.008; // radians per pixel
+/// Pitch stays this far from straight up or down so the view keeps a heading.
+const PITCH_LIMIT: f32 = std::f32::consts::FRAC_PI_2 - 0.001;
+/// Extra room around a framed bounding box.
+const FRAME_MARGIN: f32 = 1.15;
+/// Half size of a selected light, emitter or camera when framing it.
+const POINT_EXTENT: f32 = 0.5;
+const CUBE_SIZE: f32 = 28.0; // half width of the view cube in pixels
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: Vector3<f32>,
+    pub max: Vector3<f32>,
+}
+
+impl Aabb {
+    pub fn around(center: Vector3<f32>, extent: f32) -> Self {
+        let e = Vector3::repeat(extent);
+        Aabb { min: center - e, max: center + e }
+    }
+
+    pub fn from_points(points: impl IntoIterator<Item = Vector3<f32>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Aabb { min: first, max: first }, |b, p| Aabb { min: b.min.inf(&p), max: b.max.sup(&p) }))
+    }
+
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb { min: self.min.inf(&other.min), max: self.max.sup(&other.max) }
+    }
+
+    pub fn center(&self) -> Vector3<f32> {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Radius of the sphere around the box.
+    pub fn radius(&self) -> f32 {
+        (self.max - self.min).norm() / 2.0
+    }
+}
+
+/// Camera forward for an Euler pitch and yaw, as `calculate_direction_vector`.
+pub fn direction(pitch: f32, yaw: f32) -> Vector3<f32> {
+    Vector3::new(-yaw.sin() * pitch.cos(), pitch.sin(), -yaw.cos() * pitch.cos())
+}
+
+pub fn preset_angles(preset: ViewPreset) -> (f32, f32) {
+    use std::f32::consts::{FRAC_PI_2, PI};
+    match preset {
+        ViewPreset::Top => (-PITCH_LIMIT, 0.0),
+        ViewPreset::Bottom => (PITCH_LIMIT, 0.0),
+        ViewPreset::Front => (0.0, 0.0),
+        ViewPreset::Back => (0.0, PI),
+        ViewPreset::Right => (0.0, FRAC_PI_2),
+        ViewPreset::Left => (0.0, -FRAC_PI_2),
+    }
+}
+
+/// Height of the view at `distance` for a vertical field of view.
+pub fn view_height(distance: f32, fov: f32) -> f32 {
+    2.0 * distance * (fov / 2.0).tan()
+}
+
+pub fn distance_for_height(height: f32, fov: f32) -> f32 {
+    height / (2.0 * (fov / 2.0).tan())
+}
+
+/// How far back a camera must be for a sphere of `radius` to fit.
+pub fn frame_distance(radius: f32, fov: f32, aspect: f32) -> f32 {
+    let half_v = fov / 2.0;
+    let half_h = (half_v.tan() * aspect.max(1e-3)).atan();
+    radius * FRAME_MARGIN / half_v.min(half_h).sin()
+}
+
+/// Put `cam` `distance` back from `focus`, looking at it with `pitch`/`yaw`.
+fn look_at(cam: &mut Camera, focus: Vector3<f32>, pitch: f32, yaw: f32, distance: f32) {
+    cam.transform.position = focus - direction(pitch, yaw) * distance;
+    cam.transform.rotation = Vector3::new(pitch, yaw, 0.0);
+    cam.update_matrices();
+}
+
+fn navigation(app_state: &AppState) -> Navigation {
+    app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| r.editor.navigation.clone())
+        .unwrap_or_default()
+}
+
+fn set_navigation(app_state: &mut AppState, f: impl FnOnce(&mut Navigation)) {
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        f(&mut root.editor.navigation);
+    }
+}
+
+/// Point the camera is looking at: `focus_distance` straight ahead.
+fn focus_point(cam: &Camera, nav: &Navigation) -> Vector3<f32> {
+    Vector3::from(cam.get_position()) + Vector3::from(cam.calculate_direction_vector()) * nav.focus_distance
+}
+
+/// Keep the far plane beyond the focus point; the orthographic stand-in
+/// sits a long way back.
+fn fit_far(cam: &mut Camera, nav: &Navigation) {
+    if let Some((_, far)) = nav.perspective {
+        cam.far = nav.focus_distance + far;
+    }
+}
+
+/// Alt+LMB: swing the camera around the selection, or its focus point.
+pub fn orbit(app_state: &mut AppState, delta: Vec2) {
+    let nav = navigation(app_state);
+    let pivot = gizmo::selection_pivot(app_state);
+    let Some(cam) = app_state.camera.as_mut() else { return; };
+    let pivot = pivot.unwrap_or_else(|| focus_point(cam, &nav));
+    let distance = (pivot - Vector3::from(cam.get_position())).norm().max(0.01);
+    let pitch = (cam.transform.rotation.x - delta.y * ORBIT_SENSITIVITY).clamp(-PITCH_LIMIT, PITCH_LIMIT);
+    let yaw = cam.transform.rotation.y - delta.x * ORBIT_SENSITIVITY;
+    look_at(cam, pivot, pitch, yaw, distance);
+    set_navigation(app_state, |n| {
+        n.focus_distance = distance;
+        n.preset = None;
+    });
+}
+
+/// Look along a view cube axis, keeping the focus point.
+pub fn set_preset(app_state: &mut AppState, preset: ViewPreset) {
+    let nav = navigation(app_state);
+    let pivot = gizmo::selection_pivot(app_state);
+    let Some(cam) = app_state.camera.as_mut() else { return; };
+    let focus = pivot.unwrap_or_else(|| focus_point(cam, &nav));
+    let distance = (focus - Vector3::from(cam.get_position())).norm().max(0.01);
+    let (pitch, yaw) = preset_angles(preset);
+    look_at(cam, focus, pitch, yaw, distance);
+    set_navigation(app_state, |n| {
+        n.focus_distance = distance;
+        n.preset = Some(preset);
+    });
+}
+
+/// Switch projection keeping the view's height at the focus point.
+pub fn set_projection(app_state: &mut AppState, projection: Projection) {
+    let mut nav = navigation(app_state);
+    if nav.projection == projection { return; }
+    let Some(cam) = app_state.camera.as_mut() else { return; };
+    let focus = focus_point(cam, &nav);
+    let height = view_height(nav.focus_distance, cam.fov);
+    let forward = Vector3::from(cam.calculate_direction_vector());
+    match projection {
+        Projection::Orthographic => {
+            nav.perspective = Some((cam.fov, cam.far));
+            cam.fov = ORTHO_FOV;
+        }
+        Projection::Perspective => {
+            let (fov, far) = nav.perspective.take().unwrap_or((std::f32::consts::FRAC_PI_3, 100.0));
+            cam.fov = fov;
+            cam.far = far;
+        }
+    }
+    nav.focus_distance = distance_for_height(height, cam.fov);
+    nav.projection = projection;
+    cam.transform.position = focus - forward * nav.focus_distance;
+    fit_far(cam, &nav);
+    cam.update_matrices();
+    set_navigation(app_state, |n| *n = nav);
+}
+
+pub fn toggle_projection(app_state: &mut AppState) {
+    let next = match navigation(app_state).projection {
+        Projection::Perspective => Projection::Orthographic,
+        Projection::Orthographic => Projection::Perspective,
+    };
+    set_projection(app_state, next);
+}
+
+/// Mouse wheel: move toward the focus point. In orthographic the step grows
+/// with distance so zooming feels the same at any scale.
+pub fn dolly(app_state: &mut AppState, amount: f32) {
+    let mut nav = navigation(app_state);
+    let Some(cam) = app_state.camera.as_mut() else { return; };
+    let step = match nav.projection {
+        Projection::Perspective => amount,
+        Projection::Orthographic => amount * nav.focus_distance * 0.1,
+    };
+    let forward = Vector3::from(cam.calculate_direction_vector());
+    cam.transform.position += forward * step;
+    nav.focus_distance = (nav.focus_distance - step).max(0.1);
+    fit_far(cam, &nav);
+    cam.update_matrices();
+    set_navigation(app_state, |n| *n = nav);
+}
+
+/// World bounds of an object's meshes, or None for an object without
+/// vertices.
+pub fn object_bounds(object: &Object) -> Option<Aabb> {
+    Aabb::from_points(gizmo::snapping::world_vertices(object))
+}
+
+/// Bounds of everything selected: meshes by their vertices, lights,
+/// emitters and the camera as small boxes around their position.
+pub fn selection_bounds(app_state: &AppState) -> Option<Aabb> {
+    let root = app_state.get_state_data_value::<EditorRoot>("editor")?;
+    root.editor.selection.items().iter()
+        .filter_map(|item| match item {
+            Selection::SceneObject(uuid) => {
+                let o = app_state.objects.iter().find(|o| o.get_unique_id() == *uuid)?;
+                object_bounds(o).or_else(|| Some(Aabb::around(o.transform.position, POINT_EXTENT)))
+            }
+            Selection::Light(uuid) => lights::get(app_state, *uuid)
+                .map(|l| Aabb::around(Vector3::from(l.position), POINT_EXTENT)),
+            Selection::Camera => cameras::game_camera(app_state)
+                .map(|c| Aabb::around(c.transform.position, POINT_EXTENT)),
+            Selection::ParticleInstance(uuid) => root.project.as_ref()
+                .and_then(|p| p.scenes.get(p.active_scene_index))
+                .and_then(|s| s.particle_instances.iter().find(|i| i.uuid == *uuid))
+                .map(|i| Aabb::around(Vector3::from(i.position), POINT_EXTENT)),
+            _ => None,
+        })
+        .reduce(|a, b| a.union(&b))
+}
+
+/// F: fit the selection's bounding box in the view, keeping the direction.
+pub fn frame_selection(app_state: &mut AppState) {
+    let Some(bounds) = selection_bounds(app_state) else { return; };
+    let mut nav = navigation(app_state);
+    let Some(cam) = app_state.camera.as_mut() else { return; };
+    let aspect = if cam.height > 0.0 { cam.width / cam.height } else { 16.0 / 9.0 };
+    let radius = bounds.radius().max(0.05);
+    let distance = frame_distance(radius, cam.fov, aspect);
+    let (pitch, yaw) = (cam.transform.rotation.x, cam.transform.rotation.y);
+    look_at(cam, bounds.center(), pitch, yaw, distance);
+    nav.focus_distance = distance;
+    fit_far(cam, &nav);
+    cam.update_matrices();
+    set_navigation(app_state, |n| *n = nav);
+}
+
+pub struct CubeFace {
+    pub preset: ViewPreset,
+    /// Outline in pixels relative to the cube's center.
+    pub corners: [Vec2; 4],
+    /// Larger is farther from the viewer.
+    pub depth: f32,
+}
+
+/// The faces of the view cube facing a camera looking along `forward`,
+/// farthest first.
+pub fn cube_faces(forward: Vector3<f32>, size: f32) -> Vec<CubeFace> {
+    let forward = forward.normalize();
+    let (right, up) = gizmo::math::screen_basis(forward);
+    let faces = [
+        (ViewPreset::Top, Vector3::y()),
+        (ViewPreset::Bottom, -Vector3::y()),
+        (ViewPreset::Front, Vector3::z()),
+        (ViewPreset::Back, -Vector3::z()),
+        (ViewPreset::Right, Vector3::x()),
+        (ViewPreset::Left, -Vector3::x()),
+    ];
+    let project = |p: Vector3<f32>| Vec2::new(p.dot(&right), -p.dot(&up)) * size;
+    let mut out: Vec<CubeFace> = faces.iter()
+        .filter(|(_, n)| n.dot(&forward) < -1e-3)
+        .map(|(preset, n)| {
+            // Two axes spanning the face.
+            let a = if n.x.abs() > 0.5 { Vector3::y() } else { Vector3::x() };
+            let b = n.cross(&a);
+            let corners = [n + a + b, n + a - b, n - a - b, n - a + b].map(project);
+            CubeFace { preset: *preset, corners, depth: n.dot(&forward) }
+        })
+        .collect();
+    out.sort_by(|x, y| y.depth.total_cmp(&x.depth));
+    out
+}
+
+fn inside(poly: &[Vec2; 4], p: Vec2) -> bool {
+    let mut sign = 0.0f32;
+    for i in 0..4 {
+        let (a, b) = (poly[i], poly[(i + 1) % 4]);
+        let cross = (b - a).x * (p - a).y - (b - a).y * (p - a).x;
+        if cross.abs() < 1e-6 { continue; }
+        if sign == 0.0 {
+            sign = cross.signum();
+        } else if cross.signum() != sign {
+            return false;
+        }
+    }
+    true
+}
+
+/// View cube, projection toggle and bookmarks in the viewport's top-right
+/// corner.
+pub fn draw_view_cube(ctx: &egui::Context, rect: Rect, app_state: &mut AppState) {
+    let Some(forward) = app_state.camera.as_ref().map(|c| Vector3::from(c.calculate_direction_vector())) else { return; };
+    let nav = navigation(app_state);
+    let faces = cube_faces(forward, CUBE_SIZE * 0.55);
+    let mut clicked: Option<ViewPreset> = None;
+    let mut toggle = false;
+
+    egui::Area::new("view_cube")
+        .order(egui::Order::Foreground)
+        .fixed_pos(Pos2::new(rect.max.x - CUBE_SIZE * 2.0 - 16.0, rect.min.y + 8.0))
+        .show(ctx, |ui: &mut Ui| {
+            let (response, painter) = ui.allocate_painter(Vec2::splat(CUBE_SIZE * 2.0), egui::Sense::click());
+            let center = response.rect.center();
+            let hover = response.hover_pos().map(|p| p - center);
+            // Nearest face under the pointer; faces are listed farthest first.
+            let hovered = hover.and_then(|h| faces.iter().rev().find(|f| inside(&f.corners, h)).map(|f| f.preset));
+            for face in &faces {
+                let points: Vec<Pos2> = face.corners.iter().map(|c| center + *c).collect();
+                let active = nav.preset == Some(face.preset);
+                let fill = if hovered == Some(face.preset) {
+                    Color32::from_rgb(90, 160, 255)
+                } else if active {
+                    Color32::from_rgb(70, 110, 170)
+                } else {
+                    Color32::from_gray(60)
+                };
+                painter.add(egui::Shape::convex_polygon(points.clone(), fill, Stroke::new(1.0, Color32::from_gray(20))));
+                let mid = points.iter().fold(Vec2::ZERO, |acc, p| acc + p.to_vec2()) / 4.0;
+                painter.text(mid.to_pos2(), egui::Align2::CENTER_CENTER, &face.preset.label()[..1],
+                    egui::FontId::proportional(11.0), Color32::WHITE);
+            }
+            if response.clicked() {
+                clicked = hovered;
+            }
+            let label = match nav.projection {
+                Projection::Perspective => "Persp",
+                Projection::Orthographic => "Ortho",
+            };
+            toggle = ui.add_sized([CUBE_SIZE * 2.0, 18.0], egui::Button::new(label).small())
+                .on_hover_text("Toggle perspective / orthographic")
+                .clicked();
+            if let Some(preset) = nav.preset {
+                ui.vertical_centered(|ui| ui.small(preset.label()));
+            }
+            bookmarks::menu(ui, app_state);
+            cursor_3d::menu(ui, app_state);
+        });
+
+    if let Some(preset) = clicked {
+        set_preset(app_state, preset);
+    }
+    if toggle {
+        toggle_projection(app_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_look_along_axes() {
+        let f = |p| { let (a, b) = preset_angles(p); direction(a, b) };
+        assert!(f(ViewPreset::Top).y < -0.999);
+        assert!(f(ViewPreset::Front).z < -0.999);
+        assert!(f(ViewPreset::Right).x < -0.999);
+        assert!(f(ViewPreset::Left).x > 0.999);
+    }
+
+    #[test]
+    fn projection_switch_keeps_view_height() {
+        let height = view_height(10.0, 1.0);
+        let far = distance_for_height(height, ORTHO_FOV);
+        assert!(far > 100.0);
+        assert!((view_height(far, ORTHO_FOV) - height).abs() < 1e-3);
+    }
+
+    #[test]
+    fn frame_distance_scales_with_radius() {
+        let near = frame_distance(0.01, 1.0, 1.5);
+        let far = frame_distance(100.0, 1.0, 1.5);
+        assert!((far / near - 10_000.0).abs() < 1.0);
+        // A narrow window is limited by its width.
+        assert!(frame_distance(1.0, 1.0, 0.5) > frame_distance(1.0, 1.0, 2.0));
+    }
+
+    #[test]
+    fn aabb_union_and_radius() {
+        let a = Aabb::from_points([Vector3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0)]).unwrap();
+        let b = Aabb::around(Vector3::new(0.0, 2.0, 0.0), 0.0);
+        let u = a.union(&b);
+        assert_eq!(u.max, Vector3::new(2.0, 2.0, 0.0));
+        assert_eq!(u.center(), Vector3::new(1.0, 1.0, 0.0));
+        assert!((u.radius() - 2f32.sqrt()).abs() < 1e-5);
+        assert!(Aabb::from_points(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn cube_shows_three_faces_from_a_corner() {
+        let forward = Vector3::new(-1.0, -1.0, -1.0);
+        let faces = cube_faces(forward, 10.0);
+        let presets: Vec<ViewPreset> = faces.iter().map(|f| f.preset).collect();
+        assert_eq!(presets.len(), 3);
+        for p in [ViewPreset::Top, ViewPreset::Front, ViewPreset::Right] {
+            assert!(presets.contains(&p));
+        }
+        let front_on = cube_faces(Vector3::new(0.0, 0.0, -1.0), 10.0);
+        assert_eq!(front_on.len(), 1);
+        assert!(inside(&front_on[0].corners, Vec2::ZERO));
+        assert!(!inside(&front_on[0].corners, Vec2::new(50.0, 0.0)));
+    }
+}
@@ -0,0 +1,79 @@
+use egui::{Align2, Context, Key};
+use enigma_3d::AppState;
+
+use crate::editor::commands::{self, PaletteItem};
+use crate::editor::state::EditorRoot;
+
+const MAX_ROWS: usize = 12;
+
+/// Ctrl+Shift+P: type to fuzzy-search every available command and scene,
+/// arrows pick, Enter runs, Escape or a click elsewhere closes.
+pub fn draw(ctx: &Context, app_state: &mut AppState) {
+    let Some((mut query, mut selected)) = app_state.get_state_data_value::<EditorRoot>("editor")
+        .and_then(|r| r.editor.palette.as_ref().map(|p| (p.query.clone(), p.selected))) else { return; };
+
+    let mut matches = commands::rank(&commands::palette_items(app_state), &query);
+    matches.truncate(MAX_ROWS);
+    let (up, down, enter, escape) = ctx.input(|i| (
+        i.key_pressed(Key::ArrowUp),
+        i.key_pressed(Key::ArrowDown),
+        i.key_pressed(Key::Enter),
+        i.key_pressed(Key::Escape),
+    ));
+    if down { selected += 1; }
+    if up { selected = selected.saturating_sub(1); }
+    selected = selected.min(matches.len().saturating_sub(1));
+
+    let mut chosen: Option<PaletteItem> = None;
+    if enter {
+        chosen = matches.get(selected).map(|(item, _)| *item);
+    }
+    let area = egui::Area::new("command_palette")
+        .order(egui::Order::Foreground)
+        .anchor(Align2::CENTER_TOP, [0.0, 48.0])
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.set_width(460.0);
+                let edit = ui.add(egui::TextEdit::singleline(&mut query)
+                    .hint_text("Type a command…")
+                    .desired_width(f32::INFINITY));
+                edit.request_focus();
+                if edit.changed() {
+                    selected = 0;
+                }
+                ui.separator();
+                if matches.is_empty() {
+                    ui.weak("No matching commands");
+                }
+                for (i, (item, label)) in matches.iter().enumerate() {
+                    let shortcut = match item {
+                        PaletteItem::Command(id) => commands::shortcut_text(app_state, *id),
+                        PaletteItem::Scene(_) => String::new(),
+                    };
+                    ui.horizontal(|ui| {
+                        if ui.selectable_label(i == selected, label).clicked() {
+                            chosen = Some(*item);
+                        }
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.weak(shortcut);
+                        });
+                    });
+                }
+            });
+        });
+
+    let close = escape || chosen.is_some() || area.response.clicked_elsewhere();
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.palette = match root.editor.palette.take() {
+            Some(mut palette) if !close => {
+                palette.query = query;
+                palette.selected = selected;
+                Some(palette)
+            }
+            _ => None,
+        };
+    }
+    if let Some(item) = chosen {
+        commands::run_item(app_state, item);
+    }
+}
@@ -0,0 +1,112 @@
+use egui::{Color32, Context, Ui};
+use enigma_3d::AppState;
+
+use crate::editor::dock::Tab;
+use crate::editor::logging::{self, Level, Source};
+use crate::editor::state::EditorRoot;
+
+fn style(level: Level) -> (&'static str, Color32) {
+    match level {
+        Level::Error => ("⛔", Color32::from_rgb(230, 90, 80)),
+        Level::Warning => ("⚠", Color32::from_rgb(230, 180, 60)),
+        Level::Info => ("ℹ", Color32::from_rgb(120, 160, 220)),
+    }
+}
+
+pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let console = &mut root.editor.console;
+    let (errors, warnings, info) = console.counts();
+
+    let mut clear = false;
+    ui.horizontal(|ui| {
+        let filter = &mut console.filter;
+        ui.toggle_value(&mut filter.errors, format!("Errors ({errors})"));
+        ui.toggle_value(&mut filter.warnings, format!("Warnings ({warnings})"));
+        ui.toggle_value(&mut filter.info, format!("Info ({info})"));
+        egui::ComboBox::from_id_source("console_source")
+            .selected_text(filter.source.map_or("All sources", Source::label))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut filter.source, None, "All sources");
+                for source in Source::ALL {
+                    ui.selectable_value(&mut filter.source, Some(source), source.label());
+                }
+            });
+        ui.add(egui::TextEdit::singleline(&mut filter.text).hint_text("Filter").desired_width(160.0));
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            clear = ui.small_button("Clear").clicked();
+        });
+    });
+    ui.separator();
+
+    let filter = &console.filter;
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            if console.entries.is_empty() {
+                ui.weak("Nothing logged yet.");
+            }
+            for entry in console.entries.iter().filter(|e| filter.matches(e)) {
+                let (icon, color) = style(entry.level);
+                ui.horizontal_wrapped(|ui| {
+                    ui.monospace(format!("{:>8.2}", entry.at)).on_hover_text("Seconds since the editor started");
+                    ui.colored_label(color, icon);
+                    ui.label(egui::RichText::new(format!("[{}]", entry.source.label())).weak());
+                    ui.label(&entry.message);
+                    if entry.count > 1 {
+                        ui.label(egui::RichText::new(format!("×{}", entry.count)).weak());
+                    }
+                });
+            }
+        });
+
+    if clear {
+        console.entries.clear();
+    }
+}
+
+/// Errors in the bottom-right corner. They fade out on their own; clicking
+/// one opens the console.
+pub fn draw_toasts(ctx: &Context, app_state: &mut AppState) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let editor = &mut root.editor;
+    editor.toasts.retain(|t| t.shown_at.elapsed().as_secs_f32() < logging::TOAST_SECS);
+    if editor.toasts.is_empty() { return; }
+
+    let mut dismiss: Option<usize> = None;
+    let mut open_console = false;
+    egui::Area::new("toasts")
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -12.0))
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            for (i, toast) in editor.toasts.iter().enumerate() {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_max_width(320.0);
+                    ui.horizontal(|ui| {
+                        let (icon, color) = style(Level::Error);
+                        ui.colored_label(color, icon);
+                        ui.strong(toast.source.label());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("×").clicked() {
+                                dismiss = Some(i);
+                            }
+                        });
+                    });
+                    let message = ui.add(egui::Label::new(&toast.message).sense(egui::Sense::click()));
+                    if message.on_hover_text("Show in the console").clicked() {
+                        open_console = true;
+                        dismiss = Some(i);
+                    }
+                });
+            }
+        });
+
+    if let Some(i) = dismiss {
+        editor.toasts.remove(i);
+    }
+    if open_console {
+        editor.layout.dock.open(Tab::Console);
+    }
+    ctx.request_repaint_after(std::time::Duration::from_millis(250));
+}
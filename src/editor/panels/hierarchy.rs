@@ -1,9 +1,16 @@
+use std::collections::HashSet;
+
 use egui::Ui;
 use enigma_3d::AppState;
 use uuid::Uuid;
 
 use crate::editor::actions::{self, LightTemplate, ObjectTemplate};
-use crate::editor::state::{EditorRoot, Modal, PendingDelete, RenameTarget, ResourceKind, Selection};
+use crate::editor::scene_graph::{self, TreeRow};
+use crate::editor::{cameras, cursor_3d, lights, visibility};
+use crate::editor::state::{ClickMode, DragPayload, EditorRoot, HierarchyKind, Modal, PendingDelete, RenameTarget, ResourceKind, Selection, SelectionSet};
+
+/// Horizontal offset per tree level, in points.
+const INDENT: f32 = 14.0;
 
 pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
     let project_loaded = app_state
@@ -17,27 +24,72 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
     }
 
     ui.heading("Hierarchy");
+    filter_bar(ui, app_state);
     ui.separator();
 
-    let object_rows: Vec<(Uuid, String)> = app_state.objects.iter()
-        .map(|o| (o.get_unique_id(), o.name.clone()))
-        .collect();
-    let light_rows: Vec<(usize, String)> = app_state.light.iter().enumerate()
-        .map(|(i, l)| (i, format!("Light {} @ ({:.1}, {:.1}, {:.1})", i, l.position[0], l.position[1], l.position[2])))
-        .collect();
+    let (filter, isolate) = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| (r.editor.hierarchy_filter.clone(), r.editor.isolate.clone()))
+        .unwrap_or_default();
+    let shows = |kind: HierarchyKind| filter.kind == HierarchyKind::All || filter.kind == kind;
+
+    // Hidden objects are out of `app_state.objects` but still listed.
+    let objects = visibility::all_objects(app_state);
+    let (tree, hidden, locked): (Vec<TreeRow>, HashSet<Uuid>, HashSet<Uuid>) = app_state
+        .get_state_data_value::<EditorRoot>("editor")
+        .and_then(|r| {
+            let project = r.project.as_ref()?;
+            let scene = project.scenes.get(project.active_scene_index)?.uuid;
+            let tree = if filter.query.trim().is_empty() {
+                scene_graph::tree_rows(project, scene, &objects, &r.editor.collapsed_objects)
+            } else {
+                // Searching lists the matches flat, wherever they sit in the tree.
+                objects.iter()
+                    .filter(|(_, name)| filter.matches(HierarchyKind::Objects, name))
+                    .map(|(uuid, name)| TreeRow { uuid: *uuid, name: name.clone(), depth: 0, has_children: false })
+                    .collect()
+            };
+            let hidden = objects.iter().map(|(u, _)| *u).filter(|u| project.is_hidden(scene, *u)).collect();
+            let locked = objects.iter().map(|(u, _)| *u).filter(|u| project.is_locked(scene, *u)).collect();
+            Some((tree, hidden, locked))
+        })
+        .unwrap_or_default();
+    let light_rows: Vec<(Uuid, String)> = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| r.editor.lights.iter()
+            .take(app_state.light.len())
+            .map(|m| (m.uuid, m.name.clone()))
+            .filter(|(_, name)| filter.matches(HierarchyKind::Lights, name))
+            .collect())
+        .unwrap_or_default();
     let has_ambient = app_state.ambient_light.is_some();
-    let has_camera = app_state.camera.is_some();
+    let has_camera = cameras::game_camera(app_state).is_some();
 
-    let (current_selection, renaming) = match app_state.get_state_data_value::<EditorRoot>("editor") {
-        Some(r) => (r.editor.selection.clone(), r.editor.renaming.clone()),
-        None => (Selection::None, None),
+    let (current_selection, renaming, dragged) = match app_state.get_state_data_value::<EditorRoot>("editor") {
+        Some(r) => {
+            let dragged = match r.editor.drag {
+                Some(DragPayload::SceneObject(u)) => Some(u),
+                _ => None,
+            };
+            (r.editor.selection.clone(), r.editor.renaming.clone(), dragged)
+        }
+        None => (SelectionSet::default(), None, None),
     };
+    let released = ui.input(|i| i.pointer.any_released());
+    let collapsed = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| r.editor.collapsed_objects.clone())
+        .unwrap_or_default();
+    let click_mode = ui.input(|i| ClickMode::from_modifiers(i.modifiers));
 
     let mut new_selection: Option<Selection> = None;
     let mut delete_request: Option<PendingDelete> = None;
     let mut rename_start: Option<RenameTarget> = None;
     let mut rename_commit: Option<RenameTarget> = None;
     let mut rename_cancel = false;
+    let mut toggle_collapse: Option<Uuid> = None;
+    let mut drag_start: Option<Uuid> = None;
+    // Some(None) detaches the dragged object.
+    let mut drop_on: Option<Option<Uuid>> = None;
+    let mut toggle_hidden: Option<Uuid> = None;
+    let mut toggle_locked: Option<Uuid> = None;
 
     let model_rows: Vec<(Uuid, String)> = app_state
         .get_state_data_value::<EditorRoot>("editor")
@@ -50,119 +102,203 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
 
     let mut spawn_object: Option<ObjectTemplate> = None;
     let mut spawn_model: Option<Uuid> = None;
-    egui::CollapsingHeader::new(format!("Objects ({})", object_rows.len()))
-        .default_open(true)
-        .show(ui, |ui| {
-            ui.menu_button("+ Add", |ui| {
-                if ui.button("Empty").clicked() { spawn_object = Some(ObjectTemplate::Empty); ui.close_menu(); }
-                if ui.button("Cube").clicked() { spawn_object = Some(ObjectTemplate::Cube); ui.close_menu(); }
-                if ui.button("Sphere").clicked() { spawn_object = Some(ObjectTemplate::Sphere); ui.close_menu(); }
-                ui.menu_button("From Model…", |ui| {
-                    if model_rows.is_empty() {
-                        ui.label("(import a model first)");
-                    } else {
-                        for (uuid, name) in &model_rows {
-                            if ui.button(name).clicked() {
-                                spawn_model = Some(*uuid);
-                                ui.close_menu();
+    if shows(HierarchyKind::Objects) {
+        egui::CollapsingHeader::new(format!("Objects ({})", objects.len()))
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.menu_button("+ Add", |ui| {
+                    if ui.button("Empty").clicked() { spawn_object = Some(ObjectTemplate::Empty); ui.close_menu(); }
+                    if ui.button("Cube").clicked() { spawn_object = Some(ObjectTemplate::Cube); ui.close_menu(); }
+                    if ui.button("Sphere").clicked() { spawn_object = Some(ObjectTemplate::Sphere); ui.close_menu(); }
+                    ui.menu_button("From Model…", |ui| {
+                        if model_rows.is_empty() {
+                            ui.label("(import a model first)");
+                        } else {
+                            for (uuid, name) in &model_rows {
+                                if ui.button(name).clicked() {
+                                    spawn_model = Some(*uuid);
+                                    ui.close_menu();
+                                }
                             }
                         }
-                    }
+                    });
                 });
+                if dragged.is_some() {
+                    let zone = ui.weak("⤒ Drop here to detach");
+                    if ui.rect_contains_pointer(zone.rect) {
+                        ui.painter().rect_stroke(zone.rect.expand(2.0), 2.0, ui.visuals().selection.stroke);
+                        if released { drop_on = Some(None); }
+                    }
+                }
+                for TreeRow { uuid, name, depth, has_children } in &tree {
+                    let selected = current_selection.contains(&Selection::SceneObject(*uuid));
+                    let renaming_this = matches!(&renaming, Some(RenameTarget::SceneObject { uuid: u, .. }) if u == uuid);
+                    ui.horizontal(|ui| {
+                        ui.add_space(*depth as f32 * INDENT);
+                        if *has_children {
+                            let open = !collapsed.contains(uuid);
+                            if ui.small_button(if open { "▼" } else { "▶" }).clicked() {
+                                toggle_collapse = Some(*uuid);
+                            }
+                        } else {
+                            ui.add_space(ui.spacing().interact_size.y);
+                        }
+                        if renaming_this {
+                            if let Some(RenameTarget::SceneObject { uuid, draft }) = &renaming {
+                                let mut d = draft.clone();
+                                let response = ui.text_edit_singleline(&mut d);
+                                response.request_focus();
+                                let enter = response.lost_focus()
+                                    && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                let escape = ui.input(|i| i.key_pressed(egui::Key::Escape));
+                                let commit_btn = ui.small_button("✓").on_hover_text("Apply").clicked();
+                                let cancel_btn = ui.small_button("✗").on_hover_text("Cancel").clicked();
+                                if escape || cancel_btn {
+                                    rename_cancel = true;
+                                } else if enter || commit_btn {
+                                    rename_commit = Some(RenameTarget::SceneObject { uuid: *uuid, draft: d });
+                                } else {
+                                    rename_start = Some(RenameTarget::SceneObject { uuid: *uuid, draft: d });
+                                }
+                            }
+                        } else {
+                            // Hidden, and isolated-away, rows are dimmed.
+                            let dimmed = hidden.contains(uuid) || isolate.as_ref().is_some_and(|set| !set.contains(uuid));
+                            let text = if dimmed { egui::RichText::new(name).weak() } else { egui::RichText::new(name) };
+                            let resp = ui.selectable_label(selected, text).interact(egui::Sense::drag());
+                            if resp.clicked() {
+                                new_selection = Some(Selection::SceneObject(*uuid));
+                            }
+                            if resp.drag_started() {
+                                drag_start = Some(*uuid);
+                            }
+                            if dragged.is_some_and(|d| d != *uuid) && ui.rect_contains_pointer(resp.rect) {
+                                ui.painter().rect_stroke(resp.rect.expand(1.0), 2.0, ui.visuals().selection.stroke);
+                                if released { drop_on = Some(Some(*uuid)); }
+                            }
+                            if resp.double_clicked() {
+                                rename_start = Some(RenameTarget::SceneObject { uuid: *uuid, draft: name.clone() });
+                            }
+                        }
+                        let is_hidden = hidden.contains(uuid);
+                        if ui.small_button(if is_hidden { "◌" } else { "👁" })
+                            .on_hover_text(if is_hidden { "Show" } else { "Hide" }).clicked()
+                        {
+                            toggle_hidden = Some(*uuid);
+                        }
+                        let is_locked = locked.contains(uuid);
+                        if ui.small_button(if is_locked { "🔒" } else { "🔓" })
+                            .on_hover_text(if is_locked { "Unlock" } else { "Lock: not pickable or movable in the viewport" }).clicked()
+                        {
+                            toggle_locked = Some(*uuid);
+                        }
+                        if ui.small_button("×").on_hover_text("Delete").clicked() {
+                            delete_request = Some(PendingDelete::SceneObject(*uuid));
+                        }
+                    });
+                }
             });
-            for (uuid, name) in &object_rows {
-                let selected = matches!(current_selection, Selection::SceneObject(s) if s == *uuid);
-                let renaming_this = matches!(&renaming, Some(RenameTarget::SceneObject { uuid: u, .. }) if u == uuid);
-                ui.horizontal(|ui| {
-                    if renaming_this {
-                        if let Some(RenameTarget::SceneObject { uuid, draft }) = &renaming {
-                            let mut d = draft.clone();
-                            let response = ui.text_edit_singleline(&mut d);
-                            response.request_focus();
-                            let enter = response.lost_focus()
-                                && ui.input(|i| i.key_pressed(egui::Key::Enter));
-                            let escape = ui.input(|i| i.key_pressed(egui::Key::Escape));
-                            let commit_btn = ui.small_button("✓").on_hover_text("Apply").clicked();
-                            let cancel_btn = ui.small_button("✗").on_hover_text("Cancel").clicked();
-                            if escape || cancel_btn {
-                                rename_cancel = true;
-                            } else if enter || commit_btn {
-                                rename_commit = Some(RenameTarget::SceneObject { uuid: *uuid, draft: d });
-                            } else {
-                                rename_start = Some(RenameTarget::SceneObject { uuid: *uuid, draft: d });
+    }
+
+    let mut spawn_light: Option<LightTemplate> = None;
+    let mut align_camera = false;
+    let ambient_listed = filter.matches(HierarchyKind::Lights, "Ambient");
+    if shows(HierarchyKind::Lights) {
+        egui::CollapsingHeader::new(format!("Lights ({})", light_rows.len() + has_ambient as usize))
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.menu_button("+ Add", |ui| {
+                    if ui.button("Directional").clicked() { spawn_light = Some(LightTemplate::Directional); ui.close_menu(); }
+                    if ui.button("Point").clicked() { spawn_light = Some(LightTemplate::Point); ui.close_menu(); }
+                    if !has_ambient && ui.button("Ambient").clicked() {
+                        spawn_light = Some(LightTemplate::Ambient);
+                        ui.close_menu();
+                    }
+                });
+                for (uuid, name) in &light_rows {
+                    let selected = current_selection.contains(&Selection::Light(*uuid));
+                    ui.horizontal(|ui| {
+                        if let Some(RenameTarget::Light { uuid: u, draft }) = &renaming {
+                            if u == uuid {
+                                let mut d = draft.clone();
+                                let response = ui.text_edit_singleline(&mut d);
+                                response.request_focus();
+                                let enter = response.lost_focus()
+                                    && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                                let escape = ui.input(|i| i.key_pressed(egui::Key::Escape));
+                                let commit_btn = ui.small_button("✓").on_hover_text("Apply").clicked();
+                                let cancel_btn = ui.small_button("✗").on_hover_text("Cancel").clicked();
+                                if escape || cancel_btn {
+                                    rename_cancel = true;
+                                } else if enter || commit_btn {
+                                    rename_commit = Some(RenameTarget::Light { uuid: *uuid, draft: d });
+                                } else {
+                                    rename_start = Some(RenameTarget::Light { uuid: *uuid, draft: d });
+                                }
+                                return;
                             }
                         }
-                    } else {
                         let resp = ui.selectable_label(selected, name);
                         if resp.clicked() {
-                            new_selection = Some(Selection::SceneObject(*uuid));
+                            new_selection = Some(Selection::Light(*uuid));
                         }
                         if resp.double_clicked() {
-                            rename_start = Some(RenameTarget::SceneObject { uuid: *uuid, draft: name.clone() });
+                            rename_start = Some(RenameTarget::Light { uuid: *uuid, draft: name.clone() });
                         }
-                    }
-                    if ui.small_button("×").on_hover_text("Delete").clicked() {
-                        delete_request = Some(PendingDelete::SceneObject(*uuid));
-                    }
-                });
-            }
-        });
-
-    let mut spawn_light: Option<LightTemplate> = None;
-    egui::CollapsingHeader::new(format!("Lights ({})", light_rows.len() + has_ambient as usize))
-        .default_open(true)
-        .show(ui, |ui| {
-            ui.menu_button("+ Add", |ui| {
-                if ui.button("Directional").clicked() { spawn_light = Some(LightTemplate::Directional); ui.close_menu(); }
-                if ui.button("Point").clicked() { spawn_light = Some(LightTemplate::Point); ui.close_menu(); }
-                if !has_ambient && ui.button("Ambient").clicked() {
-                    spawn_light = Some(LightTemplate::Ambient);
-                    ui.close_menu();
+                        if ui.small_button("×").on_hover_text("Delete").clicked() {
+                            delete_request = Some(PendingDelete::Light(*uuid));
+                        }
+                    });
+                }
+                // Always render the ambient slot — present if Some, "+ Add" inline if None.
+                // This avoids a one-frame delay before users see the row after spawning.
+                if ambient_listed {
+                    ui.horizontal(|ui| {
+                        if has_ambient {
+                            let selected = current_selection.contains(&Selection::AmbientLight);
+                            if ui.selectable_label(selected, "Ambient").clicked() {
+                                new_selection = Some(Selection::AmbientLight);
+                            }
+                            if ui.small_button("×").on_hover_text("Delete ambient light").clicked() {
+                                delete_request = Some(PendingDelete::AmbientLight);
+                            }
+                        } else {
+                            ui.weak("Ambient (none)");
+                            if ui.small_button("+ Add").clicked() {
+                                spawn_light = Some(LightTemplate::Ambient);
+                            }
+                        }
+                    });
                 }
             });
-            for (idx, name) in &light_rows {
-                let selected = matches!(current_selection, Selection::Light(s) if s == *idx);
-                ui.horizontal(|ui| {
-                    if ui.selectable_label(selected, name).clicked() {
-                        new_selection = Some(Selection::Light(*idx));
-                    }
-                    if ui.small_button("×").on_hover_text("Delete").clicked() {
-                        delete_request = Some(PendingDelete::Light(*idx));
-                    }
-                });
-            }
-            // Always render the ambient slot — present if Some, "+ Add" inline if None.
-            // This avoids a one-frame delay before users see the row after spawning.
-            ui.horizontal(|ui| {
-                if has_ambient {
-                    let selected = matches!(current_selection, Selection::AmbientLight);
-                    if ui.selectable_label(selected, "Ambient").clicked() {
-                        new_selection = Some(Selection::AmbientLight);
-                    }
-                    if ui.small_button("×").on_hover_text("Delete ambient light").clicked() {
-                        delete_request = Some(PendingDelete::AmbientLight);
+    }
+
+    if shows(HierarchyKind::Camera) && filter.matches(HierarchyKind::Camera, "Camera") {
+        egui::CollapsingHeader::new("Camera")
+            .default_open(true)
+            .show(ui, |ui| {
+                if has_camera {
+                    let selected = current_selection.contains(&Selection::Camera);
+                    let resp = ui.selectable_label(selected, "Camera");
+                    if resp.clicked() {
+                        new_selection = Some(Selection::Camera);
                     }
+                    resp.context_menu(|ui| {
+                        if ui.button("Align to view").clicked() {
+                            align_camera = true;
+                            ui.close_menu();
+                        }
+                    });
                 } else {
-                    ui.weak("Ambient (none)");
-                    if ui.small_button("+ Add").clicked() {
-                        spawn_light = Some(LightTemplate::Ambient);
-                    }
+                    ui.horizontal(|ui| {
+                        ui.label("(no camera)");
+                        if ui.small_button("+ From view").clicked() {
+                            align_camera = true;
+                        }
+                    });
                 }
             });
-        });
-
-    egui::CollapsingHeader::new("Camera")
-        .default_open(true)
-        .show(ui, |ui| {
-            if has_camera {
-                let selected = matches!(current_selection, Selection::Camera);
-                if ui.selectable_label(selected, "Camera").clicked() {
-                    new_selection = Some(Selection::Camera);
-                }
-            } else {
-                ui.label("(no camera)");
-            }
-        });
+    }
 
     let particle_defs: Vec<(Uuid, String)> = app_state
         .get_state_data_value::<EditorRoot>("editor")
@@ -173,38 +309,43 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
         .get_state_data_value::<EditorRoot>("editor")
         .and_then(|r| r.project.as_ref())
         .and_then(|p| p.scenes.get(p.active_scene_index))
-        .map(|s| s.particle_instances.iter().map(|i| (i.uuid, i.name.clone())).collect())
+        .map(|s| s.particle_instances.iter()
+            .filter(|i| filter.matches(HierarchyKind::Particles, &i.name))
+            .map(|i| (i.uuid, i.name.clone()))
+            .collect())
         .unwrap_or_default();
 
     let mut spawn_instance_def: Option<Uuid> = None;
     let mut delete_instance: Option<Uuid> = None;
-    egui::CollapsingHeader::new(format!("Particles ({})", particle_instances.len()))
-        .default_open(true)
-        .show(ui, |ui| {
-            ui.menu_button("+ Add", |ui| {
-                if particle_defs.is_empty() {
-                    ui.label("(create a particle system first)");
-                } else {
-                    for (uuid, name) in &particle_defs {
-                        if ui.button(name).clicked() {
-                            spawn_instance_def = Some(*uuid);
-                            ui.close_menu();
+    if shows(HierarchyKind::Particles) {
+        egui::CollapsingHeader::new(format!("Particles ({})", particle_instances.len()))
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.menu_button("+ Add", |ui| {
+                    if particle_defs.is_empty() {
+                        ui.label("(create a particle system first)");
+                    } else {
+                        for (uuid, name) in &particle_defs {
+                            if ui.button(name).clicked() {
+                                spawn_instance_def = Some(*uuid);
+                                ui.close_menu();
+                            }
                         }
                     }
+                });
+                for (uuid, name) in &particle_instances {
+                    let selected = current_selection.contains(&Selection::ParticleInstance(*uuid));
+                    ui.horizontal(|ui| {
+                        if ui.selectable_label(selected, name).clicked() {
+                            new_selection = Some(Selection::ParticleInstance(*uuid));
+                        }
+                        if ui.small_button("×").on_hover_text("Delete").clicked() {
+                            delete_instance = Some(*uuid);
+                        }
+                    });
                 }
             });
-            for (uuid, name) in &particle_instances {
-                let selected = matches!(current_selection, Selection::ParticleInstance(s) if s == *uuid);
-                ui.horizontal(|ui| {
-                    if ui.selectable_label(selected, name).clicked() {
-                        new_selection = Some(Selection::ParticleInstance(*uuid));
-                    }
-                    if ui.small_button("×").on_hover_text("Delete").clicked() {
-                        delete_instance = Some(*uuid);
-                    }
-                });
-            }
-        });
+    }
 
     let has_terrain = app_state
         .get_state_data_value::<EditorRoot>("editor")
@@ -212,51 +353,22 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
         .and_then(|p| p.scenes.get(p.active_scene_index))
         .map(|s| s.terrain.is_some())
         .unwrap_or(false);
-    {
-        let selected = matches!(current_selection, Selection::Terrain);
+    if shows(HierarchyKind::Terrain) && filter.matches(HierarchyKind::Terrain, "Terrain") {
+        let selected = current_selection.contains(&Selection::Terrain);
         let label = if has_terrain { "Terrain" } else { "Terrain (none)" };
         if ui.selectable_label(selected, label).clicked() {
             new_selection = Some(Selection::Terrain);
         }
     }
 
-    let delete_key = ui.input(|i| i.key_pressed(egui::Key::Delete) || i.key_pressed(egui::Key::Backspace));
-    if delete_key && renaming.is_none() {
-        match &current_selection {
-            Selection::SceneObject(u) => delete_request = Some(PendingDelete::SceneObject(*u)),
-            Selection::Light(i) => delete_request = Some(PendingDelete::Light(*i)),
-            Selection::AmbientLight => delete_request = Some(PendingDelete::AmbientLight),
-            Selection::ParticleInstance(u) => delete_request = Some(PendingDelete::ParticleInstance(*u)),
-            _ => {}
-        }
-    }
-
     if let Some(t) = spawn_object { actions::add_object(app_state, t); }
     if let Some(uuid) = spawn_model { actions::spawn_from_model(app_state, uuid); }
     if let Some(t) = spawn_light { actions::add_light(app_state, t); }
+    if align_camera { cameras::align_game_camera_to_view(app_state); }
 
     if let Some(def_uuid) = spawn_instance_def {
-        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-            if let Some(project) = root.project.as_mut() {
-                let active = project.active_scene_index;
-                let def_name = project.particle_systems.iter()
-                    .find(|d| d.uuid == def_uuid)
-                    .map(|d| d.config.name.clone())
-                    .unwrap_or_else(|| "particles".into());
-                if let Some(scene) = project.scenes.get_mut(active) {
-                    let inst_uuid = Uuid::new_v4();
-                    let n = scene.particle_instances.len() + 1;
-                    scene.particle_instances.push(crate::editor::state::ParticleInstance {
-                        uuid: inst_uuid,
-                        def_uuid,
-                        name: format!("{} {}", def_name, n),
-                        position: [0.0, 0.0, 0.0],
-                    });
-                    root.editor.selection = Selection::ParticleInstance(inst_uuid);
-                    root.editor.dirty = true;
-                }
-            }
-        }
+        let at = cursor_3d::spawn_point(app_state);
+        actions::add_particle_instance(app_state, def_uuid, at.into());
     }
 
     if let Some(u) = delete_instance {
@@ -264,21 +376,65 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
     }
 
     if let Some(sel) = new_selection {
+        // Shift-click in the object tree selects the run of visible rows between the
+        // primary object and the clicked one.
+        let range = match (click_mode, current_selection.primary(), &sel) {
+            (ClickMode::Extend, Selection::SceneObject(from), Selection::SceneObject(to)) => {
+                let from = tree.iter().position(|r| r.uuid == *from);
+                let to = tree.iter().position(|r| r.uuid == *to);
+                match (from, to) {
+                    (Some(a), Some(b)) if a <= b => tree[a..=b].iter().map(|r| r.uuid).collect(),
+                    (Some(a), Some(b)) => tree[b..=a].iter().rev().map(|r| r.uuid).collect(),
+                    _ => Vec::new(),
+                }
+            }
+            _ => Vec::new(),
+        };
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            for uuid in range {
+                root.editor.selection.add(Selection::SceneObject(uuid));
+            }
+            root.editor.selection.click(sel, click_mode);
+        }
+    }
+
+    if let Some(uuid) = toggle_hidden { visibility::toggle_hidden(app_state, uuid); }
+    if let Some(uuid) = toggle_locked { visibility::toggle_locked(app_state, uuid); }
+
+    if let Some(uuid) = toggle_collapse {
         if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-            root.editor.selection = sel;
+            if !root.editor.collapsed_objects.remove(&uuid) {
+                root.editor.collapsed_objects.insert(uuid);
+            }
+        }
+    }
+
+    if let Some(uuid) = drag_start {
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            root.editor.drag = Some(DragPayload::SceneObject(uuid));
+        }
+    }
+    if let Some(child) = dragged {
+        if let Some(parent) = drop_on {
+            scene_graph::attach(app_state, child, parent);
+            if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+                if let Some(p) = parent { root.editor.collapsed_objects.remove(&p); }
+            }
+        }
+        if released {
+            if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+                root.editor.drag = None;
+            }
+        } else if let Some((_, name)) = objects.iter().find(|(u, _)| *u == child) {
+            egui::show_tooltip_at_pointer(ui.ctx(), egui::Id::new("hierarchy_drag"), |ui| {
+                ui.label(name);
+            });
         }
     }
 
     if let Some(req) = delete_request {
-        let label = match &req {
-            PendingDelete::SceneObject(_) => "object",
-            PendingDelete::Light(_) => "light",
-            PendingDelete::AmbientLight => "ambient light",
-            PendingDelete::ParticleInstance(_) => "particle instance",
-            _ => "item",
-        };
         if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-            root.editor.modal = Some(Modal::ConfirmDelete { label: label.to_string(), pending: req });
+            root.editor.modal = Some(Modal::ConfirmDelete { label: req.label(), pending: req });
         }
     }
 
@@ -302,6 +458,9 @@ fn commit_rename(app_state: &mut AppState, target: RenameTarget) {
                 obj.name = draft;
             }
         }
+        RenameTarget::Light { uuid, draft } => {
+            lights::rename(app_state, uuid, draft);
+        }
         _ => {}
     }
     if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
@@ -309,3 +468,34 @@ fn commit_rename(app_state: &mut AppState, target: RenameTarget) {
         root.editor.dirty = true;
     }
 }
+
+/// Search box, type filter and the isolate toggle.
+fn filter_bar(ui: &mut Ui, app_state: &mut AppState) {
+    let Some((mut filter, isolating)) = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| (r.editor.hierarchy_filter.clone(), r.editor.isolate.is_some())) else { return; };
+    let mut isolate_clicked = false;
+    ui.horizontal(|ui| {
+        let width = (ui.available_width() - 120.0).max(60.0);
+        ui.add(egui::TextEdit::singleline(&mut filter.query).hint_text("Search…").desired_width(width));
+        if filter.is_active() && ui.small_button("×").on_hover_text("Clear filter").clicked() {
+            filter = Default::default();
+        }
+        egui::ComboBox::from_id_source("hierarchy_kind")
+            .width(70.0)
+            .selected_text(filter.kind.label())
+            .show_ui(ui, |ui| {
+                for kind in HierarchyKind::ALL {
+                    ui.selectable_value(&mut filter.kind, kind, kind.label());
+                }
+            });
+        isolate_clicked = ui.selectable_label(isolating, "◎")
+            .on_hover_text(if isolating { "Show everything again" } else { "Isolate: show only the selected objects" })
+            .clicked();
+    });
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.hierarchy_filter = filter;
+    }
+    if isolate_clicked {
+        visibility::toggle_isolate(app_state);
+    }
+}
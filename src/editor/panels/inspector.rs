@@ -1,7 +1,7 @@
 use egui::Ui;
 use enigma_3d::AppState;
 
-use crate::editor::inspector;
+use crate::editor::{inspector, lights};
 use crate::editor::state::{EditorRoot, Selection};
 
 pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
@@ -13,6 +13,12 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
     ui.heading("Inspector");
     ui.separator();
 
+    if selection.is_multi() {
+        inspector::selection::draw(ui, app_state);
+        return;
+    }
+    let selection = selection.primary().clone();
+
     match selection {
         Selection::None => {
             inspector::scene_settings::draw(ui, app_state);
@@ -23,10 +29,13 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
             inspector::transform::draw_for_object(ui, app_state, uuid);
             inspector::mesh_material::draw(ui, app_state, uuid);
             inspector::animation::draw(ui, app_state, uuid);
+            inspector::script_component::draw(ui, app_state, uuid);
         }
-        Selection::Light(index) => {
-            inspector::transform::draw_for_light(ui, app_state, index);
-            inspector::light::draw(ui, app_state, index);
+        Selection::Light(uuid) => {
+            if let Some(index) = lights::index_of(app_state, uuid) {
+                inspector::transform::draw_for_light(ui, app_state, index);
+                inspector::light::draw(ui, app_state, index);
+            }
         }
         Selection::AmbientLight => {
             inspector::light::draw_ambient(ui, app_state);
@@ -49,5 +58,8 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
         Selection::Terrain => {
             inspector::terrain_editor::draw(ui, app_state);
         }
+        Selection::Script(uuid) => {
+            inspector::script_editor::draw(ui, app_state, uuid);
+        }
     }
 }
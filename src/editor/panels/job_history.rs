@@ -0,0 +1,85 @@
+use egui::Context;
+use enigma_3d::AppState;
+
+use crate::editor::state::EditorRoot;
+
+/// Past cargo jobs of the open project with their complete logs.
+pub fn draw(ctx: &Context, app_state: &mut AppState) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let editor = &mut root.editor;
+    if !editor.show_job_history {
+        return;
+    }
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut open = true;
+    egui::Window::new("Job History")
+        .open(&mut open)
+        .resizable(true)
+        .default_width(640.0)
+        .default_height(400.0)
+        .show(ctx, |ui| {
+            if editor.job_history.is_empty() {
+                ui.weak("No jobs have run in this project yet.");
+                return;
+            }
+            ui.horizontal_top(|ui| {
+                ui.vertical(|ui| {
+                    ui.set_width(220.0);
+                    egui::ScrollArea::vertical().id_source("job_history_list").show(ui, |ui| {
+                        // Newest first.
+                        for record in editor.job_history.iter().rev() {
+                            let outcome = &record.outcome;
+                            let marker = if outcome.success { "✓" } else { "✗" };
+                            let text = format!(
+                                "{marker} {} ({:.1}s) — {}",
+                                outcome.label,
+                                outcome.duration.as_secs_f32(),
+                                format_age(now.saturating_sub(record.finished_at)),
+                            );
+                            let selected = editor.job_history_selected == Some(record.id);
+                            if ui.selectable_label(selected, text).on_hover_text(&outcome.message).clicked() {
+                                editor.job_history_selected = Some(record.id);
+                            }
+                        }
+                    });
+                });
+                ui.separator();
+                ui.vertical(|ui| {
+                    let selected = editor.job_history_selected;
+                    let Some(record) = editor.job_history.iter().find(|r| Some(r.id) == selected) else {
+                        ui.weak("Select a job to see its log.");
+                        return;
+                    };
+                    ui.label(format!("{}: {}", record.outcome.label, record.outcome.message));
+                    ui.separator();
+                    egui::ScrollArea::both()
+                        .id_source("job_history_log")
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| {
+                            if record.log.is_empty() {
+                                ui.weak("(no output)");
+                            }
+                            for line in &record.log {
+                                ui.monospace(line);
+                            }
+                        });
+                });
+            });
+        });
+    if !open {
+        editor.show_job_history = false;
+    }
+}
+
+pub fn format_age(secs: u64) -> String {
+    match secs {
+        0..=59 => "just now".into(),
+        60..=3599 => format!("{} min ago", secs / 60),
+        3600..=86_399 => format!("{} h ago", secs / 3600),
+        _ => format!("{} d ago", secs / 86_400),
+    }
+}
@@ -0,0 +1,96 @@
+use egui::{Context, Event, Key};
+use enigma_3d::AppState;
+
+use crate::editor::commands::{self, CommandId, Shortcut, BINDABLE_KEYS, COMMANDS, MOUSE_BINDINGS};
+use crate::editor::state::EditorRoot;
+
+/// Every command with its shortcut, then the fixed mouse bindings. Click a
+/// shortcut, then press the new keys; Escape cancels. Changes are saved with
+/// the editor preferences.
+pub fn draw(ctx: &Context, app_state: &mut AppState) {
+    let Some((keymap, capturing)) = app_state.get_state_data_value::<EditorRoot>("editor")
+        .filter(|r| r.editor.show_keybindings)
+        .map(|r| (r.editor.keymap.clone(), r.editor.capturing_shortcut)) else { return; };
+
+    let mut change: Option<(CommandId, Option<Shortcut>)> = None;
+    let mut capture = capturing;
+    if let Some(id) = capturing {
+        let pressed = ctx.input(|i| i.events.iter().find_map(|e| match e {
+            Event::Key { key, pressed: true, modifiers, .. } => Some((*key, *modifiers)),
+            _ => None,
+        }));
+        match pressed {
+            Some((Key::Escape, _)) => capture = None,
+            Some((key, m)) if BINDABLE_KEYS.contains(&key) => {
+                change = Some((id, Some(Shortcut { key, command: m.command, shift: m.shift, alt: m.alt })));
+                capture = None;
+            }
+            _ => {}
+        }
+    }
+
+    let mut open = true;
+    egui::Window::new("Keyboard Shortcuts")
+        .open(&mut open)
+        .resizable(true)
+        .default_width(480.0)
+        .default_height(480.0)
+        .show(ctx, |ui| {
+            ui.weak("Click a shortcut, then press the new keys. Escape cancels.");
+            ui.separator();
+            egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                egui::Grid::new("keybindings").num_columns(4).striped(true).show(ui, |ui| {
+                    let mut category = "";
+                    for command in COMMANDS {
+                        if command.category != category {
+                            category = command.category;
+                            ui.strong(category);
+                            ui.end_row();
+                        }
+                        let id = command.id;
+                        ui.label(command.label);
+                        let text = if capture == Some(id) {
+                            "Press keys…".to_string()
+                        } else {
+                            keymap.shortcut(id).map_or_else(|| "—".to_string(), |s| s.to_string())
+                        };
+                        if ui.button(text).clicked() {
+                            capture = Some(id);
+                        }
+                        let conflicts = keymap.conflicts(id);
+                        if conflicts.is_empty() {
+                            ui.label("");
+                        } else {
+                            let names: Vec<&str> = conflicts.iter().map(|c| commands::find(*c).label).collect();
+                            ui.colored_label(egui::Color32::YELLOW, "⚠")
+                                .on_hover_text(format!("Also bound to {}", names.join(", ")));
+                        }
+                        ui.horizontal(|ui| {
+                            if ui.add_enabled(keymap.shortcut(id).is_some(), egui::Button::new("Clear").small()).clicked() {
+                                change = Some((id, None));
+                            }
+                            if ui.add_enabled(!keymap.is_default(id), egui::Button::new("Reset").small()).clicked() {
+                                change = Some((id, command.default));
+                            }
+                        });
+                        ui.end_row();
+                    }
+                    ui.strong("Mouse");
+                    ui.end_row();
+                    for (label, binding) in MOUSE_BINDINGS {
+                        ui.label(*label);
+                        ui.weak(*binding);
+                        ui.end_row();
+                    }
+                });
+            });
+        });
+
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.show_keybindings = open;
+        root.editor.capturing_shortcut = if open { capture } else { None };
+    }
+    if let Some((id, shortcut)) = change {
+        commands::rebind(app_state, id, shortcut);
+    }
+}
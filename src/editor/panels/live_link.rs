@@ -0,0 +1,84 @@
+use egui::Context;
+use enigma_3d::AppState;
+use uuid::Uuid;
+
+use crate::editor::state::EditorRoot;
+
+/// An object's reported transform: uuid, position, rotation, scale.
+type Pulled = (Uuid, [f32; 3], [f32; 3], [f32; 3]);
+
+/// Connection status of the live link and the object state the running game
+/// reports back.
+pub fn draw(ctx: &Context, app_state: &mut AppState) {
+    let mut pull: Option<Pulled> = None;
+    {
+        let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+        if !root.editor.show_live_link {
+            return;
+        }
+        let mut open = true;
+        egui::Window::new("Live Link")
+            .open(&mut open)
+            .resizable(true)
+            .default_width(520.0)
+            .default_height(320.0)
+            .show(ctx, |ui| {
+                let Some(link) = root.editor.live_link.as_mut() else {
+                    ui.weak("Run the game to connect.");
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    match (&link.stream, link.game_pid) {
+                        (Some(_), Some(pid)) => ui.label(format!("Connected to game (pid {pid})")),
+                        (Some(_), None) => ui.label("Connected"),
+                        (None, _) => ui.weak(format!("Waiting for the game on {}", link.addr)),
+                    };
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.checkbox(&mut link.push_enabled, "Push edits");
+                    });
+                });
+                if link.stream.is_none() {
+                    ui.weak("Projects created before the live link need `mod enigma_live_link;` and \
+                             `enigma_live_link::install(&mut app_state);` in main.rs.");
+                }
+                ui.separator();
+                if link.objects.is_empty() {
+                    ui.weak("No object state reported.");
+                    return;
+                }
+                egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    egui::Grid::new("live_link_objects").striped(true).num_columns(5).show(ui, |ui| {
+                        ui.strong("Object");
+                        ui.strong("Position");
+                        ui.strong("Rotation");
+                        ui.strong("Scale");
+                        ui.label("");
+                        ui.end_row();
+                        let fmt = |v: [f32; 3]| format!("{:.2}, {:.2}, {:.2}", v[0], v[1], v[2]);
+                        for obj in &link.objects {
+                            ui.label(&obj.name);
+                            ui.monospace(fmt(obj.position));
+                            ui.monospace(fmt(obj.rotation));
+                            ui.monospace(fmt(obj.scale));
+                            if ui.small_button("Pull").on_hover_text("Copy this transform into the editor scene").clicked() {
+                                pull = Some((obj.uuid, obj.position, obj.rotation, obj.scale));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+        if !open {
+            root.editor.show_live_link = false;
+        }
+    }
+
+    let Some((uuid, position, rotation, scale)) = pull else { return; };
+    let Some(obj) = app_state.objects.iter_mut().find(|o| o.get_unique_id() == uuid) else { return; };
+    obj.transform.set_position(position);
+    obj.transform.set_rotation(rotation);
+    obj.transform.set_scale(scale);
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.dirty = true;
+    }
+}
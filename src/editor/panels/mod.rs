@@ -3,3 +3,12 @@ pub mod hierarchy;
 pub mod inspector;
 pub mod resource_browser;
 pub mod viewport;
+pub mod problems;
+pub mod job_history;
+pub mod run_configs;
+pub mod live_link;
+pub mod keybindings;
+pub mod command_palette;
+pub mod welcome;
+pub mod preferences;
+pub mod console;
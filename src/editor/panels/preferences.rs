@@ -0,0 +1,57 @@
+use egui::Context;
+use enigma_3d::AppState;
+
+use crate::editor::state::{EditorRoot, FlySettings, SpawnAt};
+
+/// Settings with no other home in the UI. Everything here is saved to the
+/// per-user preferences as it changes.
+pub fn draw(ctx: &Context, app_state: &mut AppState) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let editor = &mut root.editor;
+    if !editor.show_preferences {
+        return;
+    }
+
+    let mut open = true;
+    egui::Window::new("Preferences")
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            egui::Grid::new("preferences").num_columns(2).show(ui, |ui| {
+                let fly = &mut editor.navigation.fly;
+                ui.strong("Camera");
+                ui.end_row();
+                ui.label("Fly speed");
+                ui.add(egui::DragValue::new(&mut fly.speed).speed(0.1).clamp_range(0.1..=200.0).suffix(" u/s"));
+                ui.end_row();
+                ui.label("Look sensitivity");
+                ui.add(egui::DragValue::new(&mut fly.look_sensitivity).speed(0.0001).clamp_range(0.0005..=0.05).max_decimals(4));
+                ui.end_row();
+                ui.label("");
+                if ui.button("Reset").clicked() {
+                    *fly = FlySettings::default();
+                }
+                ui.end_row();
+
+                ui.strong("New items");
+                ui.end_row();
+                ui.label("Spawn");
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut editor.spawn_at, SpawnAt::InFrontOfCamera, "In front of camera");
+                    ui.radio_value(&mut editor.spawn_at, SpawnAt::Cursor, "At 3D cursor");
+                });
+                ui.end_row();
+
+                ui.strong("Welcome screen");
+                ui.end_row();
+                ui.label(format!("{} recent projects", editor.recent_projects.len()));
+                if ui.add_enabled(!editor.recent_projects.is_empty(), egui::Button::new("Clear")).clicked() {
+                    editor.recent_projects.clear();
+                }
+                ui.end_row();
+            });
+            ui.separator();
+            ui.weak("Shortcuts are under File → Keyboard Shortcuts.");
+        });
+    editor.show_preferences = open;
+}
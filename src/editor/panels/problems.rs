@@ -0,0 +1,79 @@
+use egui::{Color32, Ui};
+use enigma_3d::AppState;
+
+use crate::editor::diagnostics;
+use crate::editor::dock::Tab;
+use crate::editor::state::{DiagnosticLevel, EditorRoot};
+
+pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let editor = &mut root.editor;
+    let (errors, warnings) = diagnostics::counts(&editor.problems);
+    let notes = editor.problems.len() - errors - warnings;
+
+    let mut close = false;
+    ui.horizontal(|ui| {
+        ui.heading("Problems");
+        let filter = &mut editor.problems_filter;
+        ui.toggle_value(&mut filter.errors, format!("Errors ({errors})"));
+        ui.toggle_value(&mut filter.warnings, format!("Warnings ({warnings})"));
+        ui.toggle_value(&mut filter.notes, format!("Notes ({notes})"));
+        ui.add(egui::TextEdit::singleline(&mut filter.text).hint_text("Filter").desired_width(160.0));
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if ui.small_button("×").on_hover_text("Close").clicked() {
+                close = true;
+            }
+        });
+    });
+    ui.separator();
+
+    let filter = &editor.problems_filter;
+    let needle = filter.text.to_lowercase();
+    let visible: Vec<_> = editor.problems.iter()
+        .filter(|d| match d.level {
+            DiagnosticLevel::Error => filter.errors,
+            DiagnosticLevel::Warning => filter.warnings,
+            DiagnosticLevel::Note | DiagnosticLevel::Help => filter.notes,
+        })
+        .filter(|d| needle.is_empty()
+            || d.message.to_lowercase().contains(&needle)
+            || d.file.as_deref().map(|f| f.to_lowercase().contains(&needle)).unwrap_or(false))
+        .collect();
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            if editor.problems.is_empty() {
+                ui.weak("No problems from the last build.");
+            } else if visible.is_empty() {
+                ui.weak("All problems are filtered out.");
+            }
+            for (idx, d) in visible.iter().enumerate() {
+                let (icon, color) = match d.level {
+                    DiagnosticLevel::Error => ("⛔", Color32::from_rgb(230, 90, 80)),
+                    DiagnosticLevel::Warning => ("⚠", Color32::from_rgb(230, 180, 60)),
+                    DiagnosticLevel::Note | DiagnosticLevel::Help => ("ℹ", Color32::from_rgb(120, 160, 220)),
+                };
+                let location = match &d.file {
+                    Some(file) => format!("{file}:{}:{}", d.line, d.column),
+                    None => "(crate)".to_string(),
+                };
+                let code = d.code.as_deref().map(|c| format!("[{c}] ")).unwrap_or_default();
+                let header = egui::RichText::new(format!("{icon} {code}{}", d.message)).color(color);
+                egui::CollapsingHeader::new(header)
+                    .id_source(("problem", idx))
+                    .show(ui, |ui| {
+                        ui.monospace(&location);
+                        if let Some(rendered) = &d.rendered {
+                            ui.monospace(rendered);
+                        }
+                    })
+                    .header_response
+                    .on_hover_text(&location);
+            }
+        });
+
+    if close {
+        editor.layout.dock.remove(Tab::Problems);
+    }
+}
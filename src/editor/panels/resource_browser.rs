@@ -3,9 +3,11 @@ use enigma_3d::AppState;
 use rfd::FileDialog;
 use uuid::Uuid;
 
+use crate::editor::drag_drop;
+use crate::editor::logging::{self, Source};
 use crate::editor::state::{
-    EditorRoot, MaterialDef, Modal, ParticleSystemDef, PendingDelete, RenameTarget, ResourceKind, ResourceTab,
-    Selection,
+    ClickMode, DragPayload, EditorRoot, MaterialDef, Modal, ParticleSystemDef, PendingDelete, RenameTarget,
+    ResourceKind, ResourceTab, Selection,
 };
 use crate::project;
 
@@ -24,7 +26,7 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
     ui.horizontal(|ui| {
         for tab in [
             ResourceTab::Models, ResourceTab::Textures, ResourceTab::Shaders,
-            ResourceTab::Materials, ResourceTab::Particles, ResourceTab::Scenes,
+            ResourceTab::Materials, ResourceTab::Particles, ResourceTab::Scripts, ResourceTab::Scenes,
             ResourceTab::Audio, ResourceTab::Other,
         ] {
             if ui.selectable_label(current_tab == tab, format!("{tab:?}")).clicked() {
@@ -51,6 +53,7 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
                 ResourceTab::Other => list_kind(ui, app_state, ResourceKind::Other),
                 ResourceTab::Materials => list_materials(ui, app_state),
                 ResourceTab::Particles => list_particles(ui, app_state),
+                ResourceTab::Scripts => list_scripts(ui, app_state),
                 ResourceTab::Scenes => list_scenes(ui, app_state),
             }
         });
@@ -66,7 +69,7 @@ fn list_kind(ui: &mut Ui, app_state: &mut AppState, kind: ResourceKind) {
             .filter(|e| e.kind == kind)
             .map(|e| (e.uuid, e.name.clone()))
             .collect()).unwrap_or_default();
-        (rows, root.editor.selection.clone(), root.editor.renaming.clone())
+        (rows, root.editor.selection.primary().clone(), root.editor.renaming.clone())
     };
 
     let mut new_sel: Option<Selection> = None;
@@ -74,6 +77,7 @@ fn list_kind(ui: &mut Ui, app_state: &mut AppState, kind: ResourceKind) {
     let mut rename_start: Option<RenameTarget> = None;
     let mut rename_commit: Option<RenameTarget> = None;
     let mut rename_cancel = false;
+    let mut drag_start: Option<DragPayload> = None;
 
     for (uuid, name) in &rows {
         let selected = matches!(&current_sel, Selection::Resource(u) if u == uuid);
@@ -97,8 +101,13 @@ fn list_kind(ui: &mut Ui, app_state: &mut AppState, kind: ResourceKind) {
                     }
                 }
             } else {
-                let resp = ui.selectable_label(selected, name);
+                // Models can be dragged into the viewport.
+                let mut resp = ui.selectable_label(selected, name);
+                if kind == ResourceKind::Model {
+                    resp = resp.interact(egui::Sense::drag()).on_hover_text("Drag into the viewport to place");
+                }
                 if resp.clicked() { new_sel = Some(Selection::Resource(*uuid)); }
+                if resp.drag_started() { drag_start = Some(DragPayload::Resource(*uuid)); }
                 if resp.double_clicked() {
                     rename_start = Some(RenameTarget::Resource { uuid: *uuid, draft: name.clone() });
                 }
@@ -115,7 +124,9 @@ fn list_kind(ui: &mut Ui, app_state: &mut AppState, kind: ResourceKind) {
             if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
                 if let Some(project) = root.project.as_mut() {
                     if let Err(e) = project::resource::import(std::path::Path::new(&src), kind, project) {
-                        eprintln!("import failed: {e:?}");
+                        let message = format!("Could not import {src}: {e:?}");
+                        logging::error(Source::Import, message.clone());
+                        root.editor.modal = Some(Modal::ImportError(message));
                     } else {
                         root.editor.dirty = true;
                     }
@@ -124,6 +135,7 @@ fn list_kind(ui: &mut Ui, app_state: &mut AppState, kind: ResourceKind) {
         }
     }
 
+    if let Some(payload) = drag_start { drag_drop::start(app_state, payload); }
     finalize(app_state, new_sel, delete, "resource", rename_start, rename_commit, rename_cancel);
 }
 
@@ -140,15 +152,18 @@ fn list_materials(ui: &mut Ui, app_state: &mut AppState) {
             .unwrap_or_default();
         (rows, root.editor.selection.clone(), root.editor.renaming.clone())
     };
+    // Materials can be selected together to edit them at once.
+    let click_mode = ui.input(|i| ClickMode::from_modifiers(i.modifiers));
 
     let mut new_sel: Option<Selection> = None;
     let mut delete: Option<PendingDelete> = None;
     let mut rename_start: Option<RenameTarget> = None;
     let mut rename_commit: Option<RenameTarget> = None;
     let mut rename_cancel = false;
+    let mut drag_start: Option<DragPayload> = None;
 
     for (uuid, name) in &rows {
-        let selected = matches!(&current_sel, Selection::Material(u) if u == uuid);
+        let selected = current_sel.contains(&Selection::Material(*uuid));
         let renaming_this = matches!(&renaming, Some(RenameTarget::Material { uuid: u, .. }) if u == uuid);
         ui.horizontal(|ui| {
             if renaming_this {
@@ -169,8 +184,10 @@ fn list_materials(ui: &mut Ui, app_state: &mut AppState) {
                     }
                 }
             } else {
-                let resp = ui.selectable_label(selected, name);
+                let resp = ui.selectable_label(selected, name).interact(egui::Sense::drag())
+                    .on_hover_text("Drag onto an object in the viewport to assign; Ctrl-click to select several");
                 if resp.clicked() { new_sel = Some(Selection::Material(*uuid)); }
+                if resp.drag_started() { drag_start = Some(DragPayload::Material(*uuid)); }
                 if resp.double_clicked() {
                     rename_start = Some(RenameTarget::Material { uuid: *uuid, draft: name.clone() });
                 }
@@ -188,13 +205,19 @@ fn list_materials(ui: &mut Ui, app_state: &mut AppState) {
                 let mat = MaterialDef::default_pbr(name);
                 let uuid = mat.uuid;
                 project.materials.push(mat);
-                root.editor.selection = Selection::Material(uuid);
+                root.editor.selection.set(Selection::Material(uuid));
                 root.editor.dirty = true;
             }
         }
     }
 
-    finalize(app_state, new_sel, delete, "material", rename_start, rename_commit, rename_cancel);
+    if let Some(payload) = drag_start { drag_drop::start(app_state, payload); }
+    if let Some(sel) = new_sel {
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            root.editor.selection.click(sel, click_mode);
+        }
+    }
+    finalize(app_state, None, delete, "material", rename_start, rename_commit, rename_cancel);
 }
 
 fn list_particles(ui: &mut Ui, app_state: &mut AppState) {
@@ -206,7 +229,7 @@ fn list_particles(ui: &mut Ui, app_state: &mut AppState) {
         let rows: Vec<(Uuid, String)> = root.project.as_ref()
             .map(|p| p.particle_systems.iter().map(|m| (m.uuid, m.config.name.clone())).collect())
             .unwrap_or_default();
-        (rows, root.editor.selection.clone(), root.editor.renaming.clone())
+        (rows, root.editor.selection.primary().clone(), root.editor.renaming.clone())
     };
 
     let mut new_sel: Option<Selection> = None;
@@ -214,6 +237,7 @@ fn list_particles(ui: &mut Ui, app_state: &mut AppState) {
     let mut rename_start: Option<RenameTarget> = None;
     let mut rename_commit: Option<RenameTarget> = None;
     let mut rename_cancel = false;
+    let mut drag_start: Option<DragPayload> = None;
 
     for (uuid, name) in &rows {
         let selected = matches!(&current_sel, Selection::Particle(u) if u == uuid);
@@ -236,8 +260,10 @@ fn list_particles(ui: &mut Ui, app_state: &mut AppState) {
                     }
                 }
             } else {
-                let resp = ui.selectable_label(selected, name);
+                let resp = ui.selectable_label(selected, name).interact(egui::Sense::drag())
+                    .on_hover_text("Drag into the viewport to place an emitter");
                 if resp.clicked() { new_sel = Some(Selection::Particle(*uuid)); }
+                if resp.drag_started() { drag_start = Some(DragPayload::Particle(*uuid)); }
                 if resp.double_clicked() {
                     rename_start = Some(RenameTarget::Particle { uuid: *uuid, draft: name.clone() });
                 }
@@ -255,15 +281,52 @@ fn list_particles(ui: &mut Ui, app_state: &mut AppState) {
                 let def = ParticleSystemDef::new_default(name);
                 let uuid = def.uuid;
                 project.particle_systems.push(def);
-                root.editor.selection = Selection::Particle(uuid);
+                root.editor.selection.set(Selection::Particle(uuid));
                 root.editor.dirty = true;
             }
         }
     }
 
+    if let Some(payload) = drag_start { drag_drop::start(app_state, payload); }
     finalize(app_state, new_sel, delete, "particle system", rename_start, rename_commit, rename_cancel);
 }
 
+fn list_scripts(ui: &mut Ui, app_state: &mut AppState) {
+    let mut new_clicked = false;
+    if ui.button("+ New Script").clicked() { new_clicked = true; }
+
+    let (rows, current_sel) = {
+        let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
+        let rows: Vec<(Uuid, String, String)> = root.project.as_ref()
+            .map(|p| p.scripts.iter().map(|s| (s.uuid, s.name.clone(), s.relative_path.clone())).collect())
+            .unwrap_or_default();
+        (rows, root.editor.selection.primary().clone())
+    };
+
+    let mut new_sel: Option<Selection> = None;
+    let mut delete: Option<PendingDelete> = None;
+
+    // No inline rename: the name is the Rust type, renaming means editing code.
+    for (uuid, name, path) in &rows {
+        let selected = matches!(&current_sel, Selection::Script(u) if u == uuid);
+        ui.horizontal(|ui| {
+            let resp = ui.selectable_label(selected, name).on_hover_text(path);
+            if resp.clicked() { new_sel = Some(Selection::Script(*uuid)); }
+            if ui.small_button("×").on_hover_text("Delete").clicked() {
+                delete = Some(PendingDelete::Script(*uuid));
+            }
+        });
+    }
+
+    if new_clicked {
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            root.editor.modal = Some(Modal::NewScriptName(String::new()));
+        }
+    }
+
+    finalize(app_state, new_sel, delete, "script", None, None, false);
+}
+
 fn list_scenes(ui: &mut Ui, app_state: &mut AppState) {
     let mut new_clicked = false;
     if ui.button("+ New Scene").clicked() { new_clicked = true; }
@@ -328,7 +391,7 @@ fn list_scenes(ui: &mut Ui, app_state: &mut AppState) {
             if let Some(proj) = root.project.as_mut() {
                 let mut proj_clone = proj.clone();
                 if let Err(e) = crate::project::scene::switch(&mut proj_clone, app_state, idx) {
-                    eprintln!("scene switch failed: {e:?}");
+                    logging::error(Source::Scene, format!("Switching scenes failed: {e:?}"));
                 } else if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
                     if let Some(p) = root.project.as_mut() {
                         *p = proj_clone;
@@ -357,7 +420,7 @@ fn finalize(
     }
     if let Some(s) = new_sel {
         if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-            root.editor.selection = s;
+            root.editor.selection.set(s);
         }
     }
     if rename_cancel {
@@ -0,0 +1,130 @@
+use egui::Context;
+use enigma_3d::AppState;
+use uuid::Uuid;
+
+use crate::editor::state::{EditorRoot, RunConfig, RunScene};
+
+/// Window for creating and editing the project's run configurations.
+pub fn draw(ctx: &Context, app_state: &mut AppState) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    if !root.editor.show_run_configs {
+        return;
+    }
+    let Some(project) = root.project.as_mut() else { return; };
+    let scenes: Vec<(Uuid, String)> = project.scenes.iter().map(|s| (s.uuid, s.name.clone())).collect();
+
+    let mut open = true;
+    let mut changed = false;
+    let mut remove: Option<Uuid> = None;
+    egui::Window::new("Run Configurations")
+        .open(&mut open)
+        .resizable(true)
+        .default_width(460.0)
+        .show(ctx, |ui| {
+            if ui.button("+ New Configuration").clicked() {
+                let config = RunConfig::new(format!("Config {}", project.run_configs.len() + 1));
+                project.active_run_config = Some(config.uuid);
+                project.run_configs.push(config);
+                changed = true;
+            }
+            ui.separator();
+            egui::ScrollArea::vertical().auto_shrink([false, true]).show(ui, |ui| {
+                for config in project.run_configs.iter_mut() {
+                    ui.push_id(config.uuid, |ui| {
+                        changed |= config_editor(ui, config, &scenes, &mut remove);
+                    });
+                    ui.separator();
+                }
+            });
+        });
+
+    if let Some(uuid) = remove {
+        project.run_configs.retain(|c| c.uuid != uuid);
+        if project.active_run_config == Some(uuid) {
+            project.active_run_config = None;
+        }
+        changed = true;
+    }
+    if changed {
+        root.editor.dirty = true;
+    }
+    if !open {
+        root.editor.show_run_configs = false;
+    }
+}
+
+fn config_editor(ui: &mut egui::Ui, config: &mut RunConfig, scenes: &[(Uuid, String)], remove: &mut Option<Uuid>) -> bool {
+    let mut changed = false;
+    egui::CollapsingHeader::new(&config.name).default_open(true).show(ui, |ui| {
+        egui::Grid::new("run_config_grid").num_columns(2).show(ui, |ui| {
+            ui.label("Name");
+            changed |= ui.text_edit_singleline(&mut config.name).changed();
+            ui.end_row();
+
+            ui.label("Profile");
+            changed |= ui.add(egui::TextEdit::singleline(&mut config.profile).hint_text("dev")).changed();
+            ui.end_row();
+
+            ui.label("Features");
+            changed |= ui.add(egui::TextEdit::singleline(&mut config.features).hint_text("feature_a, feature_b")).changed();
+            ui.end_row();
+
+            ui.label("Arguments");
+            changed |= ui.add(egui::TextEdit::singleline(&mut config.args).hint_text("--level 2 \"player one\"")).changed();
+            ui.end_row();
+
+            ui.label("Scene");
+            let current = match config.scene {
+                RunScene::Startup => "Startup scene".to_string(),
+                RunScene::Active => "Active scene".to_string(),
+                RunScene::Scene(uuid) => scenes.iter().find(|(u, _)| *u == uuid)
+                    .map(|(_, n)| n.clone())
+                    .unwrap_or_else(|| "(missing)".into()),
+            };
+            egui::ComboBox::from_id_source("run_config_scene").selected_text(current).show_ui(ui, |ui| {
+                if ui.selectable_label(config.scene == RunScene::Startup, "Startup scene").clicked() {
+                    config.scene = RunScene::Startup;
+                    changed = true;
+                }
+                if ui.selectable_label(config.scene == RunScene::Active, "Active scene").clicked() {
+                    config.scene = RunScene::Active;
+                    changed = true;
+                }
+                for (uuid, name) in scenes {
+                    if ui.selectable_label(config.scene == RunScene::Scene(*uuid), name).clicked() {
+                        config.scene = RunScene::Scene(*uuid);
+                        changed = true;
+                    }
+                }
+            });
+            ui.end_row();
+        });
+
+        ui.label("Environment");
+        let mut remove_var: Option<usize> = None;
+        for (idx, (key, value)) in config.env.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                changed |= ui.add(egui::TextEdit::singleline(key).hint_text("NAME").desired_width(140.0)).changed();
+                ui.label("=");
+                changed |= ui.add(egui::TextEdit::singleline(value).hint_text("value").desired_width(180.0)).changed();
+                if ui.small_button("×").clicked() {
+                    remove_var = Some(idx);
+                }
+            });
+        }
+        if let Some(idx) = remove_var {
+            config.env.remove(idx);
+            changed = true;
+        }
+        ui.horizontal(|ui| {
+            if ui.small_button("+ Variable").clicked() {
+                config.env.push((String::new(), String::new()));
+                changed = true;
+            }
+            if ui.small_button("Delete Configuration").clicked() {
+                *remove = Some(config.uuid);
+            }
+        });
+    });
+    changed
+}
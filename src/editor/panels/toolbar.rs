@@ -2,58 +2,57 @@ use egui::Ui;
 use enigma_3d::AppState;
 use rfd::FileDialog;
 
-use crate::editor::actions;
-use crate::editor::state::{EditorRoot, Modal, ProjectState};
-use crate::project;
+use crate::editor::commands::{self, CommandId};
+use crate::editor::dock::{self, Tab};
+use crate::editor::{actions, diagnostics, live_link, play};
+use crate::editor::state::{EditorRoot, JobKind, ProjectState};
 
 pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
+    let playing = play::is_playing(app_state);
     ui.horizontal(|ui| {
-        ui.menu_button("File", |ui| {
-            if ui.button("New Project").clicked() {
-                if let Some(path) = pick_folder() {
-                    if let Err(e) = project::try_new_project(&path, app_state) {
-                        eprintln!("new project failed: {e}");
-                    }
-                }
-                ui.close_menu();
-            }
-            if ui.button("Open Project").clicked() {
-                if let Some(path) = pick_file("json") {
-                    project::start_open_project(&path, app_state);
-                }
-                ui.close_menu();
-            }
-            if ui.button("Save Project").clicked() {
-                project::start_save_project_only(app_state);
-                ui.close_menu();
-            }
-            if ui.button("Save Scene").clicked() {
-                project::start_save_scene_and_project(app_state);
-                ui.close_menu();
-            }
+        // Loading, saving or switching scenes mid-play would mix play state
+        // into the project; Stop first.
+        ui.add_enabled_ui(!playing, |ui| {
+            file_menu(ui, app_state);
+            scene_menu(ui, app_state);
         });
-
-        scene_menu(ui, app_state);
+        dock::menu(ui, app_state);
 
         let project_loaded = current_project_clone(app_state).is_some();
         let busy = actions::is_busy(app_state);
-        let enabled = project_loaded && !busy;
 
-        ui.add_enabled_ui(enabled, |ui| {
-            if ui.button("Play").clicked() {
+        play_controls(ui, app_state, project_loaded);
+        ui.separator();
+
+        // While a job runs these queue behind it instead.
+        let queue_hint = if busy { "Queued after the current job" } else { "" };
+        ui.add_enabled_ui(project_loaded && !playing, |ui| {
+            if ui.button("Run").on_hover_text(if busy { queue_hint } else { "Build and run the game with cargo" }).clicked() {
                 actions::run_project(app_state);
             }
-            if ui.button("Debug Build").clicked() {
+            run_config_picker(ui, app_state);
+            if ui.button("Debug Build").on_hover_text(queue_hint).clicked() {
                 actions::build_project(app_state, false);
             }
-            if ui.button("Release Build").clicked() {
+            if ui.button("Release Build").on_hover_text(queue_hint).clicked() {
                 actions::build_project(app_state, true);
             }
-            if ui.button("Update Dependencies").clicked() {
+            if ui.button("Update Dependencies").on_hover_text(queue_hint).clicked() {
                 actions::update_dependencies(app_state);
             }
+            queue_menu(ui, app_state);
         });
+        if busy && ui.button("Cancel").on_hover_text("Stop cargo and the game").clicked() {
+            actions::cancel_job(app_state);
+        }
+        if ui.add_enabled(project_loaded, egui::Button::new("History")).clicked() {
+            if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+                root.editor.show_job_history = !root.editor.show_job_history;
+            }
+        }
 
+        let mut toggle_problems = false;
+        let mut toggle_live_link = false;
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
             if let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") {
                 if let Some(job) = root.editor.job.as_ref() {
@@ -65,6 +64,17 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
                     let dur = last.duration.as_secs_f32();
                     ui.label(format!("{} {} ({:.1}s)", marker, last.label, dur));
                 }
+                if live_link::is_connected(app_state) {
+                    toggle_live_link = ui.link("🔗 Live")
+                        .on_hover_text("The running game receives edits. Click for its object state.")
+                        .clicked();
+                }
+                let (errors, warnings) = diagnostics::counts(&root.editor.problems);
+                if errors + warnings > 0 {
+                    toggle_problems = ui.link(format!("⛔ {errors}  ⚠ {warnings}"))
+                        .on_hover_text("Show problems")
+                        .clicked();
+                }
                 if let Some(p) = &root.project {
                     if root.editor.dirty {
                         ui.label("•");
@@ -78,24 +88,149 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
                 }
             }
         });
+        if toggle_problems {
+            if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+                root.editor.layout.dock.toggle(Tab::Problems);
+            }
+        }
+        if toggle_live_link {
+            if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+                root.editor.show_live_link = !root.editor.show_live_link;
+            }
+        }
+    });
+}
+
+fn file_menu(ui: &mut Ui, app_state: &mut AppState) {
+    ui.menu_button("File", |ui| {
+        command_button(ui, app_state, CommandId::NewProject);
+        command_button(ui, app_state, CommandId::OpenProject);
+        command_button(ui, app_state, CommandId::SaveProject);
+        command_button(ui, app_state, CommandId::SaveScene);
+        ui.separator();
+        command_button(ui, app_state, CommandId::CommandPalette);
+        command_button(ui, app_state, CommandId::KeyboardShortcuts);
+        command_button(ui, app_state, CommandId::Preferences);
+    });
+}
+
+/// A menu entry that runs a registered command and shows its shortcut.
+fn command_button(ui: &mut Ui, app_state: &mut AppState, id: CommandId) {
+    let button = egui::Button::new(commands::find(id).label)
+        .shortcut_text(commands::shortcut_text(app_state, id));
+    if ui.add_enabled(commands::available(app_state, id), button).clicked() {
+        ui.close_menu();
+        commands::run(app_state, id);
+    }
+}
+
+fn run_config_picker(ui: &mut Ui, app_state: &mut AppState) {
+    let Some(project) = current_project_clone(app_state) else { return; };
+    let current = project.active_run_config
+        .and_then(|uuid| project.run_configs.iter().find(|c| c.uuid == uuid))
+        .map(|c| c.name.clone())
+        .unwrap_or_else(|| "Default".into());
+    let mut picked: Option<Option<uuid::Uuid>> = None;
+    let mut edit = false;
+    egui::ComboBox::from_id_source("run_config")
+        .selected_text(current)
+        .show_ui(ui, |ui| {
+            if ui.selectable_label(project.active_run_config.is_none(), "Default")
+                .on_hover_text("cargo run on the startup scene")
+                .clicked()
+            {
+                picked = Some(None);
+            }
+            for config in &project.run_configs {
+                let selected = project.active_run_config == Some(config.uuid);
+                if ui.selectable_label(selected, &config.name).clicked() {
+                    picked = Some(Some(config.uuid));
+                }
+            }
+            ui.separator();
+            if ui.button("Edit Configurations…").clicked() {
+                edit = true;
+            }
+        });
+    if picked.is_none() && !edit { return; }
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    if edit {
+        root.editor.show_run_configs = true;
+    }
+    if let (Some(choice), Some(project)) = (picked, root.project.as_mut()) {
+        project.active_run_config = choice;
+        root.editor.dirty = true;
+    }
+}
+
+fn queue_menu(ui: &mut Ui, app_state: &mut AppState) {
+    let queued: Vec<JobKind> = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| r.editor.job_queue.iter().copied().collect())
+        .unwrap_or_default();
+    let title = if queued.is_empty() { "Queue".to_string() } else { format!("Queue ({})", queued.len()) };
+    ui.menu_button(title, |ui| {
+        let presets = [
+            ("Release Build, then Run", [JobKind::Build { release: true }, JobKind::Run]),
+            ("Update Dependencies, then Debug Build", [JobKind::UpdateDependencies, JobKind::Build { release: false }]),
+        ];
+        for (label, jobs) in presets {
+            if ui.button(label).clicked() {
+                for kind in jobs {
+                    actions::submit(app_state, kind);
+                }
+                ui.close_menu();
+            }
+        }
+        if !queued.is_empty() {
+            ui.separator();
+            for (idx, kind) in queued.iter().enumerate() {
+                ui.label(format!("{}. {}", idx + 1, kind.label()));
+            }
+            if ui.button("Clear Queue").clicked() {
+                if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+                    root.editor.job_queue.clear();
+                }
+                ui.close_menu();
+            }
+        }
     });
 }
 
+fn play_controls(ui: &mut Ui, app_state: &mut AppState, project_loaded: bool) {
+    let session = app_state.get_state_data_value::<EditorRoot>("editor")
+        .and_then(|r| r.editor.play.as_ref().map(|s| (s.paused, s.elapsed, s.frames)));
+    match session {
+        None => {
+            let play = ui.add_enabled(project_loaded, egui::Button::new("▶ Play"))
+                .on_hover_text("Simulate the scene in the viewport. Stop restores it.");
+            if play.clicked() {
+                play::start(app_state);
+            }
+        }
+        Some((paused, elapsed, frames)) => {
+            if ui.button("■ Stop").clicked() {
+                play::stop(app_state);
+                return;
+            }
+            let label = if paused { "▶ Resume" } else { "⏸ Pause" };
+            if ui.button(label).clicked() {
+                play::set_paused(app_state, !paused);
+            }
+            if ui.add_enabled(paused, egui::Button::new("⏭ Step")).on_hover_text("Advance one frame").clicked() {
+                play::request_step(app_state);
+            }
+            ui.label(format!("{elapsed:.2}s · frame {frames}"));
+        }
+    }
+}
+
 fn scene_menu(ui: &mut Ui, app_state: &mut AppState) {
     let project_clone = current_project_clone(app_state);
     let Some(project) = project_clone else { return; };
 
     ui.menu_button("Scene", |ui| {
-        if ui.button("Save Scene").clicked() {
-            project::start_save_scene_and_project(app_state);
-            ui.close_menu();
-        }
-        if ui.button("New Scene…").clicked() {
-            if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-                root.editor.modal = Some(Modal::NewSceneName(String::new()));
-            }
-            ui.close_menu();
-        }
+        command_button(ui, app_state, CommandId::SaveScene);
+        command_button(ui, app_state, CommandId::NewScene);
         let mut switch_to: Option<usize> = None;
         ui.menu_button("Switch Scene", |ui| {
             for (idx, s) in project.scenes.iter().enumerate() {
@@ -109,18 +244,7 @@ fn scene_menu(ui: &mut Ui, app_state: &mut AppState) {
             }
         });
         if let Some(idx) = switch_to {
-            if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-                if let Some(proj) = root.project.as_mut() {
-                    let mut proj_clone = proj.clone();
-                    if let Err(e) = crate::project::scene::switch(&mut proj_clone, app_state, idx) {
-                        eprintln!("scene switch failed: {e:?}");
-                    } else if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-                        if let Some(p) = root.project.as_mut() {
-                            *p = proj_clone;
-                        }
-                    }
-                }
-            }
+            actions::switch_scene(app_state, idx);
         }
         if ui.button("Set Current as Startup").clicked() {
             if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
@@ -139,13 +263,13 @@ fn current_project_clone(app_state: &mut AppState) -> Option<ProjectState> {
 }
 
 
-fn pick_folder() -> Option<String> {
+pub fn pick_folder() -> Option<String> {
     FileDialog::new()
         .pick_folder()
         .map(|p| p.to_string_lossy().into_owned())
 }
 
-fn pick_file(filter: &str) -> Option<String> {
+pub fn pick_file(filter: &str) -> Option<String> {
     FileDialog::new()
         .add_filter(filter, &[filter])
         .pick_file()
@@ -1,17 +1,15 @@
-use egui::Ui;
+use egui::{Pos2, Rect, Ui};
 use enigma_3d::AppState;
-use enigma_3d::collision_world::RayCast;
-use enigma_3d::camera::Camera;
 use nalgebra::Vector3;
 
 use crate::editor::gizmo::math;
-use crate::editor::state::{EditorRoot, Selection};
+use crate::editor::{bookmarks, cameras, commands, cursor_3d, drag_drop, lights, navigation, picking, visibility};
+use crate::editor::state::{ClickMode, EditorRoot, Selection};
 
-const FLY_SPEED: f32 = 4.0;          // world units per second
 const FAST_MULT: f32 = 4.0;          // shift-held multiplier
-const LOOK_SENSITIVITY: f32 = 0.005; // radians per pixel
 const PAN_SENSITIVITY: f32 = 0.01;
 const WHEEL_DOLLY: f32 = 0.5;        // units per scroll-line
+const MARQUEE_MIN: f32 = 4.0;        // pixels of drag before a click becomes a box select
 
 pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
     let rect = ui.max_rect();
@@ -20,13 +18,19 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
     }
 
     let ctx = ui.ctx();
+    // Toolbars and the view cube float above the viewport and keep their clicks.
     let pointer_in_rect = ctx.input(|i| i.pointer.interact_pos())
-        .map(|p| rect.contains(p))
+        .map(|p| rect.contains(p) && ctx.layer_id_at(p).is_none_or(|l| l.order != egui::Order::Foreground))
         .unwrap_or(false);
 
     let rmb_down = ctx.input(|i| i.pointer.secondary_down());
     let mmb_down = ctx.input(|i| i.pointer.middle_down());
-    let any_drag = rmb_down || mmb_down;
+    let orbiting = ctx.input(|i| i.modifiers.alt && i.pointer.primary_down())
+        && (pointer_in_rect || app_state.get_state_data_value::<EditorRoot>("editor").is_some_and(|r| r.editor.orbiting));
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.orbiting = orbiting;
+    }
+    let any_drag = rmb_down || mmb_down || orbiting;
 
     if pointer_in_rect || any_drag {
         update_camera(ctx, app_state, pointer_in_rect);
@@ -35,41 +39,132 @@ pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
         }
     }
 
+    commands::handle_shortcuts(ctx, app_state, pointer_in_rect);
+    if any_drag {
+        // Taking the camera by hand cancels a glide to a bookmark.
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            root.editor.camera_transition = None;
+        }
+    }
+    if bookmarks::tick(app_state) {
+        ctx.request_repaint();
+    }
+
+    if orbiting {
+        let delta = ctx.input(|i| i.pointer.delta());
+        navigation::orbit(app_state, delta);
+    }
+
     // Gizmo input runs before click-to-select so it can claim mouse-down/up.
-    crate::editor::gizmo::handle_input(ctx, rect, app_state);
+    if !orbiting {
+        crate::editor::gizmo::handle_input(ctx, rect, app_state);
+    }
 
-    let primary_released = ctx.input(|i| i.pointer.primary_released());
-    if primary_released && pointer_in_rect && !any_drag {
-        let Some(pos) = ctx.input(|i| i.pointer.interact_pos()) else { return; };
-
-        let (drag_active, gizmo_consumed) = app_state
-            .get_state_data_value_mut::<EditorRoot>("editor")
-            .map(|r| {
-                (
-                    r.editor.drag.is_some(),
-                    std::mem::replace(&mut r.editor.gizmo.consumed_click_this_frame, false),
-                )
-            })
-            .unwrap_or((false, false));
-        if drag_active || gizmo_consumed { return; }
-
-        let Some(camera) = app_state.camera.as_ref() else { return; };
-        let (origin, dir) = math::unproject(camera, pos, ctx.screen_rect());
-        let length = camera.far;
-
-        let mut ray = RayCast::new(origin, dir, length);
-        ray.cast(app_state);
-
-        let new_selection = ray.get_intersection_uuids().first().copied()
-            .map(Selection::SceneObject)
-            .unwrap_or(Selection::None);
+    let mode = ctx.input(|i| ClickMode::from_modifiers(i.modifiers));
+    let primary_pressed = ctx.input(|i| i.pointer.primary_pressed());
+    if primary_pressed && pointer_in_rect && !any_drag {
+        // A press the gizmo didn't take may become a box selection.
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            if root.editor.gizmo.drag.is_none() && root.editor.drag.is_none() {
+                root.editor.marquee = ctx.input(|i| i.pointer.interact_pos());
+            }
+        }
+    }
+
+    let marquee = app_state.get_state_data_value::<EditorRoot>("editor").and_then(|r| r.editor.marquee);
+    let pointer = ctx.input(|i| i.pointer.interact_pos());
+    let marquee_rect = match (marquee, pointer) {
+        (Some(start), Some(pos)) if (pos - start).length() > MARQUEE_MIN => Some(Rect::from_two_pos(start, pos)),
+        _ => None,
+    };
 
+    let primary_released = ctx.input(|i| i.pointer.primary_released());
+    if primary_released {
         if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
-            root.editor.selection = new_selection;
+            root.editor.marquee = None;
+        }
+        if let Some(area) = marquee_rect {
+            box_select(ctx, app_state, area, mode);
+        } else if pointer_in_rect && !any_drag {
+            if let Some(pos) = pointer {
+                click_select(ctx, app_state, pos, mode);
+            }
         }
+    } else if let Some(area) = marquee_rect {
+        let painter = ui.painter_at(rect);
+        let color = egui::Color32::from_rgb(90, 160, 255);
+        painter.rect_filled(area, 0.0, color.gamma_multiply(0.15));
+        painter.rect_stroke(area, 0.0, egui::Stroke::new(1.0, color));
+        ctx.request_repaint();
     }
 
+    drag_drop::update(ctx, app_state, pointer_in_rect);
+    cursor_3d::handle_click(ctx, app_state, pointer_in_rect);
+
+    cameras::draw_frustum(ui, rect, app_state);
+    cursor_3d::draw(ui, rect, app_state);
+    picking::draw(ui, rect, app_state);
     crate::editor::gizmo::draw(ui, rect, app_state);
+    navigation::draw_view_cube(&ui.ctx().clone(), rect, app_state);
+}
+
+fn click_select(ctx: &egui::Context, app_state: &mut AppState, pos: Pos2, mode: ClickMode) {
+    let (drag_active, gizmo_consumed) = app_state
+        .get_state_data_value_mut::<EditorRoot>("editor")
+        .map(|r| {
+            (
+                r.editor.drag.is_some(),
+                std::mem::replace(&mut r.editor.gizmo.consumed_click_this_frame, false),
+            )
+        })
+        .unwrap_or((false, false));
+    if drag_active || gizmo_consumed { return; }
+
+    let hit = picking::pick(app_state, ctx.screen_rect(), pos);
+
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    match hit {
+        Some(item) => root.editor.selection.click(item, mode),
+        // Clicking empty space only clears a plain selection.
+        None if mode == ClickMode::Replace => root.editor.selection.set(Selection::None),
+        None => {}
+    }
+}
+
+/// Select every object, light and particle instance whose pivot projects
+/// into `area`.
+fn box_select(ctx: &egui::Context, app_state: &mut AppState, area: Rect, mode: ClickMode) {
+    let Some(camera) = app_state.camera.as_ref() else { return; };
+    let screen = ctx.screen_rect();
+    let inside = |p: Vector3<f32>| math::world_to_screen(camera, screen, p).map(|s| area.contains(s)).unwrap_or(false);
+
+    let mut hits: Vec<Selection> = app_state.objects.iter()
+        .filter(|o| inside(o.transform.position) && !visibility::is_locked(app_state, o.get_unique_id()))
+        .map(|o| Selection::SceneObject(o.get_unique_id()))
+        .collect();
+    hits.extend(app_state.light.iter().enumerate()
+        .filter(|(_, l)| inside(Vector3::from(l.position)))
+        .filter_map(|(i, _)| lights::uuid_at(app_state, i))
+        .map(Selection::Light));
+    if let Some(scene) = app_state.get_state_data_value::<EditorRoot>("editor")
+        .and_then(|r| r.project.as_ref())
+        .and_then(|p| p.scenes.get(p.active_scene_index))
+    {
+        hits.extend(scene.particle_instances.iter()
+            .filter(|i| inside(Vector3::from(i.position)))
+            .map(|i| Selection::ParticleInstance(i.uuid)));
+    }
+
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    if mode == ClickMode::Replace {
+        root.editor.selection.set(Selection::None);
+    }
+    for hit in hits {
+        match mode {
+            ClickMode::Toggle => root.editor.selection.toggle(hit),
+            _ => root.editor.selection.add(hit),
+        }
+    }
 }
 
 fn update_camera(ctx: &egui::Context, app_state: &mut AppState, pointer_in_rect: bool) {
@@ -80,17 +175,17 @@ fn update_camera(ctx: &egui::Context, app_state: &mut AppState, pointer_in_rect:
     let wheel = ctx.input(|i| i.scroll_delta.y);
     let shift = ctx.input(|i| i.modifiers.shift);
 
-    // Press F to frame selected — requires pointer in viewport.
-    let frame_pressed = pointer_in_rect && ctx.input(|i| i.key_pressed(egui::Key::F));
-
+    let fly = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| r.editor.navigation.fly)
+        .unwrap_or_default();
     let Some(cam) = app_state.camera.as_mut() else { return; };
 
     // Look: RMB drag rotates camera in place.
     if rmb_down && (mouse_delta.x.abs() > 0.0 || mouse_delta.y.abs() > 0.0) {
         // Camera rotation is stored as radian Euler (X, Y, Z) where Y is yaw
         // and X is pitch. calculate_direction_vector uses [pitch, yaw, _].
-        let yaw_delta = -mouse_delta.x * LOOK_SENSITIVITY;
-        let pitch_delta = -mouse_delta.y * LOOK_SENSITIVITY;
+        let yaw_delta = -mouse_delta.x * fly.look_sensitivity;
+        let pitch_delta = -mouse_delta.y * fly.look_sensitivity;
         cam.transform.rotation.y += yaw_delta;
         cam.transform.rotation.x = (cam.transform.rotation.x + pitch_delta)
             .clamp(-std::f32::consts::FRAC_PI_2 + 0.01, std::f32::consts::FRAC_PI_2 - 0.01);
@@ -113,7 +208,7 @@ fn update_camera(ctx: &egui::Context, app_state: &mut AppState, pointer_in_rect:
     }
 
     if move_vec.norm() > 0.0 {
-        let speed = if shift { FLY_SPEED * FAST_MULT } else { FLY_SPEED };
+        let speed = if shift { fly.speed * FAST_MULT } else { fly.speed };
         let step = move_vec.normalize() * speed * dt;
         cam.transform.position += step;
         cam.update_matrices();
@@ -131,56 +226,16 @@ fn update_camera(ctx: &egui::Context, app_state: &mut AppState, pointer_in_rect:
         cam.update_matrices();
     }
 
-    // Wheel: dolly forward/back along view direction.
-    if wheel.abs() > 0.0 && pointer_in_rect {
-        let forward = Vector3::from(cam.calculate_direction_vector());
-        let step_mul = if shift { FAST_MULT } else { 1.0 };
-        cam.transform.position += forward * (wheel * WHEEL_DOLLY * step_mul * 0.01);
-        cam.update_matrices();
-    }
-
-    if frame_pressed {
-        if let Some(target) = current_selection_position(app_state) {
-            if let Some(cam) = app_state.camera.as_mut() {
-                frame_target(cam, target);
-            }
+    // Looking around leaves the view cube's named view.
+    if rmb_down && mouse_delta != egui::Vec2::ZERO {
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            root.editor.navigation.preset = None;
         }
     }
-}
 
-fn current_selection_position(app_state: &AppState) -> Option<Vector3<f32>> {
-    let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return None; };
-    match &root.editor.selection {
-        Selection::SceneObject(uuid) => {
-            app_state.objects.iter()
-                .find(|o| o.get_unique_id() == *uuid)
-                .map(|o| o.transform.position)
-        }
-        Selection::Light(idx) => {
-            app_state.light.get(*idx).map(|l| Vector3::from(l.position))
-        }
-        Selection::ParticleInstance(uuid) => {
-            root.project.as_ref()
-                .and_then(|p| p.scenes.get(p.active_scene_index))
-                .and_then(|s| s.particle_instances.iter().find(|i| i.uuid == *uuid))
-                .map(|i| Vector3::from(i.position))
-        }
-        _ => None,
+    // Wheel: dolly forward/back along view direction.
+    if wheel.abs() > 0.0 && pointer_in_rect {
+        let step_mul = if shift { FAST_MULT } else { 1.0 };
+        navigation::dolly(app_state, wheel * WHEEL_DOLLY * step_mul * 0.01);
     }
 }
-
-fn frame_target(cam: &mut Camera, target: Vector3<f32>) {
-    // Position camera 5 units back along its current forward, looking at target.
-    let current_forward = Vector3::from(cam.calculate_direction_vector());
-    let distance = 5.0;
-    let new_pos = target - current_forward * distance;
-    cam.transform.position = new_pos;
-    // Aim at target — compute pitch/yaw from (target - new_pos).
-    let look = (target - new_pos).normalize();
-    let yaw = (-look.x).atan2(-look.z);
-    let pitch = look.y.asin();
-    cam.transform.rotation.x = pitch;
-    cam.transform.rotation.y = yaw;
-    cam.transform.rotation.z = 0.0;
-    cam.update_matrices();
-}
@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use egui::Ui;
+use enigma_3d::AppState;
+
+use crate::editor::commands::{self, CommandId};
+use crate::editor::panels::job_history::format_age;
+use crate::editor::state::EditorRoot;
+use crate::project;
+
+/// Shown in place of the viewport while no project is open: new and open
+/// buttons and the recently opened projects, newest first.
+pub fn draw(ui: &mut Ui, app_state: &mut AppState) {
+    commands::handle_shortcuts(ui.ctx(), app_state, false);
+    let recent = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| r.editor.recent_projects.clone())
+        .unwrap_or_default();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let mut run: Option<CommandId> = None;
+    let mut open: Option<String> = None;
+    let mut forget: Option<String> = None;
+    ui.vertical_centered(|ui| {
+        ui.add_space(ui.available_height() * 0.12);
+        ui.heading("Enigma Editor");
+        ui.add_space(12.0);
+        for id in [CommandId::NewProject, CommandId::OpenProject] {
+            let button = egui::Button::new(commands::find(id).label)
+                .shortcut_text(commands::shortcut_text(app_state, id))
+                .min_size(egui::vec2(200.0, 0.0));
+            if ui.add(button).clicked() {
+                run = Some(id);
+            }
+        }
+        ui.add_space(24.0);
+        ui.strong("Recent Projects");
+        ui.add_space(4.0);
+        if recent.is_empty() {
+            ui.weak("Projects you open are listed here.");
+            return;
+        }
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.set_max_width(560.0);
+            egui::Grid::new("recent_projects").num_columns(4).striped(true).show(ui, |ui| {
+                for entry in &recent {
+                    let exists = Path::new(&entry.path).is_file();
+                    let name = ui.add_enabled(exists, egui::Button::new(&entry.name).frame(false))
+                        .on_hover_text(&entry.path)
+                        .on_disabled_hover_text("The project file is gone");
+                    if name.clicked() {
+                        open = Some(entry.path.clone());
+                    }
+                    ui.weak(&entry.path);
+                    ui.weak(format_age(now.saturating_sub(entry.opened_at)));
+                    if ui.small_button("✕").on_hover_text("Remove from the list").clicked() {
+                        forget = Some(entry.path.clone());
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+    });
+
+    if let Some(id) = run {
+        commands::run(app_state, id);
+    }
+    if let Some(path) = open {
+        project::start_open_project(&path, app_state);
+    }
+    if let Some(path) = forget {
+        if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+            root.editor.recent_projects.retain(|r| r.path != path);
+        }
+    }
+}
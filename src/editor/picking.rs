@@ -0,0 +1,230 @@
+//! Viewport picking beyond meshes: billboard icons for lights, particle
+//! emitters and the game camera, and the terrain.
+//!
+//! Every candidate under the cursor gets a distance along the pick ray and
+//! the nearest one wins. Meshes are placed where the ray meets their nearest
+//! triangle; the terrain only approximately (see `terrain_hit`).
+
+use egui::{Color32, Pos2, Rect, Stroke, Ui};
+use enigma_3d::AppState;
+use enigma_3d::camera::Camera;
+use nalgebra::Vector3;
+use uuid::Uuid;
+
+use crate::editor::gizmo::{math, snapping};
+use crate::editor::state::{EditorRoot, Selection, TerrainDef};
+use crate::editor::{cameras, lights, visibility};
+
+/// Icon radius in pixels, for drawing and clicking.
+pub const ICON_RADIUS: f32 = 9.0;
+/// Length of a directional light's arrow in world units.
+const ARROW_LENGTH: f32 = 1.5;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum IconKind {
+    PointLight,
+    DirectionalLight { direction: Vector3<f32> },
+    Emitter,
+    Camera,
+}
+
+#[derive(Clone, Debug)]
+pub struct Icon {
+    pub item: Selection,
+    pub position: Vector3<f32>,
+    pub kind: IconKind,
+    pub color: Color32,
+}
+
+/// Icons for the game camera and every light and particle instance of the
+/// active scene.
+pub fn icons(app_state: &AppState) -> Vec<Icon> {
+    let mut icons: Vec<Icon> = app_state.light.iter().enumerate()
+        .filter_map(|(i, l)| {
+            let uuid = lights::uuid_at(app_state, i)?;
+            let kind = if l.is_directional() {
+                IconKind::DirectionalLight { direction: Vector3::from(l.direction) }
+            } else {
+                IconKind::PointLight
+            };
+            let [r, g, b] = l.color.map(|c| (c.clamp(0.0, 1.0) * 255.0) as u8);
+            Some(Icon { item: Selection::Light(uuid), position: Vector3::from(l.position), kind, color: Color32::from_rgb(r, g, b) })
+        })
+        .collect();
+    if let Some(camera) = cameras::game_camera(app_state) {
+        icons.push(Icon {
+            item: Selection::Camera,
+            position: camera.transform.position,
+            kind: IconKind::Camera,
+            color: Color32::from_gray(220),
+        });
+    }
+    if let Some(scene) = app_state.get_state_data_value::<EditorRoot>("editor")
+        .and_then(|r| r.project.as_ref())
+        .and_then(|p| p.scenes.get(p.active_scene_index))
+    {
+        icons.extend(scene.particle_instances.iter().map(|i| Icon {
+            item: Selection::ParticleInstance(i.uuid),
+            position: Vector3::from(i.position),
+            kind: IconKind::Emitter,
+            color: Color32::from_rgb(255, 170, 60),
+        }));
+    }
+    icons
+}
+
+/// The scene item under `pos`, nearest first: a mesh, an icon or the terrain.
+pub fn pick(app_state: &AppState, screen: Rect, pos: Pos2) -> Option<Selection> {
+    let camera = app_state.camera.as_ref()?;
+    let (origin, dir) = math::unproject(camera, pos, screen);
+    let mut candidates: Vec<(f32, Selection)> = Vec::new();
+
+    // Locked objects are see-through to picking.
+    let locked: Vec<Uuid> = app_state.objects.iter()
+        .map(|o| o.get_unique_id())
+        .filter(|uuid| visibility::is_locked(app_state, *uuid))
+        .collect();
+    if let Some(hit) = snapping::mesh_hit(app_state, &locked, origin, dir) {
+        candidates.push((hit.distance, Selection::SceneObject(hit.object)));
+    }
+
+    for icon in icons(app_state) {
+        let Some(at) = math::world_to_screen(camera, screen, icon.position) else { continue; };
+        if (at - pos).length() <= ICON_RADIUS {
+            candidates.push(((icon.position - origin).dot(&dir), icon.item));
+        }
+    }
+
+    if let Some(depth) = active_terrain(app_state).and_then(|t| terrain_hit(&t, origin, dir)) {
+        candidates.push((depth, Selection::Terrain));
+    }
+
+    nearest(candidates)
+}
+
+/// The active scene's terrain settings, while a terrain is in the scene.
+pub fn active_terrain(app_state: &AppState) -> Option<TerrainDef> {
+    app_state.terrain.as_ref()?;
+    let root = app_state.get_state_data_value::<EditorRoot>("editor")?;
+    let project = root.project.as_ref()?;
+    project.scenes.get(project.active_scene_index)?.terrain.clone()
+}
+
+pub fn nearest(candidates: Vec<(f32, Selection)>) -> Option<Selection> {
+    candidates.into_iter()
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, item)| item)
+}
+
+/// Distance along the ray to the terrain, approximated. The engine builds
+/// the terrain mesh from its own noise and doesn't hand the heights back,
+/// so the surface is taken as the plane at `height_mid` across the
+/// terrain's footprint, kept within its box. Clicks on tall hills or deep
+/// valleys land off the drawn surface, and a mesh close to the ground can
+/// sort in front of or behind the terrain wrongly.
+pub fn terrain_hit(def: &TerrainDef, origin: Vector3<f32>, dir: Vector3<f32>) -> Option<f32> {
+    let p = Vector3::from(def.position);
+    let half = Vector3::new(def.width / 2.0, 0.0, def.depth / 2.0);
+    let min = p - half;
+    let max = p + half + Vector3::new(0.0, def.max_height.max(0.0), 0.0);
+    let (enter, exit) = ray_aabb(origin, dir, min, max)?;
+    let mid = p.y + def.max_height * def.height_mid;
+    let cross = if dir.y.abs() > 1e-6 { (mid - origin.y) / dir.y } else { enter };
+    Some(cross.clamp(enter, exit))
+}
+
+/// Entry and exit distances of a ray through an axis-aligned box, if it hits
+/// in front of the origin.
+pub fn ray_aabb(origin: Vector3<f32>, dir: Vector3<f32>, min: Vector3<f32>, max: Vector3<f32>) -> Option<(f32, f32)> {
+    let mut enter = 0.0f32;
+    let mut exit = f32::INFINITY;
+    for axis in 0..3 {
+        if dir[axis].abs() < 1e-8 {
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let a = (min[axis] - origin[axis]) / dir[axis];
+        let b = (max[axis] - origin[axis]) / dir[axis];
+        enter = enter.max(a.min(b));
+        exit = exit.min(a.max(b));
+    }
+    (enter <= exit).then_some((enter, exit))
+}
+
+/// Draw the icons, farthest first so nearer ones overlap them.
+pub fn draw(ui: &mut Ui, rect: Rect, app_state: &AppState) {
+    let Some(camera) = app_state.camera.as_ref() else { return; };
+    let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
+    let screen = ui.ctx().screen_rect();
+    let painter = ui.painter_at(rect);
+    let origin = Vector3::from(camera.get_position());
+
+    let mut icons = icons(app_state);
+    icons.sort_by(|a, b| (b.position - origin).norm().total_cmp(&(a.position - origin).norm()));
+    for icon in icons {
+        let Some(at) = math::world_to_screen(camera, screen, icon.position) else { continue; };
+        let selected = root.editor.selection.contains(&icon.item);
+        let outline = if selected { Color32::from_rgb(255, 210, 60) } else { Color32::from_gray(30) };
+        if let IconKind::DirectionalLight { direction } = &icon.kind {
+            draw_arrow(&painter, camera, screen, icon.position, *direction, icon.color);
+        }
+        painter.circle(at, ICON_RADIUS, Color32::from_black_alpha(160), Stroke::new(if selected { 2.0 } else { 1.0 }, outline));
+        let glyph = match icon.kind {
+            IconKind::PointLight => "💡",
+            IconKind::DirectionalLight { .. } => "☀",
+            IconKind::Emitter => "✨",
+            IconKind::Camera => "🎥",
+        };
+        painter.text(at, egui::Align2::CENTER_CENTER, glyph, egui::FontId::proportional(ICON_RADIUS * 1.3), icon.color);
+    }
+}
+
+fn draw_arrow(painter: &egui::Painter, camera: &Camera, screen: Rect, from: Vector3<f32>, direction: Vector3<f32>, color: Color32) {
+    if direction.norm_squared() < 1e-8 { return; }
+    let tip = from + direction.normalize() * ARROW_LENGTH;
+    let Some((a, b)) = math::world_segment_to_screen(camera, screen, from, tip) else { return; };
+    let stroke = Stroke::new(2.0, color);
+    painter.line_segment([a, b], stroke);
+    let back = (a - b).normalized() * 8.0;
+    if back.is_finite() {
+        let side = egui::vec2(-back.y, back.x) * 0.5;
+        painter.line_segment([b, b + back + side], stroke);
+        painter.line_segment([b, b + back - side], stroke);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ray_aabb_hits_and_misses() {
+        let (min, max) = (Vector3::new(-1.0, -1.0, -1.0), Vector3::new(1.0, 1.0, 1.0));
+        let down = Vector3::new(0.0, -1.0, 0.0);
+        let (enter, exit) = ray_aabb(Vector3::new(0.0, 5.0, 0.0), down, min, max).unwrap();
+        assert!((enter - 4.0).abs() < 1e-5 && (exit - 6.0).abs() < 1e-5);
+        assert!(ray_aabb(Vector3::new(3.0, 5.0, 0.0), down, min, max).is_none());
+        // Box behind the origin.
+        assert!(ray_aabb(Vector3::new(0.0, -5.0, 0.0), down, min, max).is_none());
+    }
+
+    #[test]
+    fn terrain_hit_uses_mid_height() {
+        let mut def = TerrainDef::new_default();
+        def.max_height = 10.0;
+        def.height_mid = 0.5;
+        let t = terrain_hit(&def, Vector3::new(0.0, 20.0, 0.0), Vector3::new(0.0, -1.0, 0.0)).unwrap();
+        assert!((t - 15.0).abs() < 1e-4);
+        assert!(terrain_hit(&def, Vector3::new(500.0, 20.0, 0.0), Vector3::new(0.0, -1.0, 0.0)).is_none());
+    }
+
+    #[test]
+    fn nearest_candidate_wins() {
+        let light = Selection::Light(uuid::Uuid::new_v4());
+        let picked = nearest(vec![(12.0, Selection::Terrain), (3.0, light.clone()), (7.5, Selection::None)]);
+        assert_eq!(picked, Some(light));
+        assert_eq!(nearest(Vec::new()), None);
+    }
+}
@@ -0,0 +1,219 @@
+use std::time::Instant;
+
+use egui::Context;
+use enigma_3d::AppState;
+
+use crate::editor::logging::{self, Source};
+use crate::editor::state::{EditorRoot, PlaySession};
+
+/// Frame length used by single-frame step while paused.
+const STEP_DT: f32 = 1.0 / 60.0;
+/// Longest frame the simulation will advance in one go, so a hitch (or a
+/// breakpoint) doesn't teleport everything.
+const MAX_DT: f32 = 0.1;
+
+pub fn is_playing(app_state: &AppState) -> bool {
+    app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| r.editor.play.is_some())
+        .unwrap_or(false)
+}
+
+/// Script components are compiled into the game, so Play can't run them;
+/// say so once per Play instead of letting the scene sit still silently.
+fn note_game_only_scripts(app_state: &AppState) {
+    let Some(project) = app_state.get_state_data_value::<EditorRoot>("editor")
+        .and_then(|r| r.project.as_ref()) else { return; };
+    let Some(scene) = project.scenes.get(project.active_scene_index).map(|s| s.uuid) else { return; };
+    let count = project.script_components.iter().filter(|c| c.scene == scene).count();
+    if count > 0 {
+        logging::info(Source::Play, format!(
+            "{count} script component(s) in this scene are not simulated; scripts only run in the built game (Run)"
+        ));
+    }
+}
+
+/// Snapshot the scene and start simulating it in the viewport.
+pub fn start(app_state: &mut AppState) {
+    if is_playing(app_state) { return; }
+    let snapshot = crate::editor::visibility::serialize(app_state);
+    let camera = app_state.camera.clone();
+    let lights = crate::project::scene::extras(app_state).editor_lights;
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let Some(project) = root.project.clone() else { return; };
+    // A gizmo drag would otherwise keep writing to an object across the switch.
+    root.editor.gizmo.drag = None;
+    root.editor.play = Some(PlaySession {
+        snapshot,
+        camera,
+        project,
+        lights,
+        dirty: root.editor.dirty,
+        paused: false,
+        step_requested: false,
+        last_tick: Instant::now(),
+        elapsed: 0.0,
+        frames: 0,
+        paused_speeds: Vec::new(),
+    });
+    // Play looks through the game camera; the editor view comes back at stop.
+    if let Some(game) = crate::editor::cameras::game_camera(app_state).cloned() {
+        app_state.set_camera(game);
+    }
+    note_game_only_scripts(app_state);
+}
+
+pub fn set_paused(app_state: &mut AppState, paused: bool) {
+    let resumed_speeds = {
+        let Some(session) = app_state.get_state_data_value_mut::<EditorRoot>("editor")
+            .and_then(|r| r.editor.play.as_mut()) else { return; };
+        if session.paused == paused { return; }
+        session.paused = paused;
+        session.last_tick = Instant::now();
+        std::mem::take(&mut session.paused_speeds)
+    };
+
+    if paused {
+        // Freeze clips by zeroing their speed and remember what it was.
+        let mut speeds = Vec::new();
+        for obj in app_state.objects.iter_mut() {
+            let uuid = obj.get_unique_id();
+            if let Some(state) = obj.get_current_animation_mut().as_mut() {
+                speeds.push((uuid, state.speed));
+                state.speed = 0.0;
+            }
+        }
+        if let Some(session) = app_state.get_state_data_value_mut::<EditorRoot>("editor")
+            .and_then(|r| r.editor.play.as_mut())
+        {
+            session.paused_speeds = speeds;
+        }
+    } else {
+        for obj in app_state.objects.iter_mut() {
+            let uuid = obj.get_unique_id();
+            let Some(speed) = resumed_speeds.iter().find(|(u, _)| *u == uuid).map(|(_, s)| *s) else { continue; };
+            if let Some(state) = obj.get_current_animation_mut().as_mut() {
+                state.speed = speed;
+            }
+        }
+    }
+    // The engine skips paused particle systems in its own update.
+    for sys in app_state.particle_systems.iter_mut() {
+        sys.paused = paused;
+    }
+}
+
+/// Advance one frame while paused.
+pub fn request_step(app_state: &mut AppState) {
+    if let Some(session) = app_state.get_state_data_value_mut::<EditorRoot>("editor")
+        .and_then(|r| r.editor.play.as_mut())
+    {
+        if session.paused {
+            session.step_requested = true;
+        }
+    }
+}
+
+/// Put the scene, camera and project back exactly as they were at Play.
+pub fn stop(app_state: &mut AppState) {
+    if !is_playing(app_state) { return; }
+    // Keep the session until the snapshot can actually be restored.
+    let Some(display) = app_state.display.clone() else {
+        logging::error(Source::Play, "Display not ready, the scene was not restored");
+        return;
+    };
+    let Some(session) = app_state.get_state_data_value_mut::<EditorRoot>("editor")
+        .and_then(|r| r.editor.play.take()) else { return; };
+
+    app_state.objects.clear();
+    app_state.light.clear();
+    app_state.materials.clear();
+    // Particle systems are rebuilt from the project by the reconcile passes.
+    app_state.particle_systems.clear();
+    app_state.inject_serializer(session.snapshot, display, false);
+    if let Some(camera) = session.camera {
+        app_state.set_camera(camera);
+    }
+
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.project = Some(session.project);
+        root.editor.lights = session.lights;
+        root.editor.dirty = session.dirty;
+        root.editor.gizmo.drag = None;
+        root.editor.material_cache.clear();
+        root.editor.applied_particle_instances.clear();
+        root.editor.previewed_particle = None;
+        root.editor.per_def_particle_materials.clear();
+    }
+}
+
+/// Per-frame driver, called from `editor::draw` before the panels.
+pub fn tick(ctx: &Context, app_state: &mut AppState) {
+    let (dt, stepping) = {
+        let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+        let Some(session) = root.editor.play.as_mut() else { return; };
+        let now = Instant::now();
+        let frame_dt = (now - session.last_tick).as_secs_f32().min(MAX_DT);
+        session.last_tick = now;
+        let (dt, stepping) = if !session.paused {
+            (frame_dt, false)
+        } else if session.step_requested {
+            session.step_requested = false;
+            (STEP_DT, true)
+        } else {
+            return;
+        };
+        session.elapsed += dt;
+        session.frames += 1;
+        (dt, stepping)
+    };
+    ctx.request_repaint();
+
+    if stepping {
+        // Clips and particles are frozen; push them forward by hand.
+        let speeds = app_state.get_state_data_value::<EditorRoot>("editor")
+            .and_then(|r| r.editor.play.as_ref())
+            .map(|s| s.paused_speeds.clone())
+            .unwrap_or_default();
+        for obj in app_state.objects.iter_mut() {
+            let uuid = obj.get_unique_id();
+            let Some(speed) = speeds.iter().find(|(u, _)| *u == uuid).map(|(_, s)| *s) else { continue; };
+            let Some((name, time, looping)) = obj.get_current_animation().as_ref()
+                .map(|s| (s.name.clone(), s.time, s.looping)) else { continue; };
+            let duration = obj.get_animations().get(&name).map(|a| a.duration).unwrap_or(0.0);
+            if let Some(state) = obj.get_current_animation_mut().as_mut() {
+                state.time = advance_clip_time(time, speed * dt, duration, looping);
+            }
+        }
+        for sys in app_state.particle_systems.iter_mut() {
+            sys.update(dt);
+        }
+    }
+}
+
+/// Move a clip's playhead by `delta`, wrapping looped clips and clamping
+/// the others at their ends.
+fn advance_clip_time(time: f32, delta: f32, duration: f32, looping: bool) -> f32 {
+    if duration <= 0.0 {
+        return 0.0;
+    }
+    let t = time + delta;
+    if looping { t.rem_euclid(duration) } else { t.clamp(0.0, duration) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advance_clip_time_wraps_looped_clips() {
+        assert!((advance_clip_time(0.9, 0.2, 1.0, true) - 0.1).abs() < 1e-5);
+        assert!((advance_clip_time(0.1, -0.2, 1.0, true) - 0.9).abs() < 1e-5);
+    }
+
+    #[test]
+    fn advance_clip_time_clamps_one_shot_clips() {
+        assert_eq!(advance_clip_time(0.9, 0.2, 1.0, false), 1.0);
+        assert_eq!(advance_clip_time(0.5, 0.25, 1.0, false), 0.75);
+        assert_eq!(advance_clip_time(0.5, 0.25, 0.0, false), 0.0);
+    }
+}
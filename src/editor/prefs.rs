@@ -0,0 +1,237 @@
+//! Editor preferences that outlive a project: stored as JSON in the user's
+//! config directory, read once at startup and written back whenever they
+//! change.
+
+use std::path::{Path, PathBuf};
+
+use egui::Context;
+use enigma_3d::AppState;
+use serde::{Deserialize, Serialize};
+
+use crate::editor::commands::Keymap;
+use crate::editor::logging::{self, Source};
+use crate::editor::state::{EditorRoot, EditorState, FlySettings, GizmoMode, Layout, RecentProject, SnapSettings, Space, SpawnAt};
+
+const FILE_NAME: &str = "prefs.json";
+/// Entries kept on the welcome screen.
+const MAX_RECENT: usize = 10;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct EditorPrefs {
+    pub snap: SnapSettings,
+    pub spawn_at: SpawnAt,
+    pub keybindings: Keymap,
+    pub fly: FlySettings,
+    pub gizmo: GizmoPrefs,
+    pub layout: Layout,
+    pub recent_projects: Vec<RecentProject>,
+}
+
+/// The gizmo toolbar's toggles.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct GizmoPrefs {
+    pub mode: GizmoMode,
+    pub space: Space,
+    pub snap_enabled: bool,
+    pub grid_enabled: bool,
+}
+
+impl Default for GizmoPrefs {
+    fn default() -> Self {
+        Self { mode: GizmoMode::default(), space: Space::default(), snap_enabled: false, grid_enabled: true }
+    }
+}
+
+impl EditorPrefs {
+    pub fn of(editor: &EditorState) -> Self {
+        let g = &editor.gizmo;
+        EditorPrefs {
+            snap: g.snap,
+            spawn_at: editor.spawn_at,
+            keybindings: editor.keymap.clone(),
+            fly: editor.navigation.fly,
+            gizmo: GizmoPrefs { mode: g.mode, space: g.space, snap_enabled: g.snap_enabled, grid_enabled: g.grid_enabled },
+            layout: editor.layout.clone(),
+            recent_projects: editor.recent_projects.clone(),
+        }
+    }
+}
+
+/// `<config dir>/enigma-editor/prefs.json`, or None when no config
+/// directory can be found.
+pub fn path() -> Option<PathBuf> {
+    let base = std::env::var_os("APPDATA").map(PathBuf::from)
+        .or_else(|| std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("enigma-editor").join(FILE_NAME))
+}
+
+pub fn load_from(path: &Path) -> Result<EditorPrefs, PrefsError> {
+    let text = std::fs::read_to_string(path).map_err(PrefsError::Io)?;
+    serde_json::from_str(&text).map_err(PrefsError::Parse)
+}
+
+pub fn save_to(path: &Path, prefs: &EditorPrefs) -> Result<(), PrefsError> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(PrefsError::Io)?;
+    }
+    let text = serde_json::to_string_pretty(prefs).map_err(PrefsError::Parse)?;
+    std::fs::write(path, text).map_err(PrefsError::Io)
+}
+
+/// Startup: the saved preferences. A missing file means defaults; a broken
+/// one is reported and moved aside to `prefs.json.bak` so the next save
+/// doesn't overwrite it.
+pub fn read() -> EditorPrefs {
+    let Some(path) = path() else { return EditorPrefs::default(); };
+    read_from(&path)
+}
+
+fn read_from(path: &Path) -> EditorPrefs {
+    match load_from(path) {
+        Ok(prefs) => prefs,
+        Err(PrefsError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => EditorPrefs::default(),
+        Err(e) => {
+            let backup = path.with_extension("json.bak");
+            match std::fs::rename(path, &backup) {
+                Ok(()) => logging::warn(Source::Prefs, format!(
+                    "Could not read {}: {e}. Kept it as {} and started from defaults", path.display(), backup.display(),
+                )),
+                Err(move_err) => logging::warn(Source::Prefs, format!(
+                    "Could not read {}: {e}. Could not back it up either: {move_err}", path.display(),
+                )),
+            }
+            EditorPrefs::default()
+        }
+    }
+}
+
+/// Put `prefs` into the editor state.
+pub fn apply(app_state: &mut AppState, prefs: EditorPrefs) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let editor = &mut root.editor;
+    editor.gizmo.snap = prefs.snap;
+    editor.gizmo.mode = prefs.gizmo.mode;
+    editor.gizmo.space = prefs.gizmo.space;
+    editor.gizmo.snap_enabled = prefs.gizmo.snap_enabled;
+    editor.gizmo.grid_enabled = prefs.gizmo.grid_enabled;
+    editor.spawn_at = prefs.spawn_at;
+    editor.keymap = prefs.keybindings.clone();
+    editor.navigation.fly = prefs.fly;
+    editor.layout = prefs.layout.clone();
+    editor.recent_projects = prefs.recent_projects.clone();
+    editor.saved_prefs = Some(prefs);
+}
+
+/// Per-frame: write the preferences out when they differ from the file.
+/// Waits while a mouse button is held so dragging a slider or a panel edge
+/// writes once, at the end.
+pub fn autosave(ctx: &Context, app_state: &mut AppState) {
+    if ctx.input(|i| i.pointer.any_down()) { return; }
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let prefs = EditorPrefs::of(&root.editor);
+    if root.editor.saved_prefs.as_ref() == Some(&prefs) { return; }
+    if let Some(path) = path() {
+        if let Err(e) = save_to(&path, &prefs) {
+            logging::error(Source::Prefs, format!("Could not save {}: {e}", path.display()));
+        }
+    }
+    // Recorded even on failure, so a read-only config doesn't retry every frame.
+    root.editor.saved_prefs = Some(prefs);
+}
+
+/// Put a just-opened project at the top of the recent list.
+pub fn note_recent(app_state: &mut AppState, path: String, name: String) {
+    let opened_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        remember(&mut root.editor.recent_projects, RecentProject { path, name, opened_at });
+    }
+}
+
+/// Newest first, one entry per path, at most `MAX_RECENT`.
+pub fn remember(recent: &mut Vec<RecentProject>, entry: RecentProject) {
+    recent.retain(|r| r.path != entry.path);
+    recent.insert(0, entry);
+    recent.truncate(MAX_RECENT);
+}
+
+#[derive(Debug)]
+pub enum PrefsError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+}
+
+impl std::fmt::Display for PrefsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrefsError::Io(e) => write!(f, "I/O error: {e}"),
+            PrefsError::Parse(e) => write!(f, "JSON error: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor::dock::NamedLayout;
+
+    #[test]
+    fn missing_keys_fall_back_to_defaults() {
+        let prefs: EditorPrefs = serde_json::from_str("{}").unwrap();
+        assert_eq!(prefs, EditorPrefs::default());
+        let prefs: EditorPrefs = serde_json::from_str(r#"{"snap": {"rotate_degrees": 45.0}}"#).unwrap();
+        assert_eq!(prefs.snap.rotate_degrees, 45.0);
+        assert_eq!(prefs.snap.translate, SnapSettings::default().translate);
+        assert!(prefs.gizmo.grid_enabled);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(FILE_NAME);
+        let mut prefs = EditorPrefs::default();
+        prefs.snap.translate = 0.25;
+        prefs.snap.surface = true;
+        prefs.spawn_at = SpawnAt::Cursor;
+        prefs.keybindings.set(crate::editor::commands::CommandId::Run, None);
+        prefs.fly.speed = 12.0;
+        prefs.gizmo.mode = GizmoMode::Rotate;
+        prefs.gizmo.space = Space::Local;
+        prefs.layout.dock.right.size = 400.0;
+        prefs.layout.saved.push(NamedLayout { name: "Mine".into(), dock: crate::editor::dock::preset("Build").unwrap() });
+        prefs.recent_projects.push(RecentProject { path: "/p/enigma_project.json".into(), name: "p".into(), opened_at: 7 });
+        save_to(&path, &prefs).unwrap();
+        assert_eq!(load_from(&path).unwrap(), prefs);
+    }
+
+    #[test]
+    fn broken_file_is_kept_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(FILE_NAME);
+        std::fs::write(&path, "{ not json").unwrap();
+        assert_eq!(read_from(&path), EditorPrefs::default());
+        assert!(!path.exists());
+        let backup = dir.path().join("prefs.json.bak");
+        assert_eq!(std::fs::read_to_string(backup).unwrap(), "{ not json");
+    }
+
+    #[test]
+    fn remember_moves_reopened_projects_to_the_top() {
+        let entry = |path: &str, at: u64| RecentProject { path: path.into(), name: path.into(), opened_at: at };
+        let mut recent = Vec::new();
+        for i in 0..12 {
+            remember(&mut recent, entry(&format!("p{i}"), i));
+        }
+        assert_eq!(recent.len(), MAX_RECENT);
+        assert_eq!(recent[0].path, "p11");
+        remember(&mut recent, entry("p5", 99));
+        assert_eq!(recent.len(), MAX_RECENT);
+        assert_eq!((recent[0].path.as_str(), recent[0].opened_at), ("p5", 99));
+        assert_eq!(recent.iter().filter(|r| r.path == "p5").count(), 1);
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use enigma_3d::AppState;
+use enigma_3d::object::Object;
+use nalgebra::{UnitQuaternion, Vector3};
+use uuid::Uuid;
+
+use crate::editor::logging::{self, Source};
+use crate::editor::visibility;
+use crate::editor::state::{EditorRoot, ObjectParent, ProjectState};
+
+/// Game-side runtime that keeps children attached, written once and then
+/// owned by the game. Reads the links from the generated `PARENTS` binding.
+pub const RUNTIME_FILE: &str = "src/enigma_hierarchy.rs";
+
+/// Position, rotation and scale of an object in some space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trs {
+    pub position: Vector3<f32>,
+    pub rotation: UnitQuaternion<f32>,
+    pub scale: Vector3<f32>,
+}
+
+impl Trs {
+    pub fn of(object: &Object) -> Self {
+        let r = object.transform.rotation;
+        Trs {
+            position: object.transform.position,
+            rotation: UnitQuaternion::from_euler_angles(r.x, r.y, r.z),
+            scale: object.transform.scale,
+        }
+    }
+
+    pub fn apply_to(&self, object: &mut Object) {
+        let (rx, ry, rz) = self.rotation.euler_angles();
+        object.transform.position = self.position;
+        object.transform.rotation = Vector3::new(rx, ry, rz);
+        object.transform.scale = self.scale;
+    }
+}
+
+/// `world` expressed relative to `parent`. Non-uniform parent scale is
+/// applied per axis, so sheared results are approximated.
+pub fn to_local(parent: &Trs, world: &Trs) -> Trs {
+    let inv = parent.rotation.inverse();
+    let scale = safe_scale(parent.scale);
+    Trs {
+        position: (inv * (world.position - parent.position)).component_div(&scale),
+        rotation: inv * world.rotation,
+        scale: world.scale.component_div(&scale),
+    }
+}
+
+/// Inverse of `to_local`.
+pub fn to_world(parent: &Trs, local: &Trs) -> Trs {
+    Trs {
+        position: parent.position + parent.rotation * parent.scale.component_mul(&local.position),
+        rotation: parent.rotation * local.rotation,
+        scale: parent.scale.component_mul(&local.scale),
+    }
+}
+
+fn safe_scale(v: Vector3<f32>) -> Vector3<f32> {
+    v.map(|c| if c.abs() < 1e-6 { 1e-6 } else { c })
+}
+
+/// Attach `child` to `parent` (None detaches). World transforms are left
+/// alone, so the child stays where it is.
+pub fn set_parent(project: &mut ProjectState, scene: Uuid, child: Uuid, parent: Option<Uuid>) -> Result<(), ParentError> {
+    if let Some(parent) = parent {
+        if parent == child {
+            return Err(ParentError::SameObject);
+        }
+        if descendants(project, scene, child).contains(&parent) {
+            return Err(ParentError::Cycle);
+        }
+    }
+    project.object_parents.retain(|l| !(l.scene == scene && l.child == child));
+    if let Some(parent) = parent {
+        project.object_parents.push(ObjectParent { scene, child, parent });
+    }
+    Ok(())
+}
+
+/// Everything below `root`, depth first.
+pub fn descendants(project: &ProjectState, scene: Uuid, root: Uuid) -> Vec<Uuid> {
+    let mut out = Vec::new();
+    let mut stack = project.children_of(scene, root);
+    stack.reverse();
+    while let Some(next) = stack.pop() {
+        if out.contains(&next) || next == root { continue; }
+        out.push(next);
+        let mut children = project.children_of(scene, next);
+        children.reverse();
+        stack.extend(children);
+    }
+    out
+}
+
+/// Every link, parents before their children, so applying them in order
+/// resolves a whole chain in one pass.
+pub fn ordered_links(project: &ProjectState) -> Vec<&ObjectParent> {
+    let depth = |link: &ObjectParent| {
+        let mut depth = 0;
+        let mut current = link.parent;
+        while let Some(up) = project.parent_of(link.scene, current) {
+            depth += 1;
+            current = up;
+            if depth > project.object_parents.len() { break; }
+        }
+        depth
+    };
+    let mut links: Vec<&ObjectParent> = project.object_parents.iter().collect();
+    links.sort_by_key(|l| depth(l));
+    links
+}
+
+/// One visible row of the hierarchy tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeRow {
+    pub uuid: Uuid,
+    pub name: String,
+    pub depth: usize,
+    pub has_children: bool,
+}
+
+/// `objects` (in scene order) as a depth-first tree. Children of collapsed
+/// rows are left out; objects whose parent is missing show at the top level.
+pub fn tree_rows(project: &ProjectState, scene: Uuid, objects: &[(Uuid, String)], collapsed: &HashSet<Uuid>) -> Vec<TreeRow> {
+    let present = |u: Uuid| objects.iter().any(|(o, _)| *o == u);
+    let children = |u: Uuid| -> Vec<&(Uuid, String)> {
+        objects.iter().filter(|(o, _)| project.parent_of(scene, *o) == Some(u)).collect()
+    };
+
+    let mut rows = Vec::new();
+    let mut stack: Vec<(&(Uuid, String), usize)> = objects.iter()
+        .filter(|(o, _)| project.parent_of(scene, *o).map(|p| !present(p)).unwrap_or(true))
+        .rev()
+        .map(|o| (o, 0))
+        .collect();
+    while let Some(((uuid, name), depth)) = stack.pop() {
+        let kids = children(*uuid);
+        rows.push(TreeRow { uuid: *uuid, name: name.clone(), depth, has_children: !kids.is_empty() });
+        if !collapsed.contains(uuid) {
+            stack.extend(kids.into_iter().rev().map(|k| (k, depth + 1)));
+        }
+    }
+    rows
+}
+
+fn active_scene(app_state: &AppState) -> Option<Uuid> {
+    let project = app_state.get_state_data_value::<EditorRoot>("editor")?.project.as_ref()?;
+    project.scenes.get(project.active_scene_index).map(|s| s.uuid)
+}
+
+/// Parent of `uuid` in the active scene.
+pub fn parent(app_state: &AppState, uuid: Uuid) -> Option<Uuid> {
+    let scene = active_scene(app_state)?;
+    let project = app_state.get_state_data_value::<EditorRoot>("editor")?.project.as_ref()?;
+    project.parent_of(scene, uuid)
+}
+
+/// Descendants of `uuid` in the active scene.
+pub fn descendants_of(app_state: &AppState, uuid: Uuid) -> Vec<Uuid> {
+    let Some(scene) = active_scene(app_state) else { return Vec::new(); };
+    app_state.get_state_data_value::<EditorRoot>("editor")
+        .and_then(|r| r.project.as_ref())
+        .map(|p| descendants(p, scene, uuid))
+        .unwrap_or_default()
+}
+
+/// World transform of the object `uuid` is attached to.
+pub fn parent_world(app_state: &AppState, uuid: Uuid) -> Option<Trs> {
+    let parent = parent(app_state, uuid)?;
+    visibility::find(app_state, parent).map(Trs::of)
+}
+
+/// Move the descendants of `uuid` along with it after its transform changed
+/// from `before` to its current one. Hidden descendants move too.
+pub fn carry_children(app_state: &mut AppState, uuid: Uuid, before: Trs) {
+    let Some(after) = app_state.objects.iter().find(|o| o.get_unique_id() == uuid).map(Trs::of) else { return; };
+    if after == before { return; }
+    for child in descendants_of(app_state, uuid) {
+        if let Some(o) = visibility::find_mut(app_state, child) {
+            let local = to_local(&before, &Trs::of(o));
+            to_world(&after, &local).apply_to(o);
+        }
+    }
+}
+
+/// Attach `child` to `parent` in the active scene and report refusals.
+pub fn attach(app_state: &mut AppState, child: Uuid, parent: Option<Uuid>) {
+    let Some(scene) = active_scene(app_state) else { return; };
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let Some(project) = root.project.as_mut() else { return; };
+    if project.parent_of(scene, child) == parent { return; }
+    match set_parent(project, scene, child, parent) {
+        Ok(()) => root.editor.dirty = true,
+        Err(e) => logging::warn(Source::Scene, format!("Could not set parent: {e:?}")),
+    }
+}
+
+pub fn ensure_runtime(root: &Path) -> std::io::Result<()> {
+    let path = root.join(RUNTIME_FILE);
+    if path.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, crate::resources::HIERARCHY_RS)
+}
+
+#[derive(Debug)]
+pub enum ParentError {
+    SameObject,
+    Cycle,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_with(links: &[(Uuid, Uuid)]) -> (ProjectState, Uuid) {
+        let mut p = ProjectState::new("t".into(), "/tmp/t".into());
+        let scene = Uuid::new_v4();
+        for (child, parent) in links {
+            set_parent(&mut p, scene, *child, Some(*parent)).unwrap();
+        }
+        (p, scene)
+    }
+
+    #[test]
+    fn local_world_round_trip() {
+        let parent = Trs {
+            position: Vector3::new(1.0, 2.0, 3.0),
+            rotation: UnitQuaternion::from_euler_angles(0.3, -1.1, 0.7),
+            scale: Vector3::new(2.0, 2.0, 2.0),
+        };
+        let world = Trs {
+            position: Vector3::new(-4.0, 0.5, 2.0),
+            rotation: UnitQuaternion::from_euler_angles(1.0, 0.2, -0.4),
+            scale: Vector3::new(1.0, 3.0, 0.5),
+        };
+        let back = to_world(&parent, &to_local(&parent, &world));
+        assert!((back.position - world.position).norm() < 1e-4);
+        assert!(back.rotation.angle_to(&world.rotation) < 1e-4);
+        assert!((back.scale - world.scale).norm() < 1e-4);
+
+        let local = to_local(&parent, &Trs { position: parent.position, ..parent });
+        assert!(local.position.norm() < 1e-5);
+        assert!(local.rotation.angle() < 1e-4);
+    }
+
+    #[test]
+    fn set_parent_refuses_cycles() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let (mut p, scene) = project_with(&[(b, a), (c, b)]);
+        assert!(matches!(set_parent(&mut p, scene, a, Some(c)), Err(ParentError::Cycle)));
+        assert!(matches!(set_parent(&mut p, scene, a, Some(a)), Err(ParentError::SameObject)));
+        assert_eq!(descendants(&p, scene, a), vec![b, c]);
+
+        // Re-parenting replaces the old link; None detaches.
+        set_parent(&mut p, scene, c, Some(a)).unwrap();
+        assert_eq!(p.parent_of(scene, c), Some(a));
+        set_parent(&mut p, scene, c, None).unwrap();
+        assert_eq!(p.parent_of(scene, c), None);
+        assert_eq!(p.object_parents.len(), 1);
+    }
+
+    #[test]
+    fn links_are_ordered_parents_first() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let (p, _) = project_with(&[(c, b), (b, a)]);
+        let order: Vec<Uuid> = ordered_links(&p).iter().map(|l| l.child).collect();
+        assert_eq!(order, vec![b, c]);
+    }
+
+    #[test]
+    fn tree_rows_nest_and_collapse() {
+        let (a, b, c, d) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let (mut p, scene) = project_with(&[(c, a)]);
+        // A link to an object that isn't in the scene leaves the child at the top.
+        p.object_parents.push(ObjectParent { scene, child: d, parent: Uuid::new_v4() });
+        let objects = vec![(c, "c".to_string()), (a, "a".into()), (b, "b".into()), (d, "d".into())];
+
+        let rows = tree_rows(&p, scene, &objects, &HashSet::new());
+        let shape: Vec<(Uuid, usize, bool)> = rows.iter().map(|r| (r.uuid, r.depth, r.has_children)).collect();
+        assert_eq!(shape, vec![(a, 0, true), (c, 1, false), (b, 0, false), (d, 0, false)]);
+
+        let collapsed: HashSet<Uuid> = [a].into_iter().collect();
+        let rows = tree_rows(&p, scene, &objects, &collapsed);
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].has_children);
+    }
+
+    #[test]
+    fn set_scene_objects_drops_dangling_links() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let (mut p, scene) = project_with(&[(b, a)]);
+        p.set_scene_objects(scene, &[(a, "a".into()), (b, "b".into())]);
+        assert_eq!(p.object_parents.len(), 1);
+        p.set_scene_objects(scene, &[(b, "b".into())]);
+        assert!(p.object_parents.is_empty());
+    }
+}
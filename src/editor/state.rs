@@ -22,6 +22,72 @@ pub struct ProjectState {
     pub skybox: Option<Uuid>,
     #[serde(default)]
     pub particle_systems: Vec<ParticleSystemDef>,
+    /// Names of the objects in every scene, refreshed from the live scene on
+    /// save. Feeds the generated asset bindings, which can't read scene files.
+    #[serde(default)]
+    pub scene_objects: Vec<SceneObjectRef>,
+    #[serde(default)]
+    pub scripts: Vec<ScriptDef>,
+    /// Scripts attached to scene objects. Kept here rather than in the scene
+    /// file for the same reason as `material_assignments`.
+    #[serde(default)]
+    pub script_components: Vec<ScriptComponent>,
+    /// Parent links of scene objects. Objects keep their world transform in
+    /// the scene file; local transforms are derived from the parent's.
+    #[serde(default)]
+    pub object_parents: Vec<ObjectParent>,
+    /// Editor-only hide and lock toggles. Hidden objects are still saved.
+    #[serde(default)]
+    pub object_flags: Vec<ObjectFlags>,
+    #[serde(default)]
+    pub run_configs: Vec<RunConfig>,
+    /// Configuration used by Run. None runs plain `cargo run` on the startup scene.
+    #[serde(default)]
+    pub active_run_config: Option<Uuid>,
+}
+
+/// A named way of launching the game from the editor.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct RunConfig {
+    pub uuid: Uuid,
+    pub name: String,
+    /// Cargo profile, e.g. `dev` or `release`. Empty uses cargo's default.
+    #[serde(default)]
+    pub profile: String,
+    /// Space or comma separated cargo features.
+    #[serde(default)]
+    pub features: String,
+    /// Program arguments, split like a shell would (quotes group words).
+    #[serde(default)]
+    pub args: String,
+    #[serde(default)]
+    pub env: Vec<(String, String)>,
+    #[serde(default)]
+    pub scene: RunScene,
+}
+
+impl RunConfig {
+    pub fn new(name: String) -> Self {
+        RunConfig {
+            uuid: Uuid::new_v4(),
+            name,
+            profile: String::new(),
+            features: String::new(),
+            args: String::new(),
+            env: Vec::new(),
+            scene: RunScene::Startup,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Default)]
+pub enum RunScene {
+    /// The embedded startup scene, as a shipped build would load it.
+    #[default]
+    Startup,
+    /// Whatever scene is open in the editor when Run is pressed.
+    Active,
+    Scene(Uuid),
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -66,6 +132,105 @@ impl ParticleSystemDef {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct SceneObjectRef {
+    pub scene: Uuid,
+    pub uuid: Uuid,
+    pub name: String,
+}
+
+/// A behaviour type living in the game crate under `src/scripts/`.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct ScriptDef {
+    pub uuid: Uuid,
+    /// Rust type name, e.g. `Rotator`. Also the key the runtime registry uses.
+    pub name: String,
+    /// Path of the source file relative to the project root.
+    pub relative_path: String,
+    /// Exposed fields and their defaults; components override the values.
+    #[serde(default)]
+    pub fields: Vec<ScriptField>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct ScriptField {
+    pub name: String,
+    pub value: ScriptValue,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub enum ScriptValue {
+    Float(f32),
+    Int(i64),
+    Bool(bool),
+    Text(String),
+    Vec3([f32; 3]),
+}
+
+impl ScriptValue {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ScriptValue::Float(_) => "Float",
+            ScriptValue::Int(_) => "Int",
+            ScriptValue::Bool(_) => "Bool",
+            ScriptValue::Text(_) => "Text",
+            ScriptValue::Vec3(_) => "Vec3",
+        }
+    }
+
+    pub fn default_of(type_name: &str) -> Self {
+        match type_name {
+            "Int" => ScriptValue::Int(0),
+            "Bool" => ScriptValue::Bool(false),
+            "Text" => ScriptValue::Text(String::new()),
+            "Vec3" => ScriptValue::Vec3([0.0; 3]),
+            _ => ScriptValue::Float(0.0),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct ScriptComponent {
+    pub scene: Uuid,
+    pub object: Uuid,
+    pub script: Uuid,
+    /// Per-object overrides. Fields missing here use the def's default.
+    #[serde(default)]
+    pub fields: Vec<ScriptField>,
+}
+
+impl ScriptComponent {
+    /// Values for every field of `def`, overrides first, defaults otherwise.
+    /// Overrides for fields the def no longer has are dropped.
+    pub fn resolved_fields(&self, def: &ScriptDef) -> Vec<ScriptField> {
+        def.fields.iter().map(|f| {
+            self.fields.iter()
+                .find(|o| o.name == f.name && o.value.type_name() == f.value.type_name())
+                .cloned()
+                .unwrap_or_else(|| f.clone())
+        }).collect()
+    }
+}
+
+/// `child` is attached to `parent`; both live in `scene`.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct ObjectParent {
+    pub scene: Uuid,
+    pub child: Uuid,
+    pub parent: Uuid,
+}
+
+/// Hierarchy toggles of one object. Only objects with a flag set have an entry.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct ObjectFlags {
+    pub scene: Uuid,
+    pub object: Uuid,
+    #[serde(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    pub locked: bool,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct MaterialAssignment {
     pub scene: Uuid,
@@ -88,6 +253,13 @@ impl ProjectState {
             material_assignments: Vec::new(),
             skybox: None,
             particle_systems: Vec::new(),
+            scene_objects: Vec::new(),
+            scripts: Vec::new(),
+            script_components: Vec::new(),
+            object_parents: Vec::new(),
+            object_flags: Vec::new(),
+            run_configs: Vec::new(),
+            active_run_config: None,
         }
     }
 
@@ -111,6 +283,62 @@ impl ProjectState {
         self.material_assignments.retain(|a| !(a.scene == scene && a.object == object && a.shape == shape));
     }
 
+    /// Replace the object index of `scene` with `objects` (uuid, name).
+    /// Parent links to objects that are gone are dropped.
+    pub fn set_scene_objects(&mut self, scene: Uuid, objects: &[(Uuid, String)]) {
+        let present = |u: &Uuid| objects.iter().any(|(o, _)| o == u);
+        self.object_parents.retain(|l| l.scene != scene || (present(&l.child) && present(&l.parent)));
+        self.object_flags.retain(|f| f.scene != scene || present(&f.object));
+        self.scene_objects.retain(|o| o.scene != scene);
+        self.scene_objects.extend(objects.iter().map(|(uuid, name)| SceneObjectRef {
+            scene,
+            uuid: *uuid,
+            name: name.clone(),
+        }));
+    }
+
+    pub fn components_for_object(&self, scene: Uuid, object: Uuid) -> Vec<&ScriptComponent> {
+        self.script_components.iter()
+            .filter(|c| c.scene == scene && c.object == object)
+            .collect()
+    }
+
+    pub fn parent_of(&self, scene: Uuid, child: Uuid) -> Option<Uuid> {
+        self.object_parents.iter()
+            .find(|l| l.scene == scene && l.child == child)
+            .map(|l| l.parent)
+    }
+
+    pub fn children_of(&self, scene: Uuid, parent: Uuid) -> Vec<Uuid> {
+        self.object_parents.iter()
+            .filter(|l| l.scene == scene && l.parent == parent)
+            .map(|l| l.child)
+            .collect()
+    }
+
+    fn flags(&self, scene: Uuid, object: Uuid) -> Option<&ObjectFlags> {
+        self.object_flags.iter().find(|f| f.scene == scene && f.object == object)
+    }
+
+    pub fn is_hidden(&self, scene: Uuid, object: Uuid) -> bool {
+        self.flags(scene, object).map(|f| f.hidden).unwrap_or(false)
+    }
+
+    pub fn is_locked(&self, scene: Uuid, object: Uuid) -> bool {
+        self.flags(scene, object).map(|f| f.locked).unwrap_or(false)
+    }
+
+    /// Change the flags of `object`; entries with nothing set are removed.
+    pub fn update_flags(&mut self, scene: Uuid, object: Uuid, f: impl FnOnce(&mut ObjectFlags)) {
+        let mut flags = self.flags(scene, object).cloned()
+            .unwrap_or(ObjectFlags { scene, object, hidden: false, locked: false });
+        f(&mut flags);
+        self.object_flags.retain(|e| !(e.scene == scene && e.object == object));
+        if flags.hidden || flags.locked {
+            self.object_flags.push(flags);
+        }
+    }
+
     pub fn assignments_for_object(&self, scene: Uuid, object: Uuid) -> Vec<(usize, Uuid)> {
         self.material_assignments.iter()
             .filter(|a| a.scene == scene && a.object == object)
@@ -153,6 +381,42 @@ pub struct SceneRef {
     pub particle_instances: Vec<ParticleInstance>,
 }
 
+/// Identity of one light, stored in the scene file next to the engine's
+/// lights and in the same order. See `editor::lights`.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct LightMeta {
+    pub uuid: Uuid,
+    pub name: String,
+}
+
+/// Where the editor camera was looking in a scene, kept in the scene file.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub struct CameraPose {
+    pub position: [f32; 3],
+    /// Euler angles in radians, as in `transform.rotation`.
+    pub rotation: [f32; 3],
+}
+
+/// A saved editor viewpoint, recalled with its number key.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct CameraBookmark {
+    /// 1 to 9.
+    pub slot: u8,
+    pub name: String,
+    pub pose: CameraPose,
+    #[serde(default)]
+    pub projection: Projection,
+    pub focus_distance: f32,
+}
+
+/// An editor camera glide to a bookmark; see `editor::bookmarks`.
+#[derive(Clone, Debug)]
+pub struct CameraTransition {
+    pub from: CameraPose,
+    pub to: CameraPose,
+    pub started: std::time::Instant,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct ParticleInstance {
     pub uuid: Uuid,
@@ -281,13 +545,28 @@ impl EditorRoot {
 
 #[derive(Default)]
 pub struct EditorState {
-    pub selection: Selection,
+    pub selection: SelectionSet,
+    /// Uuid and name of each entry of `app_state.light`, index for index.
+    pub lights: Vec<LightMeta>,
+    /// The scene's camera. `app_state.camera` is the editor's own view while
+    /// editing; see `editor::cameras`.
+    pub game_camera: Option<enigma_3d::camera::Camera>,
+    /// Screen position where a viewport box selection started.
+    pub marquee: Option<egui::Pos2>,
     pub viewport_rect: Option<egui::Rect>,
     pub drag: Option<DragPayload>,
     pub modal: Option<Modal>,
     pub resource_browser_tab: ResourceTab,
     pub dirty: bool,
     pub renaming: Option<RenameTarget>,
+    /// Hierarchy rows whose children are hidden.
+    pub collapsed_objects: std::collections::HashSet<Uuid>,
+    pub hierarchy_filter: HierarchyFilter,
+    /// Objects kept out of the rendered scene while hidden, ordered by
+    /// `HiddenObject::index`. See `editor::visibility`.
+    pub hidden_stash: Vec<HiddenObject>,
+    /// Set while isolating: only these objects (and their children) show.
+    pub isolate: Option<std::collections::HashSet<Uuid>>,
     pub material_cache: HashMap<Uuid, u64>,
     pub applied_skybox: Option<Uuid>,
     pub previewed_particle: Option<(Uuid, u64)>,
@@ -300,9 +579,106 @@ pub struct EditorState {
     pub per_def_particle_materials: HashMap<Uuid, (Uuid, u64)>,
     pub job: Option<RunningJob>,
     pub last_job: Option<JobOutcome>,
+    /// Started in order once the running job succeeds; cleared on failure.
+    pub job_queue: std::collections::VecDeque<JobKind>,
+    pub job_history: Vec<JobRecord>,
+    pub show_job_history: bool,
+    pub show_run_configs: bool,
+    pub job_history_selected: Option<Uuid>,
+    /// Diagnostics of the latest build or run, replaced when the next starts.
+    pub problems: Vec<Diagnostic>,
+    pub problems_filter: ProblemsFilter,
+    /// Value copied from an inspector field; see `inspector::inspectable`.
+    pub field_clipboard: Option<crate::editor::inspector::inspectable::Value>,
+    /// Editor messages; see `editor::logging`.
+    pub console: crate::editor::logging::Console,
+    pub toasts: Vec<crate::editor::logging::Toast>,
+    /// Materials that failed to realize, reported once until they succeed.
+    pub material_failures: std::collections::HashSet<Uuid>,
     pub project_load: Option<ProjectLoadJob>,
     pub save_job: Option<SaveJob>,
     pub gizmo: GizmoState,
+    pub navigation: Navigation,
+    /// An Alt+LMB orbit started in the viewport and is still held.
+    pub orbiting: bool,
+    /// Viewpoints saved for the active scene, kept in its scene file.
+    pub bookmarks: Vec<CameraBookmark>,
+    pub camera_transition: Option<CameraTransition>,
+    /// Bookmark slot whose name is being edited, with the draft.
+    pub bookmark_rename: Option<(u8, String)>,
+    /// The 3D cursor; Shift+right-click on a surface moves it.
+    pub cursor_3d: Vector3<f32>,
+    /// Where new objects, lights and emitters appear. Saved in the prefs.
+    pub spawn_at: SpawnAt,
+    /// Changes to the default shortcuts. Saved in the prefs.
+    pub keymap: crate::editor::commands::Keymap,
+    /// Open while the command palette is showing.
+    pub palette: Option<PaletteState>,
+    pub show_keybindings: bool,
+    /// Command waiting for its new shortcut in the Keyboard Shortcuts window.
+    pub capturing_shortcut: Option<crate::editor::commands::CommandId>,
+    /// Window size and panel arrangement, restored at the next launch.
+    pub layout: Layout,
+    /// Bumped when a whole layout is applied, so the docked panels take
+    /// its sizes instead of keeping their current ones.
+    pub dock_generation: u64,
+    /// Tab being dragged to a new place.
+    pub dock_drag: Option<crate::editor::dock::Tab>,
+    /// Name typed under Window > Layouts for saving the current one.
+    pub layout_name_draft: String,
+    /// Newest first; listed on the welcome screen.
+    pub recent_projects: Vec<RecentProject>,
+    /// The preferences as last written; see `prefs::autosave`.
+    pub saved_prefs: Option<crate::editor::prefs::EditorPrefs>,
+    pub show_preferences: bool,
+    /// Set while the scene is simulated in the editor (Play mode).
+    pub play: Option<PlaySession>,
+    /// Socket to the game started by Run; created on first Run.
+    pub live_link: Option<LiveLink>,
+    pub show_live_link: bool,
+}
+
+/// A hidden object and where it goes back when shown.
+pub struct HiddenObject {
+    pub scene: Uuid,
+    /// Position in the scene's object list, counting hidden objects.
+    pub index: usize,
+    pub object: enigma_3d::object::Object,
+}
+
+/// Everything needed to put the editor back exactly as it was before Play.
+pub struct PlaySession {
+    pub snapshot: enigma_3d::AppStateSerializer,
+    pub camera: Option<enigma_3d::camera::Camera>,
+    pub project: ProjectState,
+    pub lights: Vec<LightMeta>,
+    pub dirty: bool,
+    pub paused: bool,
+    pub step_requested: bool,
+    pub last_tick: std::time::Instant,
+    /// Simulated time and frame count since Play, excluding paused time.
+    pub elapsed: f32,
+    pub frames: u64,
+    /// object uuid -> animation speed before pausing, restored on resume.
+    pub paused_speeds: Vec<(Uuid, f32)>,
+}
+
+/// Editor end of the live link. See `editor::live_link`.
+pub struct LiveLink {
+    pub addr: std::net::SocketAddr,
+    pub rx: std::sync::mpsc::Receiver<crate::editor::live_link::LinkEvent>,
+    /// Write half of the current game connection.
+    pub stream: Option<std::net::TcpStream>,
+    /// Id of the current game connection; events from older ones are stale.
+    pub connection: u64,
+    pub game_pid: Option<u32>,
+    /// Latest object state reported by the game.
+    pub objects: Vec<crate::editor::live_link::ObjectState>,
+    /// Hash of the last value pushed per item, so only changes are sent.
+    pub sent: HashMap<crate::editor::live_link::SyncKey, u64>,
+    pub push_enabled: bool,
+    /// Light uuids in the order the last Run saved them.
+    pub run_lights: Vec<Uuid>,
 }
 
 pub struct SaveJob {
@@ -332,21 +708,91 @@ pub enum ProjectLoadMessage {
 pub struct ProjectLoadPayload {
     pub project: ProjectState,
     pub scene_text: Option<String>,
+    pub job_history: Vec<JobRecord>,
 }
 
 pub struct RunningJob {
     pub label: String,
     pub started_at: std::time::Instant,
     pub rx: std::sync::mpsc::Receiver<JobMessage>,
+    /// Tail shown in the job overlay.
     pub lines: Vec<String>,
+    /// Complete output, kept for the job history.
+    pub log: Vec<String>,
+    /// Process id of the spawned cargo, once the worker reports it.
+    pub pid: Option<u32>,
+    pub cancelled: bool,
 }
 
 pub enum JobMessage {
+    Spawned(u32),
     Line(String),
+    Diagnostic(Diagnostic),
     Done(JobOutcome),
 }
 
-#[derive(Clone, Debug)]
+/// A cargo invocation the toolbar can start or queue.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum JobKind {
+    Run,
+    Build { release: bool },
+    UpdateDependencies,
+}
+
+impl JobKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            JobKind::Run => "Run",
+            JobKind::Build { release: false } => "Debug Build",
+            JobKind::Build { release: true } => "Release Build",
+            JobKind::UpdateDependencies => "Update Dependencies",
+        }
+    }
+}
+
+/// A finished job as kept in the per-project history.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct JobRecord {
+    /// Histories saved before records had ids get fresh ones on load.
+    #[serde(default = "Uuid::new_v4")]
+    pub id: Uuid,
+    pub outcome: JobOutcome,
+    /// Unix time in seconds.
+    pub finished_at: u64,
+    pub log: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DiagnosticLevel { Error, Warning, Note, Help }
+
+/// One compiler message from cargo's JSON output.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Diagnostic {
+    pub level: DiagnosticLevel,
+    pub message: String,
+    /// Primary span, relative to the project root. None for crate-level messages.
+    pub file: Option<String>,
+    pub line: u32,
+    pub column: u32,
+    pub code: Option<String>,
+    /// rustc's full human-readable rendering, shown when a row is expanded.
+    pub rendered: Option<String>,
+}
+
+pub struct ProblemsFilter {
+    pub errors: bool,
+    pub warnings: bool,
+    pub notes: bool,
+    pub text: String,
+}
+
+impl Default for ProblemsFilter {
+    fn default() -> Self {
+        Self { errors: true, warnings: true, notes: false, text: String::new() }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct JobOutcome {
     pub label: String,
     pub success: bool,
@@ -358,7 +804,7 @@ pub struct JobOutcome {
 pub enum Selection {
     #[default] None,
     SceneObject(Uuid),
-    Light(usize),
+    Light(Uuid),
     AmbientLight,
     Camera,
     Material(Uuid),
@@ -366,12 +812,172 @@ pub enum Selection {
     Particle(Uuid),
     ParticleInstance(Uuid),
     Terrain,
+    Script(Uuid),
+}
+
+impl Selection {
+    /// Items placed in the scene, which can be selected together.
+    pub fn is_scene_item(&self) -> bool {
+        matches!(self, Selection::SceneObject(_) | Selection::Light(_) | Selection::ParticleInstance(_))
+    }
+}
+
+/// How a click changes the selection: plain replaces, ctrl/cmd toggles,
+/// shift extends.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ClickMode { Replace, Toggle, Extend }
+
+impl ClickMode {
+    pub fn from_modifiers(modifiers: egui::Modifiers) -> Self {
+        if modifiers.command {
+            ClickMode::Toggle
+        } else if modifiers.shift {
+            ClickMode::Extend
+        } else {
+            ClickMode::Replace
+        }
+    }
+}
+
+/// What is selected. The last item is the primary one, shown in the
+/// inspector. Scene items can be selected together, and so can materials.
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct SelectionSet {
+    items: Vec<Selection>,
+}
+
+impl SelectionSet {
+    pub fn primary(&self) -> &Selection {
+        static NONE: Selection = Selection::None;
+        self.items.last().unwrap_or(&NONE)
+    }
+
+    pub fn items(&self) -> &[Selection] {
+        &self.items
+    }
+
+    pub fn is_multi(&self) -> bool {
+        self.items.len() > 1
+    }
+
+    pub fn contains(&self, item: &Selection) -> bool {
+        self.items.contains(item)
+    }
+
+    /// Replace the selection with `item`.
+    pub fn set(&mut self, item: Selection) {
+        self.items.clear();
+        if item != Selection::None {
+            self.items.push(item);
+        }
+    }
+
+    /// Add `item` and make it primary (shift-click).
+    pub fn add(&mut self, item: Selection) {
+        if !self.can_join(&item) {
+            return self.set(item);
+        }
+        self.items.retain(|i| *i != item);
+        self.items.push(item);
+    }
+
+    /// Add or remove `item` (ctrl-click).
+    pub fn toggle(&mut self, item: Selection) {
+        if self.contains(&item) {
+            self.items.retain(|i| *i != item);
+        } else {
+            self.add(item);
+        }
+    }
+
+    /// The selected scene objects, in selection order.
+    pub fn scene_objects(&self) -> Vec<Uuid> {
+        self.items.iter()
+            .filter_map(|i| match i { Selection::SceneObject(u) => Some(*u), _ => None })
+            .collect()
+    }
+
+    pub fn click(&mut self, item: Selection, mode: ClickMode) {
+        match mode {
+            ClickMode::Replace => self.set(item),
+            ClickMode::Toggle => self.toggle(item),
+            ClickMode::Extend => self.add(item),
+        }
+    }
+
+    fn can_join(&self, item: &Selection) -> bool {
+        let material = |i: &Selection| matches!(i, Selection::Material(_));
+        (item.is_scene_item() && self.items.iter().all(Selection::is_scene_item))
+            || (material(item) && self.items.iter().all(material))
+    }
+
+    /// The selected materials, in selection order.
+    pub fn materials(&self) -> Vec<Uuid> {
+        self.items.iter()
+            .filter_map(|i| match i { Selection::Material(u) => Some(*u), _ => None })
+            .collect()
+    }
+}
+
+/// Search box and type filter above the hierarchy.
+#[derive(Clone, Default, Debug)]
+pub struct HierarchyFilter {
+    pub query: String,
+    pub kind: HierarchyKind,
+}
+
+impl HierarchyFilter {
+    pub fn is_active(&self) -> bool {
+        !self.query.trim().is_empty() || self.kind != HierarchyKind::All
+    }
+
+    /// Whether a row of `kind` called `name` passes the filter.
+    pub fn matches(&self, kind: HierarchyKind, name: &str) -> bool {
+        let query = self.query.trim().to_lowercase();
+        (self.kind == HierarchyKind::All || self.kind == kind)
+            && (query.is_empty() || name.to_lowercase().contains(&query))
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum HierarchyKind {
+    #[default]
+    All,
+    Objects,
+    Lights,
+    Camera,
+    Particles,
+    Terrain,
+}
+
+impl HierarchyKind {
+    pub const ALL: [HierarchyKind; 6] = [
+        HierarchyKind::All, HierarchyKind::Objects, HierarchyKind::Lights,
+        HierarchyKind::Camera, HierarchyKind::Particles, HierarchyKind::Terrain,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HierarchyKind::All => "All",
+            HierarchyKind::Objects => "Objects",
+            HierarchyKind::Lights => "Lights",
+            HierarchyKind::Camera => "Camera",
+            HierarchyKind::Particles => "Particles",
+            HierarchyKind::Terrain => "Terrain",
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum DragPayload {
+    /// A model from the resource browser, spawned where it's dropped.
     Resource(Uuid),
+    /// Dropped onto the shape under the cursor.
     Material(Uuid),
+    /// A particle system, dropped as an emitter.
+    Particle(Uuid),
+    /// A row of the hierarchy, dropped onto another to parent it.
+    SceneObject(Uuid),
 }
 
 #[derive(Clone, Debug)]
@@ -379,6 +985,7 @@ pub enum Modal {
     ConfirmDelete { label: String, pending: PendingDelete },
     ImportError(String),
     NewSceneName(String),
+    NewScriptName(String),
 }
 
 #[derive(Clone, Debug)]
@@ -387,10 +994,38 @@ pub enum PendingDelete {
     Material(Uuid),
     Scene(usize),
     SceneObject(Uuid),
-    Light(usize),
+    Light(Uuid),
     AmbientLight,
     Particle(Uuid),
     ParticleInstance(Uuid),
+    Script(Uuid),
+    /// Everything in a multi-selection.
+    Many(Vec<PendingDelete>),
+}
+
+impl PendingDelete {
+    /// The delete for a selected scene item, if it can be deleted.
+    pub fn for_selection(item: &Selection) -> Option<Self> {
+        match item {
+            Selection::SceneObject(u) => Some(PendingDelete::SceneObject(*u)),
+            Selection::Light(u) => Some(PendingDelete::Light(*u)),
+            Selection::AmbientLight => Some(PendingDelete::AmbientLight),
+            Selection::ParticleInstance(u) => Some(PendingDelete::ParticleInstance(*u)),
+            _ => None,
+        }
+    }
+
+    /// What the confirmation asks to delete.
+    pub fn label(&self) -> String {
+        match self {
+            PendingDelete::SceneObject(_) => "object".to_string(),
+            PendingDelete::Light(_) => "light".to_string(),
+            PendingDelete::AmbientLight => "ambient light".to_string(),
+            PendingDelete::ParticleInstance(_) => "particle instance".to_string(),
+            PendingDelete::Many(items) => format!("{} selected items", items.len()),
+            _ => "item".to_string(),
+        }
+    }
 }
 
 #[derive(Default, Clone, Copy, PartialEq, Debug)]
@@ -400,6 +1035,7 @@ pub enum ResourceTab {
     Shaders,
     Materials,
     Particles,
+    Scripts,
     Scenes,
     Audio,
     Other,
@@ -411,12 +1047,12 @@ pub enum RenameTarget {
     Material { uuid: Uuid, draft: String },
     Scene { index: usize, draft: String },
     SceneObject { uuid: Uuid, draft: String },
-    Light { index: usize, draft: String },
+    Light { uuid: Uuid, draft: String },
     Particle { uuid: Uuid, draft: String },
     ParticleInstance { uuid: Uuid, draft: String },
 }
 
-#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum GizmoMode {
     #[default]
     None,
@@ -425,7 +1061,7 @@ pub enum GizmoMode {
     Scale,
 }
 
-#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum Space {
     #[default]
     World,
@@ -435,22 +1071,97 @@ pub enum Space {
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Axis { X, Y, Z }
 
+/// Viewport projection. Orthographic is approximated with a very narrow
+/// field of view from far away; see `editor::navigation`.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Projection {
+    #[default]
+    Perspective,
+    Orthographic,
+}
+
+/// Axis-aligned views picked from the view cube.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ViewPreset { Top, Bottom, Front, Back, Right, Left }
+
+impl ViewPreset {
+    pub fn label(self) -> &'static str {
+        match self {
+            ViewPreset::Top => "Top",
+            ViewPreset::Bottom => "Bottom",
+            ViewPreset::Front => "Front",
+            ViewPreset::Back => "Back",
+            ViewPreset::Right => "Right",
+            ViewPreset::Left => "Left",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Navigation {
+    pub projection: Projection,
+    /// How far ahead of the editor camera its focus point is; the orbit
+    /// pivot when nothing is selected.
+    pub focus_distance: f32,
+    /// Field of view and far plane to restore when leaving orthographic.
+    pub perspective: Option<(f32, f32)>,
+    /// The view cube view the camera is in, until it's turned away.
+    pub preset: Option<ViewPreset>,
+    pub fly: FlySettings,
+}
+
+impl Default for Navigation {
+    fn default() -> Self {
+        Self {
+            projection: Projection::Perspective,
+            focus_distance: 10.0,
+            perspective: None,
+            preset: None,
+            fly: FlySettings::default(),
+        }
+    }
+}
+
+/// Right-drag fly navigation; saved in the editor preferences.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct FlySettings {
+    /// World units per second.
+    pub speed: f32,
+    /// Radians per pixel.
+    pub look_sensitivity: f32,
+}
+
+impl Default for FlySettings {
+    fn default() -> Self {
+        Self { speed: 4.0, look_sensitivity: 0.005 }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Handle {
     Axis(Axis),
     Center,
+    /// Move across the plane normal to this axis.
+    Plane(Axis),
+    /// Move in the view plane, or turn about the view direction.
+    View,
+    /// Free rotation, rolled like a ball.
+    Trackball,
 }
 
+/// Translate and rotate drags keep the cursor they started at, so X/Y/Z can
+/// swap `handle` mid-drag and the drag is measured again from the start.
 pub enum Drag {
     Translate {
-        axis: Axis,
+        handle: Handle,
         start_pos: Vector3<f32>,
-        start_on_axis: Vector3<f32>,
+        start_cursor: egui::Pos2,
     },
     Rotate {
-        axis: Axis,
+        handle: Handle,
         start_quat: UnitQuaternion<f32>,
-        start_dir: Vector3<f32>,
+        start_cursor: egui::Pos2,
     },
     Scale {
         handle: Handle,
@@ -461,6 +1172,15 @@ pub enum Drag {
     },
 }
 
+/// One selected item as it was when a group drag began.
+#[derive(Clone, Debug)]
+pub struct GroupStart {
+    pub item: Selection,
+    pub position: Vector3<f32>,
+    pub rotation: UnitQuaternion<f32>,
+    pub scale: Vector3<f32>,
+}
+
 pub struct GizmoState {
     pub mode: GizmoMode,
     pub space: Space,
@@ -472,8 +1192,81 @@ pub struct GizmoState {
     /// `None` until the start-injection has registered the overlay.
     pub grid_overlay_enable: Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
     pub drag: Option<Drag>,
+    /// Start transforms of every selected item, taken when a drag begins.
+    pub drag_group: Vec<GroupStart>,
     pub hovered_handle: Option<Handle>,
     pub consumed_click_this_frame: bool,
+    /// Increments and snap targets; saved in the editor preferences.
+    pub snap: SnapSettings,
+    /// Vertex of the dragged objects that vertex snapping moves onto the
+    /// target, picked when a translate drag begins.
+    pub snap_source: Option<Vector3<f32>>,
+    /// Value typed during a drag, replacing the cursor's distance or angle.
+    pub entry: String,
+}
+
+/// Window size in points and the docked panels.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct Layout {
+    /// Window size in physical pixels.
+    pub window: [f32; 2],
+    pub dock: crate::editor::dock::DockLayout,
+    /// Arrangements saved under a name from the Window menu.
+    pub saved: Vec<crate::editor::dock::NamedLayout>,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self { window: [1080.0, 720.0], dock: Default::default(), saved: Vec::new() }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct RecentProject {
+    /// The project file.
+    pub path: String,
+    pub name: String,
+    /// Unix seconds.
+    pub opened_at: u64,
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct PaletteState {
+    pub query: String,
+    /// Highlighted row among the matches.
+    pub selected: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum SpawnAt {
+    #[default]
+    InFrontOfCamera,
+    Cursor,
+}
+
+/// Step sizes for each gizmo mode and the translate snap targets.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(default)]
+pub struct SnapSettings {
+    /// World units.
+    pub translate: f32,
+    pub rotate_degrees: f32,
+    /// Scale factor step.
+    pub scale: f32,
+    /// Translate drags move a vertex of the selection onto the nearest
+    /// vertex of another object under the cursor.
+    pub vertex: bool,
+    /// Translate drags drop the selection onto the surface under the cursor.
+    pub surface: bool,
+    /// With `surface`, turn the selection's up axis to the surface normal.
+    pub align_to_normal: bool,
+}
+
+impl Default for SnapSettings {
+    fn default() -> Self {
+        Self { translate: 1.0, rotate_degrees: 15.0, scale: 0.1, vertex: false, surface: false, align_to_normal: false }
+    }
 }
 
 impl Default for GizmoState {
@@ -485,8 +1278,12 @@ impl Default for GizmoState {
             grid_enabled: true,
             grid_overlay_enable: None,
             drag: None,
+            drag_group: Vec::new(),
             hovered_handle: None,
             consumed_click_this_frame: false,
+            snap: SnapSettings::default(),
+            snap_source: None,
+            entry: String::new(),
         }
     }
 }
@@ -548,4 +1345,84 @@ mod tests {
         assert_eq!(ResourceKind::Audio.dir_name(), "audio");
         assert_eq!(ResourceKind::Other.dir_name(), "other");
     }
+
+    #[test]
+    fn selection_set_add_toggle_and_primary() {
+        let (a, b, light) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut set = SelectionSet::default();
+        assert_eq!(*set.primary(), Selection::None);
+        set.set(Selection::SceneObject(a));
+        set.add(Selection::Light(light));
+        set.add(Selection::SceneObject(b));
+        assert_eq!(set.items().len(), 3);
+        assert_eq!(*set.primary(), Selection::SceneObject(b));
+        // Re-adding makes an item primary again.
+        set.add(Selection::SceneObject(a));
+        assert_eq!(*set.primary(), Selection::SceneObject(a));
+        assert_eq!(set.scene_objects(), vec![b, a]);
+
+        set.toggle(Selection::Light(light));
+        assert!(!set.contains(&Selection::Light(light)));
+        set.toggle(Selection::SceneObject(a));
+        assert_eq!(*set.primary(), Selection::SceneObject(b));
+    }
+
+    #[test]
+    fn selection_set_keeps_non_scene_items_single() {
+        let mut set = SelectionSet::default();
+        set.set(Selection::SceneObject(Uuid::new_v4()));
+        set.add(Selection::Material(Uuid::new_v4()));
+        assert_eq!(set.items().len(), 1);
+        assert!(matches!(set.primary(), Selection::Material(_)));
+        let light = Uuid::new_v4();
+        set.toggle(Selection::Light(light));
+        assert_eq!(set.items(), &[Selection::Light(light)]);
+        set.set(Selection::None);
+        assert_eq!(set.items().len(), 0);
+    }
+
+    #[test]
+    fn selection_set_joins_materials_with_materials() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut set = SelectionSet::default();
+        set.set(Selection::Material(a));
+        set.toggle(Selection::Material(b));
+        assert_eq!(set.materials(), vec![a, b]);
+        set.add(Selection::SceneObject(Uuid::new_v4()));
+        assert!(set.materials().is_empty());
+        set.add(Selection::Material(a));
+        assert_eq!(set.items(), &[Selection::Material(a)]);
+    }
+
+    #[test]
+    fn object_flags_set_clear_and_prune() {
+        let mut p = ProjectState::new("t".into(), "/tmp/t".into());
+        let (scene, a, b) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        p.update_flags(scene, a, |f| f.hidden = true);
+        p.update_flags(scene, a, |f| f.locked = true);
+        p.update_flags(scene, b, |f| f.locked = true);
+        assert!(p.is_hidden(scene, a) && p.is_locked(scene, a));
+        assert!(!p.is_hidden(scene, b) && p.is_locked(scene, b));
+        assert_eq!(p.object_flags.len(), 2);
+
+        // Clearing every flag drops the entry.
+        p.update_flags(scene, b, |f| f.locked = false);
+        assert_eq!(p.object_flags.len(), 1);
+
+        p.set_scene_objects(scene, &[(b, "b".into())]);
+        assert!(p.object_flags.is_empty());
+    }
+
+    #[test]
+    fn hierarchy_filter_matches_name_and_kind() {
+        let mut filter = HierarchyFilter::default();
+        assert!(!filter.is_active());
+        assert!(filter.matches(HierarchyKind::Lights, "Light 0"));
+        filter.query = " CUBE ".into();
+        assert!(filter.matches(HierarchyKind::Objects, "big cube"));
+        assert!(!filter.matches(HierarchyKind::Objects, "sphere"));
+        filter.kind = HierarchyKind::Lights;
+        assert!(!filter.matches(HierarchyKind::Objects, "big cube"));
+        assert!(filter.is_active());
+    }
 }
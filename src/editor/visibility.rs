@@ -0,0 +1,211 @@
+//! Hide, lock and isolate for scene objects.
+//!
+//! The engine draws everything in `app_state.objects`, so a hidden object is
+//! moved into `EditorState::hidden_stash` and back once it's shown again.
+//! Anything that serializes the scene goes through `serialize` so hidden
+//! objects are still saved, snapshotted and listed.
+
+use enigma_3d::{AppState, AppStateSerializer};
+use enigma_3d::object::Object;
+use uuid::Uuid;
+
+use crate::editor::{cameras, scene_graph};
+use crate::editor::state::{EditorRoot, HiddenObject};
+
+fn active_scene(root: &EditorRoot) -> Option<Uuid> {
+    let project = root.project.as_ref()?;
+    project.scenes.get(project.active_scene_index).map(|s| s.uuid)
+}
+
+/// Per-frame: move objects between the scene and the stash so the stash
+/// holds exactly the hidden objects of the active scene.
+pub fn reconcile(app_state: &mut AppState) {
+    let all = all_objects(app_state);
+    let (hidden, stale_isolate): (Vec<Uuid>, bool) = {
+        let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return; };
+        let (Some(project), Some(scene)) = (root.project.as_ref(), active_scene(root)) else { return; };
+        // Isolating objects that are all gone (deleted, or another scene loaded) ends isolation.
+        let isolated = root.editor.isolate.as_ref()
+            .filter(|set| all.iter().any(|(uuid, _)| set.contains(uuid)));
+        let hidden = all.iter()
+            .map(|(uuid, _)| *uuid)
+            .filter(|uuid| project.is_hidden(scene, *uuid) || isolated.map(|set| !set.contains(uuid)).unwrap_or(false))
+            .collect();
+        (hidden, root.editor.isolate.is_some() && isolated.is_none())
+    };
+    let scene = app_state.get_state_data_value::<EditorRoot>("editor").and_then(active_scene);
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    if stale_isolate {
+        root.editor.isolate = None;
+    }
+    let mut stash = std::mem::take(&mut root.editor.hidden_stash);
+    // Stashed objects of a scene that's no longer loaded were saved with it.
+    stash.retain(|h| Some(h.scene) == scene);
+
+    // Shown again: back to where they were, lowest index first.
+    let (shown, kept): (Vec<_>, Vec<_>) = stash.into_iter()
+        .partition(|h| !hidden.contains(&h.object.get_unique_id()));
+    stash = kept;
+    for entry in shown {
+        let at = visible_index(&indices(&stash), entry.index).min(app_state.objects.len());
+        app_state.objects.insert(at, entry.object);
+    }
+
+    // Just hidden, or came back through a scene reload.
+    if let Some(scene) = scene {
+        let mut i = 0;
+        while i < app_state.objects.len() {
+            let uuid = app_state.objects[i].get_unique_id();
+            if !hidden.contains(&uuid) {
+                i += 1;
+                continue;
+            }
+            let object = app_state.objects.remove(i);
+            stash.retain(|h| h.object.get_unique_id() != uuid);
+            let index = full_index(&indices(&stash), i);
+            let at = stash.partition_point(|h| h.index < index);
+            stash.insert(at, HiddenObject { scene, index, object });
+        }
+    }
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.hidden_stash = stash;
+    }
+}
+
+fn indices(stash: &[HiddenObject]) -> Vec<usize> {
+    stash.iter().map(|h| h.index).collect()
+}
+
+/// Index in the full list of the object at `visible` in the shown list,
+/// given the sorted full indices of the hidden ones.
+fn full_index(hidden: &[usize], visible: usize) -> usize {
+    let mut index = visible;
+    for &h in hidden {
+        if h <= index { index += 1; } else { break; }
+    }
+    index
+}
+
+/// Where an object at `full` goes in the shown list.
+fn visible_index(hidden: &[usize], full: usize) -> usize {
+    full - hidden.iter().filter(|&&h| h < full).count()
+}
+
+/// Serialize the scene including the hidden objects in their places, with
+/// the game camera rather than the editor's.
+pub fn serialize(app_state: &mut AppState) -> AppStateSerializer {
+    let stash = app_state.get_state_data_value_mut::<EditorRoot>("editor")
+        .map(|r| std::mem::take(&mut r.editor.hidden_stash))
+        .unwrap_or_default();
+    // Moved rather than cloned so the objects keep their ids.
+    let mut placed = Vec::with_capacity(stash.len());
+    for entry in stash {
+        let at = entry.index.min(app_state.objects.len());
+        app_state.objects.insert(at, entry.object);
+        placed.push((at, entry.scene, entry.index));
+    }
+    let serializer = cameras::with_game_camera(app_state, |a| a.to_serializer());
+    let mut stash: Vec<HiddenObject> = placed.into_iter().rev()
+        .map(|(at, scene, index)| HiddenObject { scene, index, object: app_state.objects.remove(at) })
+        .collect();
+    stash.reverse();
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.hidden_stash = stash;
+    }
+    serializer
+}
+
+/// The object `uuid`, shown or hidden.
+pub fn find(app_state: &AppState, uuid: Uuid) -> Option<&Object> {
+    app_state.objects.iter().find(|o| o.get_unique_id() == uuid).or_else(|| {
+        app_state.get_state_data_value::<EditorRoot>("editor")?
+            .editor.hidden_stash.iter()
+            .map(|h| &h.object)
+            .find(|o| o.get_unique_id() == uuid)
+    })
+}
+
+pub fn find_mut(app_state: &mut AppState, uuid: Uuid) -> Option<&mut Object> {
+    if let Some(i) = app_state.objects.iter().position(|o| o.get_unique_id() == uuid) {
+        return app_state.objects.get_mut(i);
+    }
+    app_state.get_state_data_value_mut::<EditorRoot>("editor")?
+        .editor.hidden_stash.iter_mut()
+        .map(|h| &mut h.object)
+        .find(|o| o.get_unique_id() == uuid)
+}
+
+/// Uuid and name of every object in the scene, hidden ones last.
+pub fn all_objects(app_state: &AppState) -> Vec<(Uuid, String)> {
+    let mut objects: Vec<(Uuid, String)> = app_state.objects.iter()
+        .map(|o| (o.get_unique_id(), o.name.clone()))
+        .collect();
+    if let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") {
+        objects.extend(root.editor.hidden_stash.iter().map(|h| (h.object.get_unique_id(), h.object.name.clone())));
+    }
+    objects
+}
+
+/// Locked objects can't be picked in the viewport or moved by the gizmo.
+pub fn is_locked(app_state: &AppState, uuid: Uuid) -> bool {
+    let Some(root) = app_state.get_state_data_value::<EditorRoot>("editor") else { return false; };
+    let (Some(project), Some(scene)) = (root.project.as_ref(), active_scene(root)) else { return false; };
+    project.is_locked(scene, uuid)
+}
+
+pub fn toggle_hidden(app_state: &mut AppState, uuid: Uuid) {
+    update(app_state, uuid, |f| f.hidden = !f.hidden);
+}
+
+pub fn toggle_locked(app_state: &mut AppState, uuid: Uuid) {
+    update(app_state, uuid, |f| f.locked = !f.locked);
+}
+
+fn update(app_state: &mut AppState, uuid: Uuid, f: impl FnOnce(&mut crate::editor::state::ObjectFlags)) {
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let Some(scene) = active_scene(root) else { return; };
+    let Some(project) = root.project.as_mut() else { return; };
+    project.update_flags(scene, uuid, f);
+    root.editor.dirty = true;
+}
+
+/// Show only the selected objects and their children, or leave isolation.
+pub fn toggle_isolate(app_state: &mut AppState) {
+    let isolating = app_state.get_state_data_value::<EditorRoot>("editor")
+        .map(|r| r.editor.isolate.is_some())
+        .unwrap_or(false);
+    let set = if isolating {
+        None
+    } else {
+        let selected = app_state.get_state_data_value::<EditorRoot>("editor")
+            .map(|r| r.editor.selection.scene_objects())
+            .unwrap_or_default();
+        if selected.is_empty() { return; }
+        let mut set: std::collections::HashSet<Uuid> = selected.iter().copied().collect();
+        for uuid in selected {
+            set.extend(scene_graph::descendants_of(app_state, uuid));
+        }
+        Some(set)
+    };
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.isolate = set;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidden_objects_keep_their_place() {
+        // Full list a b c d e; b and d hidden, so a c e are shown.
+        let hidden = [1, 3];
+        assert_eq!(full_index(&hidden, 0), 0);
+        assert_eq!(full_index(&hidden, 1), 2);
+        assert_eq!(full_index(&hidden, 2), 4);
+        // Showing d puts it between c and e; b stays hidden.
+        assert_eq!(visible_index(&[1], 3), 2);
+        // Showing b first puts it between a and c.
+        assert_eq!(visible_index(&[3], 1), 1);
+    }
+}
@@ -11,13 +11,16 @@ use crate::editor::state::EditorRoot;
 
 fn main() {
     let mut app_state = AppState::new();
-    let event_loop = enigma_3d::EventLoop::new("Enigma 3d - Engine", 1080, 720);
+    let prefs = editor::prefs::read();
+    let [width, height] = prefs.layout.window;
+    let event_loop = enigma_3d::EventLoop::new("Enigma 3d - Engine", width as u32, height as u32);
     event_loop.set_icon_from_resource(resources::ICON);
 
     let camera = Camera::new(None, None, Some(60.0), Some(16.0/9.0), Some(0.1), Some(100.0));
     app_state.set_camera(camera);
 
     app_state.add_state_data("editor", Box::new(EditorRoot::empty()));
+    editor::prefs::apply(&mut app_state, prefs);
     app_state.inject_gui(Arc::new(editor::draw));
     app_state.inject_start_function(Arc::new(register_grid_overlay));
 
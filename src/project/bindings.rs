@@ -0,0 +1,358 @@
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use enigma_3d::AppState;
+
+use crate::editor::state::{EditorRoot, ProjectState, ResourceKind};
+
+/// Game-crate file the generated bindings are written to, relative to the
+/// project root. The game's `main.rs` declares it as `mod enigma_assets;`;
+/// `missing_modules` reports projects whose `main.rs` predates that line.
+pub const BINDINGS_FILE: &str = "src/enigma_assets.rs";
+
+/// Modules the editor writes into the game crate, as the `main.rs` template
+/// declares them. Projects created before one of them existed lack its line.
+pub const GAME_MODULES: &[&str] = &["enigma_assets", "enigma_scripts", "scripts", "enigma_live_link", "enigma_hierarchy"];
+
+const HEADER: &str = "\
+// @generated by enigma-engine from enigma_project.json.
+// Regenerated on every save and build; manual edits will be lost.
+#![allow(dead_code)]
+";
+
+/// Render the bindings module for `project`. Output is deterministic so an
+/// unchanged project produces a byte-identical file (and no cargo rebuild).
+pub fn generate(project: &ProjectState) -> String {
+    let mut out = String::from(HEADER);
+    out.push_str(TYPES);
+
+    out.push_str("\npub mod scenes {\n");
+    let mut used = HashSet::new();
+    // Reserve STARTUP so a scene literally named "startup" can't shadow it.
+    used.insert("STARTUP".to_string());
+    let mut startup_ident: Option<String> = None;
+    for (idx, scene) in project.scenes.iter().enumerate() {
+        let ident = unique(const_ident(&scene.name), &mut used);
+        let _ = writeln!(
+            out,
+            "    pub const {ident}: super::Scene = super::Scene {{ uuid: {:?}, name: {:?}, path: {:?} }};",
+            scene.uuid.to_string(), scene.name, scene.relative_path,
+        );
+        if idx == project.startup_scene_index {
+            startup_ident = Some(ident);
+        }
+    }
+    if let Some(ident) = startup_ident {
+        let _ = writeln!(out, "    pub const STARTUP: super::Scene = {ident};");
+    }
+    out.push_str("}\n");
+
+    for kind in [ResourceKind::Model, ResourceKind::Texture, ResourceKind::Shader, ResourceKind::Audio, ResourceKind::Other] {
+        let _ = writeln!(out, "\npub mod {} {{", kind_module(kind));
+        let mut used = HashSet::new();
+        for entry in project.manifest.iter().filter(|e| e.kind == kind) {
+            let ident = unique(const_ident(&entry.name), &mut used);
+            let _ = writeln!(
+                out,
+                "    pub const {ident}: super::Resource = super::Resource {{ uuid: {:?}, name: {:?}, path: {:?} }};",
+                entry.uuid.to_string(), entry.name, entry.relative_path,
+            );
+        }
+        out.push_str("}\n");
+    }
+
+    out.push_str("\npub mod materials {\n");
+    let mut used = HashSet::new();
+    for mat in project.materials.iter().filter(|m| !m.name.starts_with("INTERNAL::")) {
+        let ident = unique(const_ident(&mat.name), &mut used);
+        let _ = writeln!(
+            out,
+            "    pub const {ident}: super::Material = super::Material {{ uuid: {:?}, name: {:?} }};",
+            mat.uuid.to_string(), mat.name,
+        );
+    }
+    out.push_str("}\n");
+
+    out.push_str("\npub mod particles {\n");
+    let mut used = HashSet::new();
+    for def in &project.particle_systems {
+        let ident = unique(const_ident(&def.config.name), &mut used);
+        let _ = writeln!(
+            out,
+            "    pub const {ident}: super::ParticleSystem = super::ParticleSystem {{ uuid: {:?}, name: {:?} }};",
+            def.uuid.to_string(), def.config.name,
+        );
+    }
+    out.push_str("}\n");
+
+    out.push_str("\npub mod objects {\n");
+    let mut used_modules = HashSet::new();
+    for scene in &project.scenes {
+        let module = unique(module_ident(&scene.name), &mut used_modules);
+        let _ = writeln!(out, "    pub mod {module} {{");
+        let mut used = HashSet::new();
+        for obj in project.scene_objects.iter().filter(|o| o.scene == scene.uuid) {
+            let ident = unique(const_ident(&obj.name), &mut used);
+            let _ = writeln!(
+                out,
+                "        pub const {ident}: super::super::SceneObject = super::super::SceneObject {{ uuid: {:?}, name: {:?}, scene: {:?} }};",
+                obj.uuid.to_string(), obj.name, scene.uuid.to_string(),
+            );
+        }
+        out.push_str("    }\n");
+    }
+    out.push_str("}\n");
+
+    // Parents before children, as the hierarchy runtime applies them in order.
+    out.push_str("\npub const PARENTS: &[ParentLink] = &[\n");
+    for link in crate::editor::scene_graph::ordered_links(project) {
+        let _ = writeln!(
+            out,
+            "    ParentLink {{ child: {:?}, parent: {:?} }},",
+            link.child.to_string(), link.parent.to_string(),
+        );
+    }
+    out.push_str("];\n");
+    out
+}
+
+const TYPES: &str = "
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Scene {
+    pub uuid: &'static str,
+    pub name: &'static str,
+    /// Path relative to `src/resources`.
+    pub path: &'static str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Resource {
+    pub uuid: &'static str,
+    pub name: &'static str,
+    /// Path relative to `src/resources`.
+    pub path: &'static str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Material {
+    pub uuid: &'static str,
+    pub name: &'static str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParticleSystem {
+    pub uuid: &'static str,
+    pub name: &'static str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SceneObject {
+    pub uuid: &'static str,
+    pub name: &'static str,
+    /// Uuid of the scene the object lives in.
+    pub scene: &'static str,
+}
+
+/// `child` is attached to `parent`; both are object uuids.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParentLink {
+    pub child: &'static str,
+    pub parent: &'static str,
+}
+";
+
+/// Regenerate the bindings file. Skips the write when the content is
+/// unchanged so cargo doesn't see a fresh mtime. Returns whether it wrote.
+pub fn write(project: &ProjectState) -> std::io::Result<bool> {
+    let path = Path::new(&project.root_path).join(BINDINGS_FILE);
+    let text = generate(project);
+    if fs::read_to_string(&path).map(|old| old == text).unwrap_or(false) {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, text)?;
+    Ok(true)
+}
+
+/// The `GAME_MODULES` the game's `src/main.rs` at `root` doesn't declare.
+/// An unreadable `main.rs` reports nothing; cargo will complain about it.
+pub fn missing_main_modules(root: &Path) -> Vec<&'static str> {
+    fs::read_to_string(root.join("src/main.rs"))
+        .map(|source| missing_modules(&source))
+        .unwrap_or_default()
+}
+
+fn missing_modules(main_rs: &str) -> Vec<&'static str> {
+    let declared: HashSet<&str> = main_rs.lines()
+        .map(str::trim)
+        .filter_map(|line| line.strip_prefix("pub ").unwrap_or(line).strip_prefix("mod "))
+        .filter_map(|rest| rest.strip_suffix(';'))
+        .map(str::trim)
+        .collect();
+    GAME_MODULES.iter().copied().filter(|m| !declared.contains(m)).collect()
+}
+
+/// Copy the live objects of the active scene, hidden ones included, into
+/// the project's object index so the next `generate` sees renames, additions
+/// and deletions.
+/// Must run on the main thread (reads `AppState`).
+pub fn refresh_scene_objects(app_state: &mut AppState) {
+    let objects = crate::editor::visibility::all_objects(app_state);
+    let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") else { return; };
+    let Some(project) = root.project.as_mut() else { return; };
+    let Some(scene) = project.scenes.get(project.active_scene_index).map(|s| s.uuid) else { return; };
+    project.set_scene_objects(scene, &objects);
+}
+
+fn kind_module(kind: ResourceKind) -> &'static str {
+    match kind {
+        ResourceKind::Model => "models",
+        ResourceKind::Texture => "textures",
+        ResourceKind::Shader => "shaders",
+        ResourceKind::Audio => "audio",
+        ResourceKind::Other => "other",
+    }
+}
+
+/// `"grass tile-2"` -> `GRASS_TILE_2`.
+fn const_ident(name: &str) -> String {
+    let ident = words(name).to_uppercase();
+    if ident.is_empty() { "UNNAMED".into() } else { ident }
+}
+
+/// `"Level 1"` -> `level_1`. Keywords get a trailing underscore.
+fn module_ident(name: &str) -> String {
+    let ident = words(name).to_lowercase();
+    if ident.is_empty() { return "unnamed".into(); }
+    if is_keyword(&ident) { format!("{ident}_") } else { ident }
+}
+
+/// Collapse every run of non-identifier characters to one `_` and make sure
+/// the result doesn't start with a digit.
+fn words(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c);
+        } else if !out.is_empty() && !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    while out.ends_with('_') { out.pop(); }
+    if out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+fn unique(ident: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = ident.clone();
+    let mut n = 2;
+    while used.contains(&candidate) {
+        candidate = format!("{ident}_{n}");
+        n += 1;
+    }
+    used.insert(candidate.clone());
+    candidate
+}
+
+pub(crate) fn is_keyword(s: &str) -> bool {
+    matches!(s,
+        "as" | "async" | "await" | "break" | "const" | "continue" | "crate" | "dyn" | "else"
+        | "enum" | "extern" | "false" | "fn" | "for" | "if" | "impl" | "in" | "let" | "loop"
+        | "match" | "mod" | "move" | "mut" | "pub" | "ref" | "return" | "self" | "static"
+        | "struct" | "super" | "trait" | "true" | "type" | "unsafe" | "use" | "where" | "while"
+        | "abstract" | "become" | "box" | "do" | "final" | "macro" | "override" | "priv"
+        | "typeof" | "unsized" | "virtual" | "yield" | "try")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+    use crate::editor::state::{MaterialDef, ResourceEntry, SceneRef};
+
+    fn scene(name: &str) -> SceneRef {
+        SceneRef {
+            uuid: Uuid::new_v4(),
+            name: name.into(),
+            relative_path: format!("scenes/{name}.json"),
+            terrain: None,
+            particle_instances: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn const_ident_normalizes_names() {
+        assert_eq!(const_ident("grass"), "GRASS");
+        assert_eq!(const_ident("Grass Tile-2"), "GRASS_TILE_2");
+        assert_eq!(const_ident("  rock!! "), "ROCK");
+        assert_eq!(const_ident("2d sprite"), "_2D_SPRITE");
+        assert_eq!(const_ident("???"), "UNNAMED");
+    }
+
+    #[test]
+    fn module_ident_escapes_keywords() {
+        assert_eq!(module_ident("Level 1"), "level_1");
+        assert_eq!(module_ident("type"), "type_");
+    }
+
+    #[test]
+    fn generate_emits_constants_per_category() {
+        let mut p = ProjectState::new("t".into(), "/tmp/t".into());
+        p.scenes.push(scene("main"));
+        p.manifest.push(ResourceEntry {
+            uuid: Uuid::new_v4(),
+            name: "grass".into(),
+            kind: ResourceKind::Texture,
+            relative_path: "textures/grass.png".into(),
+        });
+        p.materials.push(MaterialDef::default_pbr("Rock".into()));
+        let scene_uuid = p.scenes[0].uuid;
+        let (player, sword) = (Uuid::new_v4(), Uuid::new_v4());
+        p.set_scene_objects(scene_uuid, &[(player, "Player".into()), (sword, "Sword".into())]);
+        crate::editor::scene_graph::set_parent(&mut p, scene_uuid, sword, Some(player)).unwrap();
+
+        let text = generate(&p);
+        assert!(text.contains("pub const MAIN: super::Scene"));
+        assert!(text.contains("pub const STARTUP: super::Scene = MAIN;"));
+        assert!(text.contains("pub const GRASS: super::Resource"));
+        assert!(text.contains("\"textures/grass.png\""));
+        assert!(text.contains("pub const ROCK: super::Material"));
+        assert!(text.contains("pub mod main {"));
+        assert!(text.contains("pub const PLAYER: super::super::SceneObject"));
+        assert!(text.contains(&format!("ParentLink {{ child: \"{sword}\", parent: \"{player}\" }}")));
+    }
+
+    #[test]
+    fn generate_dedupes_colliding_names() {
+        let mut p = ProjectState::new("t".into(), "/tmp/t".into());
+        p.materials.push(MaterialDef::default_pbr("rock".into()));
+        p.materials.push(MaterialDef::default_pbr("Rock".into()));
+        let text = generate(&p);
+        assert!(text.contains("pub const ROCK: super::Material"));
+        assert!(text.contains("pub const ROCK_2: super::Material"));
+    }
+
+    #[test]
+    fn write_skips_unchanged_content() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut p = ProjectState::new("t".into(), tmp.path().to_string_lossy().into_owned());
+        p.scenes.push(scene("main"));
+        assert!(write(&p).unwrap());
+        assert!(tmp.path().join(BINDINGS_FILE).is_file());
+        assert!(!write(&p).unwrap());
+        p.materials.push(MaterialDef::default_pbr("Rock".into()));
+        assert!(write(&p).unwrap());
+    }
+
+    #[test]
+    fn missing_modules_reports_lines_old_main_rs_lacks() {
+        assert!(missing_modules(crate::resources::MAIN_RS).is_empty());
+        let old = "mod enigma_assets;\n// mod enigma_scripts;\npub mod scripts;\nfn main() {}\n";
+        assert_eq!(missing_modules(old), vec!["enigma_scripts", "enigma_live_link", "enigma_hierarchy"]);
+    }
+}
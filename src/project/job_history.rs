@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+use crate::editor::logging::{self, Source};
+use crate::editor::state::JobRecord;
+
+/// History file, relative to the project root. Editor-private, like `.trash`.
+pub const HISTORY_FILE: &str = ".enigma/job_history.json";
+/// Oldest entries are dropped beyond this.
+pub const MAX_ENTRIES: usize = 20;
+
+/// Read the history of the project at `root`. A missing or unreadable file
+/// is an empty history; it's a convenience, not project data.
+pub fn load(root: &str) -> Vec<JobRecord> {
+    let path = Path::new(root).join(HISTORY_FILE);
+    fs::read_to_string(path).ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(root: &str, records: &[JobRecord]) -> std::io::Result<()> {
+    let path = Path::new(root).join(HISTORY_FILE);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let text = serde_json::to_string(records).map_err(std::io::Error::other)?;
+    fs::write(path, text)
+}
+
+/// Append `record`, trimming to `MAX_ENTRIES`.
+pub fn push(records: &mut Vec<JobRecord>, record: JobRecord) {
+    records.push(record);
+    if records.len() > MAX_ENTRIES {
+        let overflow = records.len() - MAX_ENTRIES;
+        records.drain(0..overflow);
+    }
+}
+
+/// Numbers the saves in the order they were started.
+static NEXT_SAVE: AtomicU64 = AtomicU64::new(1);
+/// The newest save written so far. Held while writing, so writes don't
+/// overlap and an older one never lands after a newer one.
+static WRITTEN: Mutex<u64> = Mutex::new(0);
+
+/// Write the history off the main thread; logs can be large.
+pub fn start_save(root: String, records: Vec<JobRecord>) {
+    let number = NEXT_SAVE.fetch_add(1, Ordering::Relaxed);
+    thread::spawn(move || {
+        let mut written = WRITTEN.lock().unwrap_or_else(|e| e.into_inner());
+        if *written > number { return; }
+        if let Err(e) = save(&root, &records) {
+            logging::warn(Source::Build, format!("Could not save the job history: {e}"));
+        }
+        *written = number;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor::state::JobOutcome;
+
+    fn record(label: &str) -> JobRecord {
+        JobRecord {
+            id: uuid::Uuid::new_v4(),
+            outcome: JobOutcome {
+                label: label.into(),
+                success: true,
+                duration: std::time::Duration::from_millis(1500),
+                message: "ok".into(),
+            },
+            finished_at: 1_700_000_000,
+            log: vec!["Compiling game".into(), "Finished".into()],
+        }
+    }
+
+    #[test]
+    fn push_trims_oldest() {
+        let mut records = Vec::new();
+        for i in 0..MAX_ENTRIES + 3 {
+            push(&mut records, record(&format!("job {i}")));
+        }
+        assert_eq!(records.len(), MAX_ENTRIES);
+        assert_eq!(records[0].outcome.label, "job 3");
+    }
+
+    #[test]
+    fn save_and_load_roundtrip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().to_string_lossy().into_owned();
+        assert!(load(&root).is_empty());
+        save(&root, &[record("Run")]).unwrap();
+        let loaded = load(&root);
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].outcome.label, "Run");
+        assert_eq!(loaded[0].outcome.duration, std::time::Duration::from_millis(1500));
+        assert_eq!(loaded[0].log.len(), 2);
+    }
+}
@@ -111,11 +111,14 @@ pub fn material_hash(def: &MaterialDef) -> u64 {
     h.finish()
 }
 
+/// Realize the materials that changed since `cache` was filled. A material
+/// that fails is left out of the cache, so it is retried next time, and the
+/// rest still go through; the failures are returned.
 pub fn reconcile(
     project: &ProjectState,
     app_state: &mut AppState,
     cache: &mut HashMap<Uuid, u64>,
-) -> Result<(), RealizeError> {
+) -> Result<Vec<(Uuid, RealizeError)>, RealizeError> {
     let Some(display) = app_state.display.clone() else {
         return Err(RealizeError::NoDisplay);
     };
@@ -126,12 +129,20 @@ pub fn reconcile(
     app_state.materials.retain(|m| live_uuids.contains(&m.uuid) || m.name.starts_with("INTERNAL::"));
     cache.retain(|uuid, _| live_uuids.contains(uuid));
 
+    let mut failures = Vec::new();
+
     for def in &project.materials {
         let new_hash = material_hash(def);
         let stale = cache.get(&def.uuid).copied() != Some(new_hash);
         if !stale { continue; }
 
-        let mat = realize(def, project, display.clone())?;
+        let mat = match realize(def, project, display.clone()) {
+            Ok(mat) => mat,
+            Err(e) => {
+                failures.push((def.uuid, e));
+                continue;
+            }
+        };
         if let Some(pos) = app_state.materials.iter().position(|m| m.uuid == def.uuid) {
             app_state.materials[pos] = mat;
         } else {
@@ -139,7 +150,7 @@ pub fn reconcile(
         }
         cache.insert(def.uuid, new_hash);
     }
-    Ok(())
+    Ok(failures)
 }
 
 #[derive(Debug)]
@@ -1,6 +1,10 @@
 pub mod resource;
 pub mod scene;
 pub mod material;
+pub mod bindings;
+pub mod script;
+pub mod job_history;
+pub mod run_config;
 
 use std::fs;
 use std::path::Path;
@@ -10,6 +14,8 @@ use std::time::Instant;
 use enigma_3d::{AppState, AppStateSerializer};
 use uuid::Uuid;
 
+use crate::editor::cameras;
+use crate::editor::logging::{self, Source};
 use crate::editor::state::{
     EditorRoot, ProjectLoadJob, ProjectLoadMessage, ProjectLoadPayload, ProjectState, SaveJob,
     SaveMessage, SceneRef,
@@ -43,10 +49,18 @@ pub fn try_new_project(path: &str, app_state: &mut AppState) -> Result<(), Proje
     project.startup_scene_index = 0;
 
     write_project_file(&path, &project)?;
+    bindings::write(&project).map_err(ProjectError::Io)?;
+    script::ensure_runtime(Path::new(&path)).map_err(ProjectError::Io)?;
+    crate::editor::live_link::ensure_runtime(Path::new(&path)).map_err(ProjectError::Io)?;
+    crate::editor::scene_graph::ensure_runtime(Path::new(&path)).map_err(ProjectError::Io)?;
+    script::write_registry(&project).map_err(ProjectError::Io)?;
 
     let root = app_state.get_state_data_value_mut::<EditorRoot>("editor")
         .ok_or(ProjectError::EditorRootMissing)?;
     root.project = Some(project);
+    root.editor.job_history.clear();
+    root.editor.job_history_selected = None;
+    crate::editor::prefs::note_recent(app_state, format!("{path}/{PROJECT_FILE}"), project_name);
     Ok(())
 }
 
@@ -74,7 +88,7 @@ pub fn try_open_project(path: &str, app_state: &mut AppState) -> Result<(), Proj
 
     if let Some(path) = active_scene_path {
         if let Err(e) = inject_scene_file(app_state, &path) {
-            eprintln!("warning: failed to load active scene: {e}");
+            logging::error(Source::Scene, format!("Could not load the active scene: {e}"));
         }
     }
 
@@ -88,12 +102,16 @@ fn inject_scene_file(app_state: &mut AppState, path: &Path) -> Result<(), Projec
     let text = fs::read_to_string(path).map_err(ProjectError::Io)?;
     if text.trim().is_empty() || text.trim() == "{}" {
         clear_scene(app_state);
+        let view = cameras::take_view(app_state);
+        scene::apply_extras(app_state, view, Default::default());
         return Ok(());
     }
     let serializer: AppStateSerializer = serde_json::from_str(&text).map_err(ProjectError::Parse)?;
     let display = app_state.display.clone().ok_or(ProjectError::NoDisplay)?;
     clear_scene(app_state);
+    let view = cameras::take_view(app_state);
     app_state.inject_serializer(serializer, display, false);
+    scene::apply_extras(app_state, view, scene::read_extras(&text));
     Ok(())
 }
 
@@ -148,9 +166,12 @@ pub fn start_open_project(path: &str, app_state: &mut AppState) {
             None
         };
 
+        let job_history = job_history::load(&root_dir);
+
         let _ = tx.send(ProjectLoadMessage::Done(Ok(ProjectLoadPayload {
             project,
             scene_text,
+            job_history,
         })));
     });
 
@@ -196,7 +217,7 @@ pub fn poll_project_load(app_state: &mut AppState) {
 
     match result {
         Err(e) => {
-            eprintln!("open project failed: {e}");
+            logging::error(Source::Project, format!("Opening the project failed: {e}"));
             if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
                 r.editor.project_load = None;
             }
@@ -211,9 +232,14 @@ pub fn poll_project_load(app_state: &mut AppState) {
 }
 
 fn apply_load_payload(app_state: &mut AppState, payload: ProjectLoadPayload) {
+    let project_file = format!("{}/{PROJECT_FILE}", payload.project.root_path);
+    logging::info(Source::Project, format!("Opened {project_file}"));
+    crate::editor::prefs::note_recent(app_state, project_file, payload.project.name.clone());
     if let Some(r) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
         r.editor.material_cache.clear();
         r.editor.applied_skybox = None;
+        r.editor.job_history = payload.job_history;
+        r.editor.job_history_selected = None;
         r.project = Some(payload.project);
     }
 
@@ -221,27 +247,37 @@ fn apply_load_payload(app_state: &mut AppState, payload: ProjectLoadPayload) {
     let trimmed = scene_text.trim();
     if trimmed.is_empty() || trimmed == "{}" {
         clear_scene(app_state);
+        let view = cameras::take_view(app_state);
+        scene::apply_extras(app_state, view, Default::default());
         return;
     }
     let serializer: AppStateSerializer = match serde_json::from_str(&scene_text) {
         Ok(s) => s,
         Err(e) => {
-            eprintln!("scene parse on open: {e}");
+            logging::error(Source::Scene, format!("Could not parse the scene: {e}"));
             return;
         }
     };
     let Some(display) = app_state.display.clone() else { return; };
     clear_scene(app_state);
+    let view = cameras::take_view(app_state);
     app_state.inject_serializer(serializer, display, false);
+    scene::apply_extras(app_state, view, scene::read_extras(&scene_text));
 }
 
 pub fn start_save_scene_and_project(app_state: &mut AppState) {
+    if crate::editor::play::is_playing(app_state) {
+        logging::warn(Source::Save, "Stop play mode before saving");
+        return;
+    }
+    bindings::refresh_scene_objects(app_state);
     let Some(project) = app_state.get_state_data_value::<EditorRoot>("editor")
         .and_then(|r| r.project.clone()) else { return; };
     let Some(scene) = project.scenes.get(project.active_scene_index).cloned() else { return; };
 
     // to_serializer must run on the main thread (touches AppState).
-    let serializer = app_state.to_serializer();
+    let serializer = crate::editor::visibility::serialize(app_state);
+    let extras = scene::extras(app_state);
 
     let (tx, rx) = mpsc::channel();
     let started_at = Instant::now();
@@ -252,7 +288,7 @@ pub fn start_save_scene_and_project(app_state: &mut AppState) {
 
     thread::spawn(move || {
         let _ = tx.send(SaveMessage::Status(format!("serializing scene '{scene_name}'")));
-        let text = match serde_json::to_string_pretty(&serializer) {
+        let text = match scene::to_json(&serializer, &extras) {
             Ok(t) => t,
             Err(e) => {
                 let _ = tx.send(SaveMessage::Done(Err(format!("scene serialize: {e}"))));
@@ -279,6 +315,17 @@ pub fn start_save_scene_and_project(app_state: &mut AppState) {
             return;
         }
 
+        let _ = tx.send(SaveMessage::Status("generating asset bindings".into()));
+        if let Err(e) = bindings::write(&project_clone) {
+            let _ = tx.send(SaveMessage::Done(Err(format!("bindings write: {e}"))));
+            return;
+        }
+        let _ = tx.send(SaveMessage::Status("generating script registry".into()));
+        if let Err(e) = script::write_registry(&project_clone) {
+            let _ = tx.send(SaveMessage::Done(Err(format!("script registry write: {e}"))));
+            return;
+        }
+
         let _ = tx.send(SaveMessage::Done(Ok(())));
     });
 
@@ -293,6 +340,10 @@ pub fn start_save_scene_and_project(app_state: &mut AppState) {
 }
 
 pub fn start_save_project_only(app_state: &mut AppState) {
+    if crate::editor::play::is_playing(app_state) {
+        logging::warn(Source::Save, "Stop play mode before saving");
+        return;
+    }
     let Some(project) = app_state.get_state_data_value::<EditorRoot>("editor")
         .and_then(|r| r.project.clone()) else { return; };
 
@@ -314,6 +365,17 @@ pub fn start_save_project_only(app_state: &mut AppState) {
             let _ = tx.send(SaveMessage::Done(Err(format!("project write: {e}"))));
             return;
         }
+
+        let _ = tx.send(SaveMessage::Status("generating asset bindings".into()));
+        if let Err(e) = bindings::write(&project) {
+            let _ = tx.send(SaveMessage::Done(Err(format!("bindings write: {e}"))));
+            return;
+        }
+        let _ = tx.send(SaveMessage::Status("generating script registry".into()));
+        if let Err(e) = script::write_registry(&project) {
+            let _ = tx.send(SaveMessage::Done(Err(format!("script registry write: {e}"))));
+            return;
+        }
         let _ = tx.send(SaveMessage::Done(Ok(())));
     });
 
@@ -361,9 +423,10 @@ pub fn poll_save_job(app_state: &mut AppState) {
         match res {
             Ok(()) => {
                 r.editor.dirty = false;
+                logging::info(Source::Save, "Saved");
             }
             Err(e) => {
-                eprintln!("save failed: {e}");
+                logging::error(Source::Save, format!("Save failed: {e}"));
             }
         }
         r.editor.save_job = None;
@@ -392,7 +455,8 @@ fn check_empty_directory(path: &str) -> Result<bool, ProjectError> {
 fn create_folder_struct(path: &str, project_name: &str) -> Result<(), ProjectError> {
     let project_dir = Path::new(path);
     for sub in ["src/resources/textures", "src/resources/models", "src/resources/shader",
-                "src/resources/scenes", "src/resources/audio", "src/resources/other"] {
+                "src/resources/scenes", "src/resources/audio", "src/resources/other",
+                "src/scripts"] {
         fs::create_dir_all(project_dir.join(sub)).map_err(ProjectError::Io)?;
     }
 
@@ -0,0 +1,143 @@
+use std::path::{Path, PathBuf};
+
+use crate::editor::state::{ProjectState, RunConfig, RunScene};
+
+/// Environment variable the game template reads to load a scene file other
+/// than the embedded startup scene.
+pub const SCENE_ENV: &str = "ENIGMA_SCENE";
+
+/// Cargo arguments for `cargo run` with `config`, `extra` (e.g. the message
+/// format flag) placed before the `--` that starts the program arguments.
+pub fn cargo_args(config: &RunConfig, extra: &[&str]) -> Vec<String> {
+    let mut args = vec!["run".to_string()];
+    let profile = config.profile.trim();
+    if !profile.is_empty() {
+        args.push("--profile".into());
+        args.push(profile.into());
+    }
+    let features: Vec<&str> = config.features
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|f| !f.is_empty())
+        .collect();
+    if !features.is_empty() {
+        args.push("--features".into());
+        args.push(features.join(","));
+    }
+    args.extend(extra.iter().map(|s| s.to_string()));
+    let program_args = split_args(&config.args);
+    if !program_args.is_empty() {
+        args.push("--".into());
+        args.extend(program_args);
+    }
+    args
+}
+
+/// Environment for the cargo process: the configured variables plus the
+/// scene override, if the config launches something other than startup.
+pub fn environment(config: &RunConfig, project: &ProjectState) -> Vec<(String, String)> {
+    let mut env: Vec<(String, String)> = config.env.iter()
+        .filter(|(k, _)| !k.trim().is_empty())
+        .cloned()
+        .collect();
+    if let Some(path) = scene_path(config, project) {
+        env.push((SCENE_ENV.into(), path.to_string_lossy().into_owned()));
+    }
+    env
+}
+
+fn scene_path(config: &RunConfig, project: &ProjectState) -> Option<PathBuf> {
+    let scene = match config.scene {
+        RunScene::Startup => return None,
+        RunScene::Active => project.scenes.get(project.active_scene_index)?,
+        RunScene::Scene(uuid) => project.scenes.iter().find(|s| s.uuid == uuid)?,
+    };
+    Some(Path::new(&project.root_path).join("src/resources").join(&scene.relative_path))
+}
+
+/// Split program arguments on whitespace; single or double quotes group
+/// words and a backslash escapes the next character.
+pub fn split_args(text: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', _) => {
+                if let Some(next) = chars.next() { current.push(next); }
+                in_word = true;
+            }
+            ('"' | '\'', None) => { quote = Some(c); in_word = true; }
+            (c, Some(q)) if c == q => quote = None,
+            (c, None) if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (c, _) => { current.push(c); in_word = true; }
+        }
+    }
+    if in_word {
+        args.push(current);
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor::state::SceneRef;
+    use uuid::Uuid;
+
+    #[test]
+    fn split_args_handles_quotes_and_escapes() {
+        assert_eq!(split_args("  --level 3 "), vec!["--level", "3"]);
+        assert_eq!(split_args(r#"--name "Big Boss" 'a b' c\ d"#), vec!["--name", "Big Boss", "a b", "c d"]);
+        assert_eq!(split_args(r#"--empty """#), vec!["--empty", ""]);
+        assert!(split_args("   ").is_empty());
+    }
+
+    #[test]
+    fn cargo_args_orders_flags_before_program_args() {
+        let mut config = RunConfig::new("fast".into());
+        config.profile = "release".into();
+        config.features = "debug-overlay, god_mode".into();
+        config.args = "--fullscreen".into();
+        assert_eq!(
+            cargo_args(&config, &["--message-format=json"]),
+            vec!["run", "--profile", "release", "--features", "debug-overlay,god_mode",
+                 "--message-format=json", "--", "--fullscreen"],
+        );
+        assert_eq!(cargo_args(&RunConfig::new("plain".into()), &[]), vec!["run"]);
+    }
+
+    #[test]
+    fn environment_adds_scene_override() {
+        let mut project = ProjectState::new("t".into(), "/p".into());
+        let level = Uuid::new_v4();
+        for (uuid, name) in [(Uuid::new_v4(), "main"), (level, "level2")] {
+            project.scenes.push(SceneRef {
+                uuid,
+                name: name.into(),
+                relative_path: format!("scenes/{name}.json"),
+                terrain: None,
+                particle_instances: Vec::new(),
+            });
+        }
+        let mut config = RunConfig::new("c".into());
+        config.env.push(("RUST_LOG".into(), "debug".into()));
+        assert_eq!(environment(&config, &project), vec![("RUST_LOG".to_string(), "debug".to_string())]);
+
+        config.scene = RunScene::Scene(level);
+        let env = environment(&config, &project);
+        let (key, path) = &env[1];
+        assert_eq!(key, SCENE_ENV);
+        assert!(path.ends_with("src/resources/scenes/level2.json"));
+
+        project.active_scene_index = 0;
+        config.scene = RunScene::Active;
+        assert!(environment(&config, &project)[1].1.ends_with("scenes/main.json"));
+    }
+}
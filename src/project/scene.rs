@@ -1,16 +1,69 @@
 use std::fs;
 use std::path::Path;
 use enigma_3d::{AppState, AppStateSerializer};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::editor::state::{ProjectState, SceneRef};
+use crate::editor::cameras;
+use crate::editor::state::{CameraBookmark, CameraPose, EditorRoot, LightMeta, ProjectState, RunScene, SceneRef};
 
-pub fn save_active(project: &ProjectState, app_state: &AppState) -> Result<(), SceneError> {
+/// A scene file as written by the editor: the engine's data plus editor-only
+/// keys, which the game's loader ignores.
+#[derive(Serialize)]
+struct SceneFileOut<'a> {
+    #[serde(flatten)]
+    scene: &'a AppStateSerializer,
+    #[serde(flatten)]
+    extras: &'a SceneExtras,
+}
+
+/// The editor-only keys of a scene file.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct SceneExtras {
+    #[serde(default)]
+    pub editor_lights: Vec<LightMeta>,
+    #[serde(default)]
+    pub editor_camera: Option<CameraPose>,
+    #[serde(default)]
+    pub editor_bookmarks: Vec<CameraBookmark>,
+}
+
+pub fn to_json(scene: &AppStateSerializer, extras: &SceneExtras) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&SceneFileOut { scene, extras })
+}
+
+/// Editor keys of `text`; scenes saved before they existed have none.
+pub fn read_extras(text: &str) -> SceneExtras {
+    serde_json::from_str(text).unwrap_or_default()
+}
+
+/// The editor keys for the scene currently loaded.
+pub fn extras(app_state: &AppState) -> SceneExtras {
+    let root = app_state.get_state_data_value::<EditorRoot>("editor");
+    SceneExtras {
+        editor_lights: root.map(|r| r.editor.lights.clone()).unwrap_or_default(),
+        editor_camera: cameras::editor_pose(app_state),
+        editor_bookmarks: root.map(|r| r.editor.bookmarks.clone()).unwrap_or_default(),
+    }
+}
+
+/// Finish a scene load: `view` is the editor camera taken out before the
+/// scene was injected.
+pub fn apply_extras(app_state: &mut AppState, view: Option<enigma_3d::camera::Camera>, extras: SceneExtras) {
+    crate::editor::lights::load(app_state, extras.editor_lights);
+    cameras::load(app_state, view, extras.editor_camera);
+    if let Some(root) = app_state.get_state_data_value_mut::<EditorRoot>("editor") {
+        root.editor.bookmarks = extras.editor_bookmarks;
+        root.editor.camera_transition = None;
+    }
+}
+
+pub fn save_active(project: &ProjectState, app_state: &mut AppState) -> Result<(), SceneError> {
     let scene = project.scenes.get(project.active_scene_index).ok_or(SceneError::NoActiveScene)?;
     let path = scene_path(project, scene);
-    let mut serializer = app_state.to_serializer();
+    let mut serializer = crate::editor::visibility::serialize(app_state);
     normalize_animation_times(&mut serializer.objects);
-    let text = serde_json::to_string_pretty(&serializer).map_err(SceneError::Parse)?;
+    let text = to_json(&serializer, &extras(app_state)).map_err(SceneError::Parse)?;
     fs::write(&path, text).map_err(SceneError::Io)?;
     Ok(())
 }
@@ -20,6 +73,13 @@ pub fn switch(
     app_state: &mut AppState,
     target_index: usize,
 ) -> Result<(), SceneError> {
+    if crate::editor::play::is_playing(app_state) {
+        return Err(SceneError::Playing);
+    }
+    if let Some(scene) = project.scenes.get(project.active_scene_index).map(|s| s.uuid) {
+        let objects = crate::editor::visibility::all_objects(app_state);
+        project.set_scene_objects(scene, &objects);
+    }
     save_active(project, app_state).ok();   // best-effort
 
     let scene = project.scenes.get(target_index).ok_or(SceneError::BadIndex)?.clone();
@@ -28,12 +88,17 @@ pub fn switch(
     let trimmed = text.trim();
 
     let display = app_state.display.clone().ok_or(SceneError::NoDisplay)?;
+    let serializer: Option<AppStateSerializer> = if !trimmed.is_empty() && trimmed != "{}" {
+        Some(serde_json::from_str(&text).map_err(SceneError::Parse)?)
+    } else {
+        None
+    };
     clear_scene(app_state);
-
-    if !trimmed.is_empty() && trimmed != "{}" {
-        let serializer: AppStateSerializer = serde_json::from_str(&text).map_err(SceneError::Parse)?;
+    let view = cameras::take_view(app_state);
+    if let Some(serializer) = serializer {
         app_state.inject_serializer(serializer, display, /*additive=*/false);
     }
+    apply_extras(app_state, view, read_extras(&text));
     project.active_scene_index = target_index;
     Ok(())
 }
@@ -65,6 +130,14 @@ pub fn delete(project: &mut ProjectState, index: usize) -> Result<(), SceneError
         return Err(SceneError::BadIndex);
     }
     let scene = project.scenes.remove(index);
+    project.scene_objects.retain(|o| o.scene != scene.uuid);
+    project.script_components.retain(|c| c.scene != scene.uuid);
+    project.object_parents.retain(|l| l.scene != scene.uuid);
+    for config in project.run_configs.iter_mut() {
+        if config.scene == RunScene::Scene(scene.uuid) {
+            config.scene = RunScene::Startup;
+        }
+    }
     let path = Path::new(&project.root_path).join("src/resources").join(&scene.relative_path);
     if path.exists() {
         let trash_dir = Path::new(&project.root_path).join(".trash");
@@ -117,12 +190,44 @@ pub enum SceneError {
     AlreadyExists,
     CannotDeleteLast,
     NoDisplay,
+    Playing,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn read_extras_tolerates_missing_and_reads_lights() {
+        assert!(read_extras(r#"{"objects": []}"#).editor_lights.is_empty());
+        assert!(read_extras("{}").editor_lights.is_empty());
+        let uuid = Uuid::new_v4();
+        let text = format!(r#"{{"objects": [], "editor_lights": [{{"uuid": "{uuid}", "name": "Key"}}]}}"#);
+        let extras = read_extras(&text);
+        assert_eq!(extras.editor_lights, vec![LightMeta { uuid, name: "Key".into() }]);
+        assert_eq!(extras.editor_camera, None);
+    }
+
+    #[test]
+    fn extras_round_trip_next_to_scene_keys() {
+        let extras = SceneExtras {
+            editor_lights: Vec::new(),
+            editor_camera: Some(CameraPose { position: [1.0, 2.0, 3.0], rotation: [0.1, 0.2, 0.0] }),
+            editor_bookmarks: vec![CameraBookmark {
+                slot: 2,
+                name: "Gate".into(),
+                pose: CameraPose { position: [0.0; 3], rotation: [0.0; 3] },
+                projection: crate::editor::state::Projection::Orthographic,
+                focus_distance: 4.0,
+            }],
+        };
+        let mut value = serde_json::to_value(&extras).unwrap();
+        value["objects"] = serde_json::json!([]);
+        let back = read_extras(&value.to_string());
+        assert_eq!(back.editor_camera, extras.editor_camera);
+        assert_eq!(back.editor_bookmarks, extras.editor_bookmarks);
+    }
+
     fn make_project(tmp: &Path) -> ProjectState {
         let p = ProjectState::new("t".into(), tmp.to_string_lossy().into_owned());
         fs::create_dir_all(tmp.join("src/resources/scenes")).unwrap();
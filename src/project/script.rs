@@ -0,0 +1,264 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::editor::state::{ProjectState, ScriptDef, ScriptValue};
+
+/// Directory of the behaviour sources, relative to the project root.
+pub const SCRIPTS_DIR: &str = "src/scripts";
+/// Generated registration code; declared as `mod scripts;` in the game's `main.rs`.
+pub const REGISTRY_FILE: &str = "src/scripts/mod.rs";
+/// Runtime support module, written once and then owned by the game.
+pub const RUNTIME_FILE: &str = "src/enigma_scripts.rs";
+
+const HEADER: &str = "\
+// @generated by enigma-engine from enigma_project.json.
+// Regenerated on every save and build; manual edits will be lost.
+#![allow(dead_code, unused_imports, unused_variables)]
+";
+
+/// Scaffold a new behaviour `name` in the game crate and register it in the
+/// project. An existing source file with the same name is adopted instead of
+/// overwritten so no user code is lost.
+pub fn create(project: &mut ProjectState, name: &str) -> Result<Uuid, ScriptError> {
+    let type_name = type_ident(name).ok_or(ScriptError::BadName)?;
+    let stem = file_stem(&type_name);
+    // The stem becomes a `pub mod` in the registry.
+    if super::bindings::is_keyword(&stem) {
+        return Err(ScriptError::BadName);
+    }
+    if project.scripts.iter().any(|s| file_stem(&s.name) == stem) {
+        return Err(ScriptError::AlreadyExists);
+    }
+
+    let root = Path::new(&project.root_path);
+    ensure_runtime(root).map_err(ScriptError::Io)?;
+    let relative_path = format!("{SCRIPTS_DIR}/{stem}.rs");
+    let target = root.join(&relative_path);
+    if !target.exists() {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(ScriptError::Io)?;
+        }
+        let source = crate::resources::SCRIPT_RS.replace("ENIGMA_SCRIPT_NAME", &type_name);
+        fs::write(&target, source).map_err(ScriptError::Io)?;
+    }
+
+    let uuid = Uuid::new_v4();
+    project.scripts.push(ScriptDef { uuid, name: type_name, relative_path, fields: Vec::new() });
+    write_registry(project).map_err(ScriptError::Io)?;
+    Ok(uuid)
+}
+
+/// Unregister a behaviour, detach it from every object and move its source
+/// file to `.trash`.
+pub fn delete(project: &mut ProjectState, uuid: Uuid) -> Result<(), ScriptError> {
+    let idx = project.scripts.iter().position(|s| s.uuid == uuid)
+        .ok_or(ScriptError::NotFound)?;
+    let def = project.scripts.remove(idx);
+    project.script_components.retain(|c| c.script != uuid);
+
+    let src = Path::new(&project.root_path).join(&def.relative_path);
+    if src.exists() {
+        let trash_dir = Path::new(&project.root_path).join(".trash");
+        fs::create_dir_all(&trash_dir).map_err(ScriptError::Io)?;
+        let file_name = def.relative_path.rsplit('/').next().unwrap_or("script.rs");
+        fs::rename(&src, trash_dir.join(format!("script_{file_name}"))).map_err(ScriptError::Io)?;
+    }
+    write_registry(project).map_err(ScriptError::Io)?;
+    Ok(())
+}
+
+/// Write the runtime module if the project doesn't have one yet.
+pub fn ensure_runtime(root: &Path) -> std::io::Result<()> {
+    let path = root.join(RUNTIME_FILE);
+    if path.exists() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, crate::resources::SCRIPT_RUNTIME_RS)
+}
+
+/// Render `scripts/mod.rs`: one `pub mod` per behaviour, the `register`
+/// function and the component table for every scene.
+pub fn generate_registry(project: &ProjectState) -> String {
+    let mut out = String::from(HEADER);
+    out.push_str("\nuse crate::enigma_scripts::{ComponentData, FieldValue, Registry};\n\n");
+    for def in &project.scripts {
+        let _ = writeln!(out, "pub mod {};", file_stem(&def.name));
+    }
+
+    out.push_str("\npub fn register(registry: &mut Registry) {\n");
+    for def in &project.scripts {
+        let _ = writeln!(
+            out,
+            "    registry.register({:?}, |fields| Box::new({}::{}::from_fields(fields)));",
+            def.name, file_stem(&def.name), def.name,
+        );
+    }
+    out.push_str("}\n");
+
+    out.push_str("\npub static COMPONENTS: &[ComponentData] = &[\n");
+    for component in &project.script_components {
+        let Some(def) = project.scripts.iter().find(|s| s.uuid == component.script) else { continue; };
+        let fields: Vec<String> = component.resolved_fields(def).iter()
+            .map(|f| format!("({:?}, {})", f.name, value_literal(&f.value)))
+            .collect();
+        let _ = writeln!(
+            out,
+            "    ComponentData {{ object: {:?}, script: {:?}, fields: &[{}] }},",
+            component.object.to_string(), def.name, fields.join(", "),
+        );
+    }
+    out.push_str("];\n");
+    out
+}
+
+/// Regenerate the registry. Skips the write when unchanged so cargo doesn't
+/// rebuild. Returns whether it wrote.
+pub fn write_registry(project: &ProjectState) -> std::io::Result<bool> {
+    let path = Path::new(&project.root_path).join(REGISTRY_FILE);
+    let text = generate_registry(project);
+    if fs::read_to_string(&path).map(|old| old == text).unwrap_or(false) {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, text)?;
+    Ok(true)
+}
+
+fn value_literal(value: &ScriptValue) -> String {
+    // Debug formatting of finite floats is a valid Rust literal ("1.0").
+    let float = |v: f32| if v.is_finite() { format!("{v:?}") } else { "0.0".into() };
+    match value {
+        ScriptValue::Float(v) => format!("FieldValue::Float({})", float(*v)),
+        ScriptValue::Int(v) => format!("FieldValue::Int({v})"),
+        ScriptValue::Bool(v) => format!("FieldValue::Bool({v})"),
+        ScriptValue::Text(v) => format!("FieldValue::Text({v:?})"),
+        ScriptValue::Vec3(v) => format!("FieldValue::Vec3([{}, {}, {}])", float(v[0]), float(v[1]), float(v[2])),
+    }
+}
+
+/// `"player controller"` -> `PlayerController`. None if nothing usable is left
+/// or the result would start with a digit.
+pub fn type_ident(name: &str) -> Option<String> {
+    let mut out = String::new();
+    for word in name.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+            out.extend(chars);
+        }
+    }
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(out)
+}
+
+/// `PlayerController` -> `player_controller`.
+pub fn file_stem(type_name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in type_name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 { out.push('_'); }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[derive(Debug)]
+pub enum ScriptError {
+    Io(std::io::Error),
+    BadName,
+    AlreadyExists,
+    NotFound,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor::state::{ScriptComponent, ScriptField};
+
+    #[test]
+    fn type_ident_and_file_stem() {
+        assert_eq!(type_ident("player controller").as_deref(), Some("PlayerController"));
+        assert_eq!(type_ident("Rotator").as_deref(), Some("Rotator"));
+        assert_eq!(type_ident("2fast"), None);
+        assert_eq!(type_ident("!!"), None);
+        assert_eq!(file_stem("PlayerController"), "player_controller");
+        assert_eq!(file_stem("Rotator"), "rotator");
+    }
+
+    #[test]
+    fn create_scaffolds_source_and_registry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut p = ProjectState::new("t".into(), tmp.path().to_string_lossy().into_owned());
+        let uuid = create(&mut p, "rotator").unwrap();
+        assert_eq!(p.scripts[0].uuid, uuid);
+        assert_eq!(p.scripts[0].name, "Rotator");
+        let source = fs::read_to_string(tmp.path().join("src/scripts/rotator.rs")).unwrap();
+        assert!(source.contains("pub struct Rotator"));
+        assert!(tmp.path().join(RUNTIME_FILE).is_file());
+        let registry = fs::read_to_string(tmp.path().join(REGISTRY_FILE)).unwrap();
+        assert!(registry.contains("pub mod rotator;"));
+        assert!(registry.contains("registry.register(\"Rotator\""));
+        assert!(matches!(create(&mut p, "Rotator"), Err(ScriptError::AlreadyExists)));
+        assert!(matches!(create(&mut p, "type"), Err(ScriptError::BadName)));
+    }
+
+    #[test]
+    fn registry_lists_components_with_resolved_fields() {
+        let mut p = ProjectState::new("t".into(), "/tmp/t".into());
+        let script = Uuid::new_v4();
+        p.scripts.push(ScriptDef {
+            uuid: script,
+            name: "Rotator".into(),
+            relative_path: "src/scripts/rotator.rs".into(),
+            fields: vec![
+                ScriptField { name: "speed".into(), value: ScriptValue::Float(1.0) },
+                ScriptField { name: "axis".into(), value: ScriptValue::Vec3([0.0, 1.0, 0.0]) },
+            ],
+        });
+        let object = Uuid::new_v4();
+        p.script_components.push(ScriptComponent {
+            scene: Uuid::new_v4(),
+            object,
+            script,
+            fields: vec![ScriptField { name: "speed".into(), value: ScriptValue::Float(2.5) }],
+        });
+        // Components pointing at a deleted script are skipped.
+        p.script_components.push(ScriptComponent {
+            scene: Uuid::new_v4(), object, script: Uuid::new_v4(), fields: Vec::new(),
+        });
+
+        let text = generate_registry(&p);
+        assert!(text.contains(&format!("object: \"{object}\", script: \"Rotator\"")));
+        assert!(text.contains("(\"speed\", FieldValue::Float(2.5))"));
+        assert!(text.contains("(\"axis\", FieldValue::Vec3([0.0, 1.0, 0.0]))"));
+        assert_eq!(text.matches("ComponentData {").count(), 1);
+    }
+
+    #[test]
+    fn delete_detaches_and_trashes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut p = ProjectState::new("t".into(), tmp.path().to_string_lossy().into_owned());
+        let uuid = create(&mut p, "Mover").unwrap();
+        p.script_components.push(ScriptComponent {
+            scene: Uuid::new_v4(), object: Uuid::new_v4(), script: uuid, fields: Vec::new(),
+        });
+        delete(&mut p, uuid).unwrap();
+        assert!(p.scripts.is_empty());
+        assert!(p.script_components.is_empty());
+        assert!(tmp.path().join(".trash/script_mover.rs").is_file());
+        let registry = fs::read_to_string(tmp.path().join(REGISTRY_FILE)).unwrap();
+        assert!(!registry.contains("mover"));
+    }
+}
@@ -1,3 +1,7 @@
 pub const CARGO_TOML: &str = include_str!("Cargo.toml.resource");
 pub const MAIN_RS: &str = include_str!("main.rs.resource");
+pub const SCRIPT_RUNTIME_RS: &str = include_str!("enigma_scripts.rs.resource");
+pub const SCRIPT_RS: &str = include_str!("script.rs.resource");
+pub const LIVE_LINK_RS: &str = include_str!("enigma_live_link.rs.resource");
+pub const HIERARCHY_RS: &str = include_str!("enigma_hierarchy.rs.resource");
 pub const ICON: &'static [u8] = include_bytes!("icon.png.resource");